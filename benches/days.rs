@@ -0,0 +1,99 @@
+// Benchmarks each day's parse, part 1 and part 2 on the example from its
+// puzzle text in tests/data/, so the numbers don't depend on whose input it is.
+//
+//     cargo bench --bench days -- day15/part2
+
+use aoc2021::core::input;
+use aoc2021::core::registry;
+use aoc2021::core::runner::Solution;
+use aoc2021::y2021::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+    snailfish::Number,
+};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+
+fn bench_day<S: Solution>(c: &mut Criterion) {
+    let day = registry::find(S::YEAR, S::DAY).unwrap();
+    let data = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data");
+    // Days with several examples have them numbered; take the first.
+    let path = Some(day.input_path(&data))
+        .filter(|path| path.exists())
+        .unwrap_or_else(|| data.join(format!("{}-1.txt", day.name)));
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => input::normalize(&text),
+        Err(_) => {
+            eprintln!("Skipping {}: no example in {}", day.name, data.display());
+            return;
+        }
+    };
+    let parse = || S::parse(text.as_bytes()).unwrap();
+
+    let mut group = c.benchmark_group(day.name);
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(text.as_bytes()))));
+    // Parse afresh for every run, since some days cache work in their input.
+    group.bench_function("part1", |b| {
+        b.iter_batched(parse, |input| S::part1(&input), BatchSize::SmallInput)
+    });
+    group.bench_function("part2", |b| {
+        b.iter_batched(parse, |input| S::part2(&input), BatchSize::SmallInput)
+    });
+    group.finish();
+}
+
+fn bench_days(c: &mut Criterion) {
+    bench_day::<day01::Day01>(c);
+    bench_day::<day02::Day02>(c);
+    bench_day::<day03::Day03>(c);
+    bench_day::<day04::Day04>(c);
+    bench_day::<day05::Day05>(c);
+    bench_day::<day06::Day06>(c);
+    bench_day::<day07::Day07>(c);
+    bench_day::<day08::Day08>(c);
+    bench_day::<day09::Day09>(c);
+    bench_day::<day10::Day10>(c);
+    bench_day::<day11::Day11>(c);
+    bench_day::<day12::Day12>(c);
+    bench_day::<day13::Day13>(c);
+    bench_day::<day14::Day14>(c);
+    bench_day::<day15::Day15>(c);
+    bench_day::<day16::Day16>(c);
+    bench_day::<day17::Day17>(c);
+    bench_day::<day18::Day18>(c);
+    bench_day::<day19::Day19>(c);
+    bench_day::<day20::Day20>(c);
+    bench_day::<day21::Day21>(c);
+    bench_day::<day22::Day22>(c);
+    bench_day::<day23::Day23>(c);
+    bench_day::<day24::Day24>(c);
+    bench_day::<day25::Day25>(c);
+}
+
+// Adding up the example's snailfish numbers, without the rest of day 18.
+fn bench_snailfish(c: &mut Criterion) {
+    let numbers = include_str!("../tests/data/day18.txt")
+        .lines()
+        .map(|line| line.parse::<Number>().unwrap())
+        .collect::<Vec<_>>();
+
+    c.bench_function("snailfish/sum", |b| {
+        b.iter_batched(
+            || numbers.clone(),
+            |numbers| numbers.into_iter().sum::<Number>(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group! {
+    name = benches;
+    // Some parts take a while even on the examples.
+    config = Criterion::default().sample_size(10);
+    targets = bench_days, bench_snailfish
+}
+criterion_main!(benches);
@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc2021::y2021::day19;
+use aoc2021::core::runner::Solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = day19::Day19::parse(data);
+});
@@ -0,0 +1,40 @@
+/* C interface to the Advent of Code 2021 solvers in libaoc2021.  See
+ * src/ffi.rs for the details. */
+#ifndef AOC2021_H
+#define AOC2021_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define AOC2021_OK 0
+#define AOC2021_NO_SUCH_DAY 1
+#define AOC2021_NO_SUCH_PART 2
+#define AOC2021_INVALID_UTF8 3
+/* The error message is written to the output buffer, cut short if need be. */
+#define AOC2021_INVALID_INPUT 4
+/* *out_len is set to the length needed and nothing is written. */
+#define AOC2021_BUFFER_TOO_SMALL 5
+#define AOC2021_NULL_POINTER 6
+#define AOC2021_PANIC 7
+/* The input parsed but has no answer, or the search for one gave up.  The
+ * message is written as for AOC2021_INVALID_INPUT, as it is for the next. */
+#define AOC2021_NO_SOLUTION 8
+#define AOC2021_FAILURE 9
+
+/* Solves part (1 or 2) of day for the len bytes of UTF-8 at input_ptr.
+ * out_buf has room for *out_len bytes; the answer is written there, without
+ * a terminating NUL, and *out_len set to its length.  Numbers come back as
+ * digits and a part with no answer as an empty string.  Returns one of the
+ * AOC2021_ codes above. */
+int32_t aoc2021_solve(uint32_t day, uint32_t part, const uint8_t *input_ptr, size_t len,
+                      uint8_t *out_buf, size_t *out_len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
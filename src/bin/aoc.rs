@@ -0,0 +1,226 @@
+use aoc2021::core::config::Config;
+use aoc2021::core::error::{exit_code, AocError, Result};
+use aoc2021::core::logging;
+use aoc2021::core::registry::{self, Day, Year, DEFAULT_YEAR};
+use aoc2021::core::report::{self, Format, Row};
+use aoc2021::core::runner::{self, Answers};
+use aoc2021::core::scaffold;
+use aoc2021::core::serve;
+use aoc2021::core::watch::Watcher;
+use aoc2021::y2021::gen::{self, Generator};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::Duration;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+enum Opt {
+    /// Run every day against its input and print a summary table
+    All {
+        /// Which year's days to run [default: year from aoc2021.toml, or 2021]
+        #[structopt(long)]
+        year: Option<u32>,
+
+        /// Directory holding the dayNN.txt inputs [default: input_dir from
+        /// aoc2021.toml, or input, with /YYYY after it for years but 2021]
+        #[structopt(long, parse(from_os_str))]
+        input_dir: Option<PathBuf>,
+
+        /// Report format: markdown or csv [default: markdown]
+        #[structopt(long)]
+        format: Option<Format>,
+
+        /// Reuse answers cached under .cache/ for unchanged inputs
+        #[structopt(long)]
+        cache: bool,
+    },
+
+    /// Re-run a day whenever its input or source changes
+    Watch {
+        day: u32,
+
+        /// Which year the day is from [default: year from aoc2021.toml, or
+        /// 2021]
+        #[structopt(long)]
+        year: Option<u32>,
+
+        /// Puzzle input file [default: from aoc2021.toml, or input/dayNN.txt]
+        #[structopt(parse(from_os_str))]
+        input: Option<PathBuf>,
+    },
+
+    /// Print a random input for a day, for seeing how a solution scales
+    Gen {
+        day: u32,
+
+        /// Roughly how big to make it: lines, grid width, ... depending on
+        /// the day [default: about the size of the real input]
+        #[structopt(long)]
+        size: Option<usize>,
+
+        /// Seed for the random number generator [default: 0]
+        #[structopt(long)]
+        seed: Option<u64>,
+    },
+
+    /// Set up a new day: its module and binary, registered with the rest, an
+    /// empty input and an entry in answers.toml to fill in
+    New {
+        day: u32,
+
+        /// Which year the day is from [default: year from aoc2021.toml, or
+        /// 2021]
+        #[structopt(long)]
+        year: Option<u32>,
+    },
+
+    /// Serve the solvers over HTTP: POST an input to /solve/DAY/PART, where
+    /// PART is 1, 2 or all, to get the answers and timings back as JSON
+    Serve {
+        /// Address to listen on [default: 127.0.0.1:8080]
+        #[structopt(long)]
+        addr: Option<String>,
+
+        /// Which year's days to serve [default: year from aoc2021.toml, or
+        /// 2021]
+        #[structopt(long)]
+        year: Option<u32>,
+    },
+}
+
+fn run_all(config: &Config, year: &Year, input_dir: Option<PathBuf>, format: Format, cache: bool) {
+    let mut rows = vec![];
+
+    for day in year.days {
+        let input = match &input_dir {
+            Some(input_dir) => day.input_path(input_dir),
+            None => config.input_path(day.year, day.number),
+        };
+        if !input.exists() {
+            eprintln!(
+                "Skipping day {}: no input at {}",
+                day.number,
+                input.display()
+            );
+            continue;
+        }
+
+        eprintln!("Running day {}", day.number);
+        rows.push(match day.run(&input, cache) {
+            Ok(answers) => Row::Solved(answers),
+            Err(AocError::Day { message, .. }) => Row::Failed {
+                day: day.number,
+                message,
+            },
+            Err(err) => Row::Failed {
+                day: day.number,
+                message: err.to_string(),
+            },
+        });
+    }
+
+    print!("{}", report::render(&rows, format));
+}
+
+fn rebuild_and_run(day: &Day, input: &Path, rebuild: bool) -> Result<Answers> {
+    if rebuild {
+        day.build()?;
+    }
+    day.run(input, false)
+}
+
+fn watch(config: &Config, day: &Day, input: Option<PathBuf>) {
+    let input = input.unwrap_or_else(|| config.input_path(day.year, day.number));
+    let mut watcher = Watcher::new()
+        .file(&input)
+        .file(day.source_path())
+        .source_dir(registry::source_dir())
+        .exclude(registry::source_dir().join("bin"))
+        .start();
+
+    let mut rebuild = false;
+    loop {
+        match rebuild_and_run(day, &input, rebuild) {
+            Ok(answers) => answers.print_text(),
+            Err(err) => eprintln!("{}", err),
+        }
+
+        let changed = watcher.wait_for_change(Duration::from_millis(500));
+        for path in &changed {
+            eprintln!("Changed: {}", path.display());
+        }
+        rebuild = changed.iter().any(|path| path != &input);
+    }
+}
+
+fn exit_with(err: AocError) -> ! {
+    let code = err.exit_code();
+    err.report("aoc", false);
+    process::exit(code)
+}
+
+fn find_year(config: &Config, year: Option<u32>) -> &'static Year {
+    let year = year.or(config.year).unwrap_or(DEFAULT_YEAR);
+    registry::year(year).unwrap_or_else(|| {
+        eprintln!("aoc: no days for year {}", year);
+        process::exit(exit_code::BAD_ARGUMENTS);
+    })
+}
+
+fn main() {
+    let opt: Opt = runner::parse_args();
+
+    let config = Config::load().unwrap_or_else(|err| exit_with(err));
+
+    match opt {
+        Opt::All {
+            year,
+            input_dir,
+            format,
+            cache,
+        } => {
+            let year = find_year(&config, year);
+            let format = format.or(config.report_format).unwrap_or(Format::Markdown);
+            run_all(&config, year, input_dir, format, cache)
+        }
+        Opt::Watch { day, year, input } => {
+            match registry::find(find_year(&config, year).number, day) {
+                Some(day) => watch(&config, day, input),
+                None => {
+                    eprintln!("aoc: no such day {}", day);
+                    process::exit(exit_code::BAD_ARGUMENTS);
+                }
+            }
+        }
+        Opt::Gen { day, size, seed } => {
+            let size = size.unwrap_or_else(|| gen::default_size(day));
+            match Generator::new(seed.unwrap_or(0)).input(day, size) {
+                Some(input) => print!("{}", input),
+                None => {
+                    eprintln!("aoc: no generator for day {}", day);
+                    process::exit(exit_code::BAD_ARGUMENTS);
+                }
+            }
+        }
+        Opt::New { day, year } => {
+            let year = year.or(config.year).unwrap_or(DEFAULT_YEAR);
+            let root = registry::source_dir().parent().unwrap().to_owned();
+            match scaffold::create(&root, &config, year, day) {
+                Ok(changed) => {
+                    for path in changed {
+                        println!("{}", path.strip_prefix(&root).unwrap_or(&path).display());
+                    }
+                }
+                Err(err) => exit_with(err),
+            }
+        }
+        Opt::Serve { addr, year } => {
+            logging::init(1);
+            let year = find_year(&config, year);
+            let addr = addr.unwrap_or_else(|| "127.0.0.1:8080".to_string());
+            let server = serve::listen(&addr).unwrap_or_else(|err| exit_with(err));
+            eprintln!("Listening on http://{}", server.server_addr());
+            serve::serve(server, year)
+        }
+    }
+}
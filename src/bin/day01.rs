@@ -1,39 +1,122 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::{Path, PathBuf};
+use aoc2021::core::runner::{self, Answers, Opt};
+use aoc2021::y2021::day01::{self, Day01, Smoothing};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
-struct Opt {
-    #[structopt(parse(from_os_str))]
-    input: PathBuf,
-}
+struct DayOpt {
+    #[structopt(flatten)]
+    opt: Opt,
+
+    /// Count the increases between sliding windows N depths wide instead of
+    /// running the parts; repeat for more than one width
+    #[structopt(
+        long = "window",
+        value_name = "N",
+        number_of_values = 1,
+        parse(try_from_str = runner::parse_count),
+        conflicts_with_all = runner::RUN_ONLY,
+        conflicts_with = "part"
+    )]
+    windows: Vec<usize>,
+
+    /// Read the depths a line at a time rather than all at once, for inputs
+    /// too big to fit in memory
+    #[structopt(long, conflicts_with = "smooth", conflicts_with_all = runner::RUN_ONLY)]
+    stream: bool,
+
+    /// Smooth out noise before counting, replacing each depth with the median
+    /// or mean of those around it
+    #[structopt(long, value_name = "median|mean", conflicts_with_all = runner::RUN_ONLY)]
+    smooth: Option<Smoothing>,
 
-fn read_depths<P: AsRef<Path>>(path: &P) -> Box<[u64]> {
-    let file = File::open(path).unwrap();
-    let reader = BufReader::new(file);
-    reader
-        .lines()
-        .map(Result::unwrap)
-        .map(|line| line.parse().unwrap())
-        .collect::<Vec<_>>()
-        .into_boxed_slice()
+    /// Print every increase and statistics about them rather than only how
+    /// many there are
+    #[structopt(
+        long,
+        conflicts_with = "stream",
+        conflicts_with_all = runner::RUN_ONLY,
+        conflicts_with = "part"
+    )]
+    report: bool,
+
+    /// How many depths either side to smooth over [default: 1]
+    #[structopt(long, value_name = "N", requires = "smooth")]
+    radius: Option<usize>,
 }
 
-fn count_increases(depths: &[u64], offset: usize) -> usize {
-    depths
-        .iter()
-        .zip(&depths[offset..])
-        .filter(|(before, after)| after > before)
-        .count()
+fn read_depths(day_opt: &DayOpt) -> Vec<u64> {
+    let depths = runner::input::<Day01>(&day_opt.opt).into_vec();
+    match day_opt.smooth {
+        Some(smoothing) => day01::smooth(&depths, smoothing, day_opt.radius.unwrap_or(1)),
+        None => depths,
+    }
 }
 
 fn main() {
-    let opt = Opt::from_args();
+    let day_opt: DayOpt = runner::parse_args();
+    if day_opt.windows.is_empty() && !day_opt.stream && day_opt.smooth.is_none() && !day_opt.report
+    {
+        return runner::main_with::<Day01>(day_opt.opt);
+    }
+
+    let opt = &day_opt.opt;
+    let json = runner::json::<Day01>(opt);
+    // With no windows given, the same answers as the parts.
+    let windows = if day_opt.windows.is_empty() {
+        vec![1, 3]
+    } else {
+        day_opt.windows.clone()
+    };
+
+    let counts = if day_opt.stream {
+        let reader = runner::stream_input::<Day01>(opt);
+        runner::or_exit::<Day01, _>(opt, day01::stream_increases(reader, &windows))
+    } else {
+        let depths = read_depths(&day_opt);
+        if day_opt.report {
+            let reports = windows
+                .iter()
+                .map(|&window| day01::report(&depths, window))
+                .collect::<Vec<_>>();
+            if json {
+                println!("{}", serde_json::to_string(&reports).unwrap());
+            } else {
+                for report in reports {
+                    println!("{}", report);
+                }
+            }
+            return;
+        }
+        windows
+            .iter()
+            .map(|&window| day01::count_increases(&depths, window))
+            .collect()
+    };
 
-    let depths = read_depths(&opt.input);
-    let num_increases = count_increases(&depths, 1);
-    println!("{}", num_increases);
-    let window_increases = count_increases(&depths, 3);
-    println!("{}", window_increases);
+    if day_opt.windows.is_empty() {
+        let part = runner::part::<Day01>(opt);
+        let answers = Answers {
+            day: 1,
+            part1: part.includes(1).then(|| counts[0].into()),
+            part2: part.includes(2).then(|| counts[1].into()),
+            timings: None,
+            repeat: None,
+        };
+        if json {
+            answers.print_json();
+        } else {
+            answers.print_text();
+        }
+    } else if json {
+        let counts = windows
+            .iter()
+            .zip(counts)
+            .map(|(window, increases)| serde_json::json!({ "window": window, "increases": increases }))
+            .collect::<Vec<_>>();
+        println!("{}", serde_json::Value::from(counts));
+    } else {
+        for (window, increases) in windows.iter().zip(counts) {
+            println!("window {}: {}", window, increases);
+        }
+    }
 }
@@ -1,87 +1,100 @@
+use aoc2021::core::answer::Answer;
+use aoc2021::core::runner::{self, Answers, Opt};
+use aoc2021::y2021::day02::{self, Day02, Mode, Position};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::{Path, PathBuf};
+use std::io::BufWriter;
+use std::path::PathBuf;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
-struct Opt {
-    #[structopt(parse(from_os_str))]
-    input: PathBuf,
-}
-
-#[derive(Debug)]
-struct Position {
-    x: isize,
-    y: isize,
-    aim: isize,
-}
-
-#[derive(Debug)]
-enum Command {
-    Forward(isize),
-    Down(isize),
-    Up(isize),
-}
+struct DayOpt {
+    #[structopt(flatten)]
+    opt: Opt,
 
-fn parse_arg(value: &str) -> Result<isize, String> {
-    value.parse::<isize>().map_err(|e| e.to_string())
-}
+    /// Write where the submarine is after every command to this file as CSV
+    #[structopt(
+        long,
+        value_name = "FILE",
+        parse(from_os_str),
+        conflicts_with_all = runner::RUN_ONLY
+    )]
+    trace: Option<PathBuf>,
 
-impl TryFrom<String> for Command {
-    type Error = String;
+    /// How --trace reads the commands: simple, as in part 1, or aim, as in
+    /// part 2 [default: aim]
+    #[structopt(long, value_name = "simple|aim", requires = "trace")]
+    mode: Option<Mode>,
 
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        let parts: Vec<_> = value.split(' ').collect();
-        if parts.len() != 2 {
-            return Err(format!("Invalid command {}", value));
-        }
+    /// Skip lines that aren't valid commands, with a warning for each, rather
+    /// than stopping at the first
+    #[structopt(long, conflicts_with_all = runner::RUN_ONLY)]
+    lenient: bool,
 
-        use Command::*;
-        match parts[0] {
-            "forward" => Ok(Forward(parse_arg(parts[1])?)),
-            "down" => Ok(Down(parse_arg(parts[1])?)),
-            "up" => Ok(Up(parse_arg(parts[1])?)),
-            _ => Err(format!("Unknown command {}", parts[0])),
-        }
-    }
+    /// Where the submarine starts, as x,y,aim, for carrying on from the end
+    /// of an earlier part of the course [default: 0,0,0]
+    #[structopt(
+        long,
+        value_name = "x,y,aim",
+        allow_hyphen_values = true,
+        conflicts_with_all = runner::RUN_ONLY
+    )]
+    start: Option<Position>,
 }
 
-fn read_commands<P: AsRef<Path>>(input: P) -> Box<[Command]> {
-    BufReader::new(File::open(input).unwrap())
-        .lines()
-        .map(Result::unwrap)
-        .map(Command::try_from)
-        .map(Result::unwrap)
-        .collect::<Vec<_>>()
-        .into_boxed_slice()
+fn product(commands: &[day02::Command], mode: Mode, start: Position) -> Answer {
+    let end = day02::execute_from(commands, mode, start);
+    (end.x * end.y).into()
 }
 
-fn execute_command(command: &Command, position: &mut Position) {
-    use Command::*;
-    match command {
-        Forward(x) => {
-            position.x += x;
-            position.y += x * position.aim;
-        }
-        Down(x) => position.aim += x,
-        Up(x) => position.aim -= x,
+fn main() {
+    let day_opt: DayOpt = runner::parse_args();
+    if day_opt.trace.is_none() && !day_opt.lenient && day_opt.start.is_none() {
+        return runner::main_with::<Day02>(day_opt.opt);
     }
-}
 
-fn execute_commands(commands: &[Command]) -> Position {
-    let mut position = Position { x: 0, y: 0, aim: 0 };
+    let opt = &day_opt.opt;
+    let reader = runner::open_input::<Day02>(opt);
+    let commands = if day_opt.lenient {
+        let (commands, skipped) =
+            runner::or_exit::<Day02, _>(opt, day02::read_commands_lenient(reader));
+        for err in &skipped {
+            eprintln!("day02: skipping {}", err);
+        }
+        if !skipped.is_empty() {
+            let lines = if skipped.len() == 1 { "line" } else { "lines" };
+            eprintln!("day02: ignored {} invalid {}", skipped.len(), lines);
+        }
+        commands
+    } else {
+        runner::or_exit::<Day02, _>(opt, day02::read_commands(reader))
+    };
 
-    for command in commands {
-        execute_command(command, &mut position);
+    let start = day_opt.start.unwrap_or_default();
+    if let Some(path) = &day_opt.trace {
+        let mode = day_opt.mode.unwrap_or(Mode::Aim);
+        runner::or_exit::<Day02, _>(
+            opt,
+            File::create(path)
+                .map_err(Into::into)
+                .and_then(|file| day02::write_trace(&commands, mode, start, BufWriter::new(file))),
+        );
     }
 
-    position
-}
-
-fn main() {
-    let opt = Opt::from_args();
-
-    let commands = read_commands(&opt.input);
-    let end_pos = execute_commands(&commands);
-    println!("{}", end_pos.x * end_pos.y);
+    let part = runner::part::<Day02>(opt);
+    let answers = Answers {
+        day: 2,
+        part1: part
+            .includes(1)
+            .then(|| product(&commands, Mode::Simple, start)),
+        part2: part
+            .includes(2)
+            .then(|| product(&commands, Mode::Aim, start)),
+        timings: None,
+        repeat: None,
+    };
+    if runner::json::<Day02>(opt) {
+        answers.print_json();
+    } else {
+        answers.print_text();
+    }
 }
@@ -0,0 +1,1494 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::rc::Rc;
+
+use crate::core::tracker::{OperationTrack, Track};
+
+pub trait State: Sized {
+    fn min_remaining_cost(&self) -> usize;
+    fn successors(&self) -> Box<dyn Iterator<Item = (Self, usize)> + '_>;
+    fn is_complete(&self) -> bool;
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Stats {
+    pub expanded: usize,
+    pub generated: usize,
+    pub max_heap_size: usize,
+    pub duplicates: usize,
+}
+
+type Progress<'a> = (usize, Box<dyn FnMut(&Stats) + 'a>);
+
+pub struct SolveOptions<'a, T = ()> {
+    weight: f64,
+    progress: Option<Progress<'a>>,
+    tracker: T,
+}
+
+impl<'a> SolveOptions<'a> {
+    pub fn new() -> Self {
+        SolveOptions {
+            weight: 1.0,
+            progress: None,
+            tracker: (),
+        }
+    }
+}
+
+impl<'a, T> SolveOptions<'a, T> {
+    // Record each expansion as an operation on `tracker`, timing the pop,
+    // successor generation, heuristic and pushes separately.
+    pub fn tracker<U: Track>(self, tracker: U) -> SolveOptions<'a, U> {
+        SolveOptions {
+            weight: self.weight,
+            progress: self.progress,
+            tracker,
+        }
+    }
+
+    // Search on f = g + weight * h.  A weight above 1 gets to an answer sooner
+    // but it may cost up to `weight` times the best one.
+    pub fn weight(mut self, weight: f64) -> Self {
+        assert!(
+            weight.is_finite() && weight >= 0.0,
+            "invalid heuristic weight {}",
+            weight
+        );
+        self.weight = weight;
+        self
+    }
+
+    // Call `callback` with the stats so far after every `every` expansions.
+    pub fn progress(mut self, every: usize, callback: impl FnMut(&Stats) + 'a) -> Self {
+        assert!(every > 0, "progress interval must be at least 1");
+        self.progress = Some((every, Box::new(callback)));
+        self
+    }
+
+    fn expanded(&mut self, stats: &mut Stats) {
+        stats.expanded += 1;
+        if let Some((every, callback)) = &mut self.progress {
+            if stats.expanded.is_multiple_of(*every) {
+                callback(stats);
+            }
+        }
+    }
+
+    fn estimate<S: State>(&self, state: &S) -> usize {
+        weighted_estimate(state.min_remaining_cost(), self.weight)
+    }
+}
+
+fn weighted_estimate(min_remaining_cost: usize, weight: f64) -> usize {
+    if weight == 1.0 {
+        min_remaining_cost
+    } else {
+        (min_remaining_cost as f64 * weight).round() as usize
+    }
+}
+
+impl Default for SolveOptions<'_> {
+    fn default() -> Self {
+        SolveOptions::new()
+    }
+}
+
+// The queue of candidates waiting to be expanded, popped lowest priority
+// first.
+pub trait Frontier<T>: Default {
+    fn push(&mut self, priority: usize, item: T);
+    fn pop(&mut self) -> Option<T>;
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<S: Eq> Frontier<Candidate<S>> for BinaryHeap<Candidate<S>> {
+    fn push(&mut self, _priority: usize, candidate: Candidate<S>) {
+        BinaryHeap::push(self, candidate)
+    }
+
+    fn pop(&mut self) -> Option<Candidate<S>> {
+        BinaryHeap::pop(self)
+    }
+
+    fn len(&self) -> usize {
+        BinaryHeap::len(self)
+    }
+}
+
+pub fn solve<S: Eq + Hash + State + Clone>(initial_state: S) -> Option<(S, usize)> {
+    solve_with_options(initial_state, SolveOptions::new()).0
+}
+
+pub fn solve_with_options<S, T>(
+    initial_state: S,
+    options: SolveOptions<T>,
+) -> (Option<(S, usize)>, Stats)
+where
+    S: Eq + Hash + State + Clone,
+    T: Track,
+{
+    solve_with_frontier::<S, BinaryHeap<Candidate<S>>, T>(initial_state, options)
+}
+
+pub fn solve_with_frontier<S, Q, T>(
+    initial_state: S,
+    mut options: SolveOptions<T>,
+) -> (Option<(S, usize)>, Stats)
+where
+    S: Eq + Hash + State + Clone,
+    Q: Frontier<Candidate<S>>,
+    T: Track,
+{
+    let mut heap = Q::default();
+    let mut best_costs: HashMap<S, usize> = HashMap::new();
+    let mut stats = Stats::default();
+
+    improves(&mut best_costs, &initial_state, 0);
+    let candidate = Candidate::new(initial_state, 0, &options);
+    heap.push(candidate.priority(), candidate);
+
+    loop {
+        let operation = options.tracker.track_operation();
+
+        let candidate = {
+            let _pop = operation.track_duration("pop");
+            heap.pop()
+        };
+        let candidate = match candidate {
+            Some(candidate) => candidate,
+            None => break,
+        };
+
+        if candidate.state.is_complete() {
+            return (Some((candidate.state, candidate.cost)), stats);
+        }
+
+        if is_stale(&best_costs, &candidate) {
+            stats.duplicates += 1;
+            continue;
+        }
+
+        let successors = {
+            let _successors = operation.track_duration("successors");
+            candidate.state.successors().collect::<Vec<_>>()
+        };
+
+        // Spans cover all the successors at once rather than one each, which
+        // would cost more than the work they time.
+        let next_candidates = {
+            let _heuristic = operation.track_duration("heuristic");
+            successors
+                .into_iter()
+                .map(|(state, cost)| Candidate::new(state, candidate.cost + cost, &options))
+                .collect::<Vec<_>>()
+        };
+
+        {
+            let _push = operation.track_duration("push");
+            for next_candidate in next_candidates {
+                stats.generated += 1;
+                if improves(&mut best_costs, &next_candidate.state, next_candidate.cost) {
+                    heap.push(next_candidate.priority(), next_candidate);
+                } else {
+                    stats.duplicates += 1;
+                }
+            }
+        }
+
+        stats.max_heap_size = stats.max_heap_size.max(heap.len());
+        options.expanded(&mut stats);
+    }
+
+    (None, stats)
+}
+
+pub fn solve_with_path<S: Eq + Hash + State + Clone>(initial_state: S) -> Option<Vec<(S, usize)>> {
+    solve_with_path_and_options(initial_state, SolveOptions::new()).0
+}
+
+pub fn solve_with_path_and_options<S: Eq + Hash + State + Clone>(
+    initial_state: S,
+    mut options: SolveOptions,
+) -> (Option<Vec<(S, usize)>>, Stats) {
+    let mut heap: BinaryHeap<Candidate<S>> = BinaryHeap::new();
+    let mut best_costs: HashMap<S, usize> = HashMap::new();
+    let mut nodes: Vec<(S, usize, Option<usize>)> = vec![];
+    let mut stats = Stats::default();
+
+    improves(&mut best_costs, &initial_state, 0);
+    heap.push(Candidate::new(initial_state, 0, &options));
+
+    while let Some(candidate) = heap.pop() {
+        if is_stale(&best_costs, &candidate) {
+            stats.duplicates += 1;
+            continue;
+        }
+
+        let index = nodes.len();
+        nodes.push((candidate.state.clone(), candidate.cost, candidate.parent));
+
+        if candidate.state.is_complete() {
+            return (Some(reconstruct_path(nodes, index)), stats);
+        }
+
+        for next_candidate in candidate.successors(&options) {
+            stats.generated += 1;
+            if improves(&mut best_costs, &next_candidate.state, next_candidate.cost) {
+                heap.push(next_candidate.with_parent(index));
+            } else {
+                stats.duplicates += 1;
+            }
+        }
+
+        stats.max_heap_size = stats.max_heap_size.max(heap.len());
+        options.expanded(&mut stats);
+    }
+
+    (None, stats)
+}
+
+pub fn dijkstra<S, I, F, G>(
+    initial_state: S,
+    mut successors: F,
+    mut is_goal: G,
+) -> Option<(S, usize)>
+where
+    S: Eq + Hash + Clone,
+    I: IntoIterator<Item = (S, usize)>,
+    F: FnMut(&S) -> I,
+    G: FnMut(&S) -> bool,
+{
+    let mut heap: BinaryHeap<Candidate<S>> = BinaryHeap::new();
+    let mut best_costs: HashMap<S, usize> = HashMap::new();
+
+    improves(&mut best_costs, &initial_state, 0);
+    heap.push(Candidate::with_estimate(initial_state, 0, 0));
+
+    while let Some(candidate) = heap.pop() {
+        if is_goal(&candidate.state) {
+            return Some((candidate.state, candidate.cost));
+        }
+
+        if is_stale(&best_costs, &candidate) {
+            continue;
+        }
+
+        for (state, cost) in successors(&candidate.state) {
+            let cost = candidate.cost + cost;
+            if improves(&mut best_costs, &state, cost) {
+                heap.push(Candidate::with_estimate(state, cost, 0));
+            }
+        }
+    }
+
+    None
+}
+
+// Records `cost` as the best way to reach `state` if it beats what's known, so
+// that only candidates which improve on an earlier one get queued.
+fn improves<S: Eq + Hash + Clone>(
+    best_costs: &mut HashMap<S, usize>,
+    state: &S,
+    cost: usize,
+) -> bool {
+    match best_costs.get_mut(state) {
+        Some(best) if *best <= cost => false,
+        Some(best) => {
+            *best = cost;
+            true
+        }
+        None => {
+            best_costs.insert(state.clone(), cost);
+            true
+        }
+    }
+}
+
+// A candidate is stale if a cheaper path to its state was queued after it.
+fn is_stale<S: Eq + Hash>(best_costs: &HashMap<S, usize>, candidate: &Candidate<S>) -> bool {
+    best_costs
+        .get(&candidate.state)
+        .is_some_and(|&best| best < candidate.cost)
+}
+
+// Candidates expanded per thread in each round of the parallel searches.
+const PAR_BATCH_PER_THREAD: usize = 16;
+
+pub fn par_solve<S>(initial_state: S) -> Option<(S, usize)>
+where
+    S: Eq + Hash + State + Clone + Send + Sync,
+{
+    par_search(
+        initial_state,
+        S::min_remaining_cost,
+        |state: &S| state.successors().collect::<Vec<_>>(),
+        S::is_complete,
+    )
+}
+
+pub fn par_dijkstra<S, I, F, G>(initial_state: S, successors: F, is_goal: G) -> Option<(S, usize)>
+where
+    S: Eq + Hash + Clone + Send + Sync,
+    I: IntoIterator<Item = (S, usize)>,
+    F: Fn(&S) -> I + Sync,
+    G: FnMut(&S) -> bool,
+{
+    par_search(initial_state, |_| 0, successors, is_goal)
+}
+
+// Pops a batch of the best candidates and expands them all at once across
+// threads, checking successors against the shared map of expanded states.
+// A state late in a batch may be expanded before a cheaper path to it has
+// been found, so record the cost each state was expanded at and expand it
+// again if a cheaper path turns up.  For the same reason a goal is only
+// accepted when it's the best candidate left, i.e. first in its batch.
+fn par_search<S, E, I, F, G>(
+    initial_state: S,
+    estimate: E,
+    successors: F,
+    mut is_goal: G,
+) -> Option<(S, usize)>
+where
+    S: Eq + Hash + Clone + Send + Sync,
+    E: Fn(&S) -> usize + Sync,
+    I: IntoIterator<Item = (S, usize)>,
+    F: Fn(&S) -> I + Sync,
+    G: FnMut(&S) -> bool,
+{
+    let batch_size = rayon::current_num_threads() * PAR_BATCH_PER_THREAD;
+    let mut heap: BinaryHeap<Candidate<S>> = BinaryHeap::new();
+    let mut expanded: HashMap<S, usize> = HashMap::new();
+
+    let min_remaining_cost = estimate(&initial_state);
+    heap.push(Candidate::with_estimate(
+        initial_state,
+        0,
+        min_remaining_cost,
+    ));
+
+    loop {
+        let mut batch = vec![];
+
+        while batch.len() < batch_size {
+            let candidate = match heap.pop() {
+                Some(candidate) => candidate,
+                None => break,
+            };
+
+            if expanded
+                .get(&candidate.state)
+                .is_some_and(|&cost| cost <= candidate.cost)
+            {
+                continue;
+            }
+
+            if is_goal(&candidate.state) {
+                if batch.is_empty() {
+                    return Some((candidate.state, candidate.cost));
+                }
+                heap.push(candidate);
+                break;
+            }
+
+            expanded.insert(candidate.state.clone(), candidate.cost);
+            batch.push(candidate);
+        }
+
+        if batch.is_empty() {
+            return None;
+        }
+
+        let next_candidates = batch
+            .par_iter()
+            .map(|candidate| {
+                successors(&candidate.state)
+                    .into_iter()
+                    .map(|(state, cost)| (state, candidate.cost + cost))
+                    .filter(|(state, cost)| expanded.get(state).is_none_or(|&best| *cost < best))
+                    .map(|(state, cost)| {
+                        let min_remaining_cost = estimate(&state);
+                        Candidate::with_estimate(state, cost, min_remaining_cost)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        heap.extend(next_candidates.into_iter().flatten());
+    }
+}
+
+pub fn ida_star<S: Eq + State + Clone>(initial_state: S) -> Option<(S, usize)> {
+    let mut bound = initial_state.min_remaining_cost();
+    let mut path = vec![initial_state];
+
+    loop {
+        match ida_search(&mut path, 0, bound) {
+            Ok(solution) => return Some(solution),
+            Err(Some(next_bound)) => bound = next_bound,
+            Err(None) => return None,
+        }
+    }
+}
+
+// Depth-first search from the last state on `path`, pruning anything whose
+// estimated total cost exceeds `bound`.  On failure returns the smallest
+// estimate that was pruned, if any, to use as the next bound.
+fn ida_search<S: Eq + State + Clone>(
+    path: &mut Vec<S>,
+    cost: usize,
+    bound: usize,
+) -> Result<(S, usize), Option<usize>> {
+    let state = path.last().unwrap();
+
+    let estimate = cost + state.min_remaining_cost();
+    if estimate > bound {
+        return Err(Some(estimate));
+    }
+
+    if state.is_complete() {
+        return Ok((state.clone(), cost));
+    }
+
+    let successors = state.successors().collect::<Vec<_>>();
+    let mut next_bound: Option<usize> = None;
+
+    for (next_state, step_cost) in successors {
+        if path.contains(&next_state) {
+            continue;
+        }
+
+        path.push(next_state);
+        let result = ida_search(path, cost + step_cost, bound);
+        path.pop();
+
+        match result {
+            Ok(solution) => return Ok(solution),
+            Err(Some(estimate)) => {
+                next_bound = Some(next_bound.map_or(estimate, |bound| bound.min(estimate)))
+            }
+            Err(None) => {}
+        }
+    }
+
+    Err(next_bound)
+}
+
+// Anytime Repairing A*: searches with an inflated heuristic to find some
+// solution quickly, then repeatedly lowers the weight and repairs the search,
+// reusing what's been found so far.  Each item is cheaper than the last and
+// the final one, found with a weight of 1, is optimal.
+pub struct Anytime<S> {
+    weight: f64,
+    step: f64,
+    open: BinaryHeap<Candidate<S>>,
+    closed: HashSet<S>,
+    inconsistent: Vec<Candidate<S>>,
+    best_costs: HashMap<S, usize>,
+    best_solution: Option<(usize, usize)>,
+    done: bool,
+}
+
+pub fn anytime<S: Eq + Hash + State + Clone>(
+    initial_state: S,
+    weight: f64,
+    step: f64,
+) -> Anytime<S> {
+    assert!(
+        weight.is_finite() && weight >= 1.0,
+        "invalid heuristic weight {}",
+        weight
+    );
+    assert!(step > 0.0, "weight step must be positive");
+
+    let mut best_costs = HashMap::new();
+    improves(&mut best_costs, &initial_state, 0);
+
+    let min_remaining_cost = weighted_estimate(initial_state.min_remaining_cost(), weight);
+    let mut open = BinaryHeap::new();
+    open.push(Candidate::with_estimate(
+        initial_state,
+        0,
+        min_remaining_cost,
+    ));
+
+    Anytime {
+        weight,
+        step,
+        open,
+        closed: HashSet::new(),
+        inconsistent: vec![],
+        best_costs,
+        best_solution: None,
+        done: false,
+    }
+}
+
+impl<S: Eq + Hash + State + Clone> Anytime<S> {
+    // Search at the current weight until nothing left could beat the best
+    // solution so far, returning a better one if it turned up.  States that
+    // get cheaper once expanded wait for the next round, except in the last
+    // one where they're reopened straight away so the answer is exact.
+    fn improve(&mut self) -> Option<(S, usize)> {
+        let mut solution = None;
+
+        while let Some(candidate) = self.open.pop() {
+            if is_stale(&self.best_costs, &candidate) {
+                continue;
+            }
+
+            if let Some(best) = self.best_estimate() {
+                if best <= candidate.cost + candidate.min_remaining_cost {
+                    self.open.push(candidate);
+                    break;
+                }
+            }
+
+            if candidate.state.is_complete() {
+                let min_remaining_cost = candidate.state.min_remaining_cost();
+                self.best_solution = Some((candidate.cost, min_remaining_cost));
+                solution = Some((candidate.state, candidate.cost));
+                continue;
+            }
+
+            self.closed.insert(candidate.state.clone());
+
+            for (state, cost) in candidate.state.successors() {
+                let cost = candidate.cost + cost;
+                if improves(&mut self.best_costs, &state, cost) {
+                    let min_remaining_cost =
+                        weighted_estimate(state.min_remaining_cost(), self.weight);
+                    let next_candidate = Candidate::with_estimate(state, cost, min_remaining_cost);
+                    if self.weight > 1.0 && self.closed.contains(&next_candidate.state) {
+                        self.inconsistent.push(next_candidate);
+                    } else {
+                        self.open.push(next_candidate);
+                    }
+                }
+            }
+        }
+
+        solution
+    }
+
+    // The estimate the best solution would be queued with at the current
+    // weight.  Some heuristics don't reach zero at the goal, so this can't
+    // just be its cost.
+    fn best_estimate(&self) -> Option<usize> {
+        self.best_solution.map(|(cost, min_remaining_cost)| {
+            cost + weighted_estimate(min_remaining_cost, self.weight)
+        })
+    }
+
+    // Lower the weight and requeue everything still open, plus any states
+    // that got cheaper after they'd been expanded, under the new weight.
+    fn reduce_weight(&mut self) {
+        self.weight = (self.weight - self.step).max(1.0);
+
+        let candidates = self
+            .open
+            .drain()
+            .chain(self.inconsistent.drain(..))
+            .filter(|candidate| !is_stale(&self.best_costs, candidate))
+            .map(|candidate| {
+                let min_remaining_cost =
+                    weighted_estimate(candidate.state.min_remaining_cost(), self.weight);
+                Candidate::with_estimate(candidate.state, candidate.cost, min_remaining_cost)
+            })
+            .collect::<Vec<_>>();
+
+        self.open.extend(candidates);
+        self.closed.clear();
+    }
+}
+
+impl<S: Eq + Hash + State + Clone> Iterator for Anytime<S> {
+    type Item = (S, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let solution = self.improve();
+
+            if self.weight == 1.0 {
+                self.done = true;
+            } else {
+                self.reduce_weight();
+            }
+
+            if solution.is_some() {
+                return solution;
+            }
+        }
+
+        None
+    }
+}
+
+// Simplified memory-bounded A*: keeps at most `max_nodes` states in memory.
+// Successors are added one at a time, best first.  When it's full the worst
+// leaf is forgotten and its estimate remembered by its parent, which
+// regenerates it if that estimate becomes the best on offer.  Returns `None`
+// if no path fits in memory, otherwise the best path that does.
+pub fn sma_star<S: Eq + Hash + State + Clone>(
+    initial_state: S,
+    max_nodes: usize,
+) -> Option<(S, usize)> {
+    assert!(max_nodes > 1, "need room for at least two nodes");
+
+    let mut tree = BoundedTree::new();
+    let estimate = initial_state.min_remaining_cost();
+    tree.add(initial_state, 0, estimate, None);
+
+    while let Some(&(key, _, index)) = tree.queue.first() {
+        if key == usize::MAX {
+            return None;
+        }
+
+        let node = tree.node(index);
+        if node.state.is_complete() {
+            return Some((node.state.clone(), node.cost));
+        }
+
+        if node.pending.is_none() {
+            // Successors that are already on the path, or that would need
+            // more room than there is to go anywhere, are left out.
+            let depth = node.depth + 1;
+            let pending = node
+                .state
+                .successors()
+                .filter(|(state, _)| !tree.is_on_path(index, state))
+                .filter(|(state, _)| depth + 1 < max_nodes || state.is_complete())
+                .map(|(state, cost)| {
+                    let cost = node.cost + cost;
+                    let estimate = node.estimate.max(cost + state.min_remaining_cost());
+                    (state, cost, estimate)
+                })
+                .collect::<Vec<_>>();
+            tree.node_mut(index).pending = Some(pending);
+        }
+
+        if tree.is_dead(index) {
+            tree.node_mut(index).estimate = usize::MAX;
+            tree.forget(index);
+            continue;
+        }
+
+        if tree.len() >= max_nodes {
+            let worst = tree.worst_leaf(index)?;
+            tree.forget(worst);
+        }
+
+        let (state, cost, estimate) = tree.take_best_pending(index);
+        tree.add(state, cost, estimate, Some(index));
+        tree.back_up(index);
+    }
+
+    None
+}
+
+struct BoundedNode<S> {
+    state: S,
+    cost: usize,
+    estimate: usize,
+    depth: usize,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    // Successors not in memory, either not added yet or forgotten, with their
+    // estimates.  `None` until the node is first expanded.
+    pending: Option<Vec<(S, usize, usize)>>,
+    queued: Option<usize>,
+}
+
+impl<S> BoundedNode<S> {
+    fn best_pending(&self) -> Option<usize> {
+        self.pending
+            .as_ref()?
+            .iter()
+            .map(|&(_, _, estimate)| estimate)
+            .min()
+    }
+
+    // Where the node belongs in the queue: by its own estimate until it's
+    // been expanded, then by its best successor still to add.
+    fn key(&self) -> Option<usize> {
+        match self.pending {
+            None => Some(self.estimate),
+            Some(_) => self
+                .best_pending()
+                .map(|estimate| estimate.max(self.estimate)),
+        }
+    }
+}
+
+struct BoundedTree<S> {
+    nodes: Vec<Option<BoundedNode<S>>>,
+    free: Vec<usize>,
+    // Ordered by estimate, deepest first among equals, so the first entry is
+    // the next to expand and the last leaf is the first to forget.
+    queue: BTreeSet<(usize, Reverse<usize>, usize)>,
+}
+
+impl<S: Eq + Clone> BoundedTree<S> {
+    fn new() -> Self {
+        BoundedTree {
+            nodes: vec![],
+            free: vec![],
+            queue: BTreeSet::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    fn node(&self, index: usize) -> &BoundedNode<S> {
+        self.nodes[index].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, index: usize) -> &mut BoundedNode<S> {
+        self.nodes[index].as_mut().unwrap()
+    }
+
+    fn add(&mut self, state: S, cost: usize, estimate: usize, parent: Option<usize>) {
+        let depth = parent.map_or(0, |parent| self.node(parent).depth + 1);
+        let node = BoundedNode {
+            state,
+            cost,
+            estimate,
+            depth,
+            parent,
+            children: vec![],
+            pending: None,
+            queued: None,
+        };
+
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = Some(node);
+                index
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+
+        if let Some(parent) = parent {
+            self.node_mut(parent).children.push(index);
+        }
+        self.requeue(index);
+    }
+
+    fn requeue(&mut self, index: usize) {
+        self.dequeue(index);
+        let node = self.node_mut(index);
+        node.queued = node.key();
+        if let Some(key) = node.queued {
+            let depth = node.depth;
+            self.queue.insert((key, Reverse(depth), index));
+        }
+    }
+
+    fn dequeue(&mut self, index: usize) {
+        let node = self.node_mut(index);
+        if let Some(key) = node.queued.take() {
+            let depth = node.depth;
+            self.queue.remove(&(key, Reverse(depth), index));
+        }
+    }
+
+    fn is_on_path(&self, index: usize, state: &S) -> bool {
+        let mut next = Some(index);
+        while let Some(index) = next {
+            let node = self.node(index);
+            if node.state == *state {
+                return true;
+            }
+            next = node.parent;
+        }
+        false
+    }
+
+    // Expanded with nothing left to add and nothing in memory below it.
+    fn is_dead(&self, index: usize) -> bool {
+        let node = self.node(index);
+        node.children.is_empty() && node.pending.as_ref().is_some_and(Vec::is_empty)
+    }
+
+    fn take_best_pending(&mut self, index: usize) -> (S, usize, usize) {
+        let pending = self.node_mut(index).pending.as_mut().unwrap();
+        let best = (0..pending.len()).min_by_key(|&i| pending[i].2).unwrap();
+        let (state, cost, estimate) = pending.swap_remove(best);
+        self.requeue(index);
+        (state, cost, estimate)
+    }
+
+    fn worst_leaf(&self, except: usize) -> Option<usize> {
+        self.queue
+            .iter()
+            .rev()
+            .map(|&(_, _, index)| index)
+            .find(|&index| index != except && self.node(index).children.is_empty())
+    }
+
+    // Raises a node's estimate to that of its best successor, and carries
+    // the change up to its ancestors.
+    fn back_up(&mut self, index: usize) {
+        let mut next = Some(index);
+        while let Some(index) = next {
+            let node = self.node(index);
+            let best = node
+                .children
+                .iter()
+                .map(|&child| self.node(child).estimate)
+                .chain(node.best_pending())
+                .min()
+                .unwrap_or(usize::MAX);
+            if best <= node.estimate {
+                break;
+            }
+            self.node_mut(index).estimate = best;
+            self.requeue(index);
+            next = self.node(index).parent;
+        }
+    }
+
+    // Drop a leaf, handing it back to its parent to add again later.  A
+    // parent left with nothing but dead ends is dropped as well.
+    fn forget(&mut self, index: usize) {
+        self.dequeue(index);
+        let node = self.nodes[index].take().unwrap();
+        self.free.push(index);
+
+        if let Some(parent) = node.parent {
+            let parent_node = self.node_mut(parent);
+            parent_node.children.retain(|&child| child != index);
+            if node.estimate != usize::MAX {
+                let pending = parent_node.pending.as_mut().unwrap();
+                pending.push((node.state, node.cost, node.estimate));
+            }
+
+            if self.is_dead(parent) {
+                self.node_mut(parent).estimate = usize::MAX;
+                self.forget(parent);
+            } else {
+                self.requeue(parent);
+                self.back_up(parent);
+            }
+        }
+    }
+}
+
+fn reconstruct_path<S>(nodes: Vec<(S, usize, Option<usize>)>, goal: usize) -> Vec<(S, usize)> {
+    // Parents are always recorded before their children, so the path is in
+    // index order.
+    let mut on_path = vec![false; nodes.len()];
+    let mut next = Some(goal);
+    while let Some(index) = next {
+        on_path[index] = true;
+        next = nodes[index].2;
+    }
+
+    nodes
+        .into_iter()
+        .zip(on_path)
+        .filter(|(_, on_path)| *on_path)
+        .map(|((state, cost, _), _)| (state, cost))
+        .collect()
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Candidate<S> {
+    state: S,
+    cost: usize,
+    min_remaining_cost: usize,
+    parent: Option<usize>,
+}
+
+impl<S> Candidate<S> {
+    fn with_estimate(state: S, cost: usize, min_remaining_cost: usize) -> Self {
+        Candidate {
+            state,
+            cost,
+            min_remaining_cost,
+            parent: None,
+        }
+    }
+
+    fn priority(&self) -> usize {
+        self.cost + self.min_remaining_cost
+    }
+}
+
+impl<S: State> Candidate<S> {
+    fn new<T>(state: S, cost: usize, options: &SolveOptions<T>) -> Self {
+        let min_remaining_cost = options.estimate(&state);
+        Self::with_estimate(state, cost, min_remaining_cost)
+    }
+
+    fn with_parent(self, parent: usize) -> Self {
+        Candidate {
+            parent: Some(parent),
+            ..self
+        }
+    }
+
+    fn successors<'a>(
+        &'a self,
+        options: &'a SolveOptions<'a>,
+    ) -> impl Iterator<Item = Candidate<S>> + 'a {
+        self.state
+            .successors()
+            .map(|(state, cost)| Self::new(state, self.cost + cost, options))
+    }
+}
+
+impl<S: PartialEq> PartialOrd for Candidate<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.priority().cmp(&other.priority()).reverse())
+    }
+}
+
+impl<S: Eq> Ord for Candidate<S> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.partial_cmp(other).unwrap()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation<S> {
+    Inconsistent {
+        parent: S,
+        child: S,
+        cost: usize,
+        parent_estimate: usize,
+        child_estimate: usize,
+    },
+    NonZeroAtGoal {
+        state: S,
+        estimate: usize,
+    },
+}
+
+// Wraps a state to check its heuristic as the search goes, recording every
+// step where it overestimates relative to its successor, h(parent) > cost +
+// h(child), and any goal it doesn't think is free to finish.
+#[derive(Clone)]
+pub struct Checked<S> {
+    state: S,
+    violations: Rc<RefCell<Vec<Violation<S>>>>,
+    // Goals already checked, as searches ask whether a state is complete
+    // more than once.
+    goals: Rc<RefCell<HashSet<S>>>,
+}
+
+impl<S: PartialEq> PartialEq for Checked<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+    }
+}
+
+impl<S: Eq> Eq for Checked<S> {}
+
+impl<S: Hash> Hash for Checked<S> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.state.hash(state)
+    }
+}
+
+impl<S: Clone> Checked<S> {
+    pub fn new(state: S) -> Self {
+        Checked {
+            state,
+            violations: Rc::new(RefCell::new(vec![])),
+            goals: Rc::new(RefCell::new(HashSet::new())),
+        }
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn violations(&self) -> Vec<Violation<S>> {
+        self.violations.borrow().clone()
+    }
+
+    fn successor(&self, state: S) -> Self {
+        Checked {
+            state,
+            violations: self.violations.clone(),
+            goals: self.goals.clone(),
+        }
+    }
+}
+
+impl<S: Clone + Debug> Checked<S> {
+    pub fn assert_valid(&self) {
+        if let Some(violation) = self.violations.borrow().first() {
+            panic!("invalid heuristic: {:?}", violation);
+        }
+    }
+}
+
+impl<S: State + Clone + Eq + Hash> State for Checked<S> {
+    fn min_remaining_cost(&self) -> usize {
+        self.state.min_remaining_cost()
+    }
+
+    fn is_complete(&self) -> bool {
+        let complete = self.state.is_complete();
+        let estimate = self.state.min_remaining_cost();
+        if complete && estimate != 0 && self.goals.borrow_mut().insert(self.state.clone()) {
+            self.violations.borrow_mut().push(Violation::NonZeroAtGoal {
+                state: self.state.clone(),
+                estimate,
+            });
+        }
+        complete
+    }
+
+    fn successors(&self) -> Box<dyn Iterator<Item = (Self, usize)> + '_> {
+        let parent_estimate = self.state.min_remaining_cost();
+        Box::new(self.state.successors().map(move |(state, cost)| {
+            let child_estimate = state.min_remaining_cost();
+            if parent_estimate > cost + child_estimate {
+                self.violations.borrow_mut().push(Violation::Inconsistent {
+                    parent: self.state.clone(),
+                    child: state.clone(),
+                    cost,
+                    parent_estimate,
+                    child_estimate,
+                });
+            }
+            (self.successor(state), cost)
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::tracker::Tracker;
+    use std::sync::Arc;
+
+    // A directed graph to search from node 0 to its last node, with each
+    // node's estimate half its true distance to the goal so it's consistent.
+    struct Graph {
+        edges: Vec<Vec<(usize, usize)>>,
+        estimates: Vec<usize>,
+    }
+
+    #[derive(Clone)]
+    struct Node {
+        id: usize,
+        graph: Arc<Graph>,
+    }
+
+    impl PartialEq for Node {
+        fn eq(&self, other: &Self) -> bool {
+            self.id == other.id
+        }
+    }
+
+    impl Eq for Node {}
+
+    impl Hash for Node {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.id.hash(state)
+        }
+    }
+
+    impl std::fmt::Debug for Node {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "Node({})", self.id)
+        }
+    }
+
+    impl State for Node {
+        fn min_remaining_cost(&self) -> usize {
+            self.graph.estimates[self.id]
+        }
+
+        fn successors(&self) -> Box<dyn Iterator<Item = (Self, usize)> + '_> {
+            Box::new(self.graph.edges[self.id].iter().map(|&(id, cost)| {
+                let node = Node {
+                    id,
+                    graph: self.graph.clone(),
+                };
+                (node, cost)
+            }))
+        }
+
+        fn is_complete(&self) -> bool {
+            self.id == self.graph.edges.len() - 1
+        }
+    }
+
+    fn graph(edges: Vec<Vec<(usize, usize)>>) -> Node {
+        let goal = edges.len() - 1;
+        let estimates = (0..edges.len())
+            .map(|id| {
+                let from = Node {
+                    id,
+                    graph: Arc::new(Graph {
+                        edges: edges.clone(),
+                        estimates: vec![0; edges.len()],
+                    }),
+                };
+                distance(&from).map_or(0, |distance| distance / 2)
+            })
+            .collect();
+        assert!(goal > 0);
+        Node {
+            id: 0,
+            graph: Arc::new(Graph { edges, estimates }),
+        }
+    }
+
+    // The same graph with each node's estimate its true distance to the goal,
+    // so that any weight above 1 overestimates.
+    fn exact_estimates(start: &Node) -> Node {
+        let estimates = (0..start.graph.edges.len())
+            .map(|id| {
+                let from = Node {
+                    id,
+                    graph: start.graph.clone(),
+                };
+                distance(&from).unwrap_or(0)
+            })
+            .collect();
+        Node {
+            id: start.id,
+            graph: Arc::new(Graph {
+                edges: start.graph.edges.clone(),
+                estimates,
+            }),
+        }
+    }
+
+    // SplitMix64, which is plenty to make up small graphs from a seed.
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            ((z ^ (z >> 31)) % n as u64) as usize
+        }
+    }
+
+    fn random_graph(seed: u64, nodes: usize, max_cost: usize) -> Node {
+        let mut rng = Rng(seed);
+        let edges = (0..nodes)
+            .map(|_| {
+                (0..rng.below(4))
+                    .map(|_| (rng.below(nodes), 1 + rng.below(max_cost)))
+                    .collect()
+            })
+            .collect();
+        graph(edges)
+    }
+
+    fn distance(start: &Node) -> Option<usize> {
+        dijkstra(
+            start.clone(),
+            |node| node.successors().collect::<Vec<_>>(),
+            Node::is_complete,
+        )
+        .map(|(_, cost)| cost)
+    }
+
+    // Twelve nodes in a ladder: two routes along, with rungs between them and
+    // the cheapest way up the second.
+    fn ladder() -> Node {
+        let mut edges = vec![vec![]; 12];
+        for i in (0..10).step_by(2) {
+            edges[i].extend([(i + 1, 3), (i + 2, 2)]);
+            edges[i + 1].extend([(i, 3), (i + 3, 1)]);
+        }
+        edges[10].push((11, 4));
+        graph(edges)
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // Going round by 1 is cheaper than straight from 0 to 2, and nothing
+        // leads to 4.
+        let edges = [
+            vec![(1, 1), (2, 5)],
+            vec![(2, 1)],
+            vec![(3, 1)],
+            vec![],
+            vec![],
+        ];
+        let successors = |&node: &usize| edges[node].clone();
+        assert_eq!(dijkstra(0, successors, |&node| node == 3), Some((3, 3)));
+        assert_eq!(dijkstra(0, successors, |&node| node == 0), Some((0, 0)));
+        assert_eq!(dijkstra(0, successors, |&node| node == 4), None);
+    }
+
+    #[test]
+    fn test_cheaper_route_later() {
+        // Node 2 is queued straight from 0 first, then again more cheaply
+        // through 1.  The first is stale by the time it's popped so only the
+        // cheaper one gets expanded.
+        let start = graph(vec![
+            vec![(1, 1), (2, 10)],
+            vec![(2, 1)],
+            vec![(3, 20)],
+            vec![],
+        ]);
+        let (found, stats) = solve_with_options(start.clone(), SolveOptions::new());
+        assert_eq!(found.map(|(_, cost)| cost), Some(22));
+        assert_eq!(stats.expanded, 3);
+        assert_eq!(stats.duplicates, 1);
+
+        let path = solve_with_path(start).unwrap();
+        let path = path
+            .iter()
+            .map(|(node, cost)| (node.id, *cost))
+            .collect::<Vec<_>>();
+        assert_eq!(path, [(0, 0), (1, 1), (2, 2), (3, 22)]);
+    }
+
+    #[test]
+    fn test_stats() {
+        // A line of five nodes with a dead end off the second, which looks
+        // promising enough to be expanded on the way.
+        let start = graph(vec![
+            vec![(1, 1)],
+            vec![(2, 1), (4, 1)],
+            vec![(3, 1)],
+            vec![(5, 1)],
+            vec![],
+            vec![],
+        ]);
+        let mut reported = vec![];
+        let options = SolveOptions::new().progress(2, |stats| reported.push(stats.expanded));
+        let (found, stats) = solve_with_options(start, options);
+        assert_eq!(found.map(|(_, cost)| cost), Some(4));
+        assert_eq!(stats.expanded, 5);
+        assert_eq!(stats.generated, 5);
+        assert_eq!(stats.max_heap_size, 2);
+        assert_eq!(stats.duplicates, 0);
+        assert_eq!(reported, [2, 4]);
+    }
+
+    #[test]
+    fn test_tracker() {
+        let start = graph(vec![
+            vec![(1, 1), (2, 1)],
+            vec![(3, 1)],
+            vec![(3, 2)],
+            vec![],
+        ]);
+        let tracker = Tracker::new(0, std::io::sink());
+        let options = SolveOptions::new().tracker(tracker.clone());
+        let (found, stats) = solve_with_options(start, options);
+        assert_eq!(found.map(|(_, cost)| cost), Some(2));
+
+        // One of each span per expansion, and a pop for the goal too.
+        let operations = tracker
+            .metrics()
+            .into_iter()
+            .map(|metric| (metric.operation, metric.count))
+            .collect::<Vec<_>>();
+        let expanded = stats.expanded;
+        assert_eq!(
+            operations,
+            [
+                ("heuristic".to_string(), expanded),
+                ("pop".to_string(), expanded + 1),
+                ("push".to_string(), expanded),
+                ("successors".to_string(), expanded),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_checked() {
+        // The start overestimates against its successor, and the goal thinks
+        // there's further to go.
+        let graph = Arc::new(Graph {
+            edges: vec![vec![(1, 1)], vec![(2, 1)], vec![]],
+            estimates: vec![5, 0, 3],
+        });
+        let node = |id| Node {
+            id,
+            graph: graph.clone(),
+        };
+        let start = Checked::new(node(0));
+        let (goal, cost) = solve(start.clone()).unwrap();
+        assert_eq!(cost, 2);
+        // Asking again doesn't record the goal twice.
+        assert!(goal.is_complete());
+        assert_eq!(
+            start.violations(),
+            [
+                Violation::Inconsistent {
+                    parent: node(0),
+                    child: node(1),
+                    cost: 1,
+                    parent_estimate: 5,
+                    child_estimate: 0,
+                },
+                Violation::NonZeroAtGoal {
+                    state: node(2),
+                    estimate: 3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sma_star() {
+        let start = ladder();
+        let best = distance(&start);
+        assert_eq!(best, Some(8));
+        assert_eq!(sma_star(start.clone(), 100).map(|(_, cost)| cost), best);
+        for max_nodes in 2..12 {
+            if let Some((goal, cost)) = sma_star(start.clone(), max_nodes) {
+                assert!(goal.is_complete());
+                assert!(cost >= best.unwrap());
+            }
+        }
+        // The best path visits seven nodes, so needs room for all of them.
+        assert_eq!(sma_star(start.clone(), 4), None);
+        assert_eq!(sma_star(start, 7).map(|(_, cost)| cost), best);
+    }
+
+    #[test]
+    fn test_sma_star_random() {
+        for seed in 0..200 {
+            let start = random_graph(seed, 12, 5);
+            let best = distance(&start);
+            // No path can visit more than all twelve nodes.
+            assert_eq!(
+                sma_star(start.clone(), 12).map(|(_, cost)| cost),
+                best,
+                "seed {}",
+                seed
+            );
+            for max_nodes in 2..8 {
+                let found = sma_star(start.clone(), max_nodes).map(|(_, cost)| cost);
+                assert!(
+                    found.is_none() || found >= best,
+                    "seed {} max_nodes {}",
+                    seed,
+                    max_nodes
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_ida_star() {
+        let start = ladder();
+        assert_eq!(
+            ida_star(start.clone()).map(|(_, cost)| cost),
+            distance(&start)
+        );
+
+        for seed in 0..200 {
+            let start = random_graph(seed, 8, 5);
+            assert_eq!(
+                ida_star(start.clone()).map(|(_, cost)| cost),
+                distance(&start),
+                "seed {}",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn test_ida_star_unreachable() {
+        // Nothing leads to the goal, but there's a cycle to go round.
+        let start = graph(vec![
+            vec![(1, 1), (2, 2)],
+            vec![(2, 1)],
+            vec![(0, 1)],
+            vec![],
+        ]);
+        assert_eq!(distance(&start), None);
+        assert_eq!(ida_star(start), None);
+    }
+
+    #[test]
+    fn test_par_search() {
+        // Costs of 1 or 2 make for plenty of paths that tie.
+        for seed in 0..100 {
+            let start = random_graph(seed, 40, 2);
+            let best = solve(start.clone()).map(|(_, cost)| cost);
+            assert_eq!(best, distance(&start), "seed {}", seed);
+            assert_eq!(
+                par_solve(start.clone()).map(|(_, cost)| cost),
+                best,
+                "seed {}",
+                seed
+            );
+            let par_distance = par_dijkstra(
+                start,
+                |node| node.successors().collect::<Vec<_>>(),
+                Node::is_complete,
+            );
+            assert_eq!(par_distance.map(|(_, cost)| cost), best, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_weight() {
+        for seed in 0..200 {
+            let start = exact_estimates(&random_graph(seed, 12, 9));
+            let best = distance(&start);
+            for weight in [1.0, 1.5, 2.0, 3.0, 5.0] {
+                let options = SolveOptions::new().weight(weight);
+                let found = solve_with_options(start.clone(), options).0;
+                let found = found.map(|(_, cost)| cost);
+                assert_eq!(found.is_some(), best.is_some(), "seed {}", seed);
+                if let (Some(found), Some(best)) = (found, best) {
+                    assert!(found >= best, "seed {} weight {}", seed, weight);
+                    assert!(
+                        found as f64 <= weight * best as f64,
+                        "seed {} weight {}",
+                        seed,
+                        weight
+                    );
+                    if weight == 1.0 {
+                        assert_eq!(found, best, "seed {}", seed);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_anytime() {
+        for seed in 0..200 {
+            let start = exact_estimates(&random_graph(seed, 12, 9));
+            let costs = anytime(start.clone(), 5.0, 1.5)
+                .map(|(goal, cost)| {
+                    assert!(goal.is_complete());
+                    cost
+                })
+                .collect::<Vec<_>>();
+            assert!(
+                costs.windows(2).all(|pair| pair[1] <= pair[0]),
+                "seed {} costs {:?}",
+                seed,
+                costs
+            );
+            assert_eq!(costs.last().copied(), distance(&start), "seed {}", seed);
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, queue, style, terminal};
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+const MIN_DELAY: Duration = Duration::from_millis(1);
+const MAX_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    // Keep waiting for the current frame.
+    Wait,
+    // Move on to the next frame.
+    Advance,
+    // Stop animating and let the solution run to the end.
+    Quit,
+}
+
+// What the keys have asked for so far: space pauses, `n` or right steps while
+// paused, `+` and `-` change speed, and `q`, Esc or Ctrl-C quit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Controls {
+    pub delay: Duration,
+    pub paused: bool,
+}
+
+impl Controls {
+    pub fn new(delay: Duration) -> Self {
+        Controls {
+            delay: delay.clamp(MIN_DELAY, MAX_DELAY),
+            paused: false,
+        }
+    }
+
+    pub fn handle(&mut self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+            KeyCode::Char(' ') => {
+                self.paused = !self.paused;
+                if self.paused {
+                    Action::Wait
+                } else {
+                    Action::Advance
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Right if self.paused => Action::Advance,
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.delay = (self.delay / 2).max(MIN_DELAY);
+                Action::Wait
+            }
+            KeyCode::Char('-') => {
+                self.delay = (self.delay * 2).min(MAX_DELAY);
+                Action::Wait
+            }
+            _ => Action::Wait,
+        }
+    }
+}
+
+// Draws frames over each other on stderr, which it takes over until dropped.
+pub struct Animator {
+    controls: Controls,
+    frames: usize,
+}
+
+impl Animator {
+    pub fn new(delay: Duration) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stderr(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Animator {
+            controls: Controls::new(delay),
+            frames: 0,
+        })
+    }
+
+    fn draw(&self, text: &str) -> io::Result<()> {
+        let mut out = io::stderr().lock();
+        let status = format!(
+            "frame {}  delay {}ms{}  [space] pause  [n] step  [+/-] speed  [q] quit",
+            self.frames,
+            self.controls.delay.as_millis(),
+            if self.controls.paused { "  PAUSED" } else { "" }
+        );
+        queue!(
+            out,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(terminal::ClearType::All),
+            style::Print(status),
+            cursor::MoveToNextLine(2)
+        )?;
+        // Raw mode doesn't turn "\n" into a carriage return as well.
+        for line in text.lines() {
+            queue!(out, style::Print(line), cursor::MoveToNextLine(1))?;
+        }
+        out.flush()
+    }
+
+    // Shows `text` and waits for the next frame to be due, or for the user
+    // to step on if paused.
+    fn frame(&mut self, text: &str) -> io::Result<Action> {
+        self.frames += 1;
+        self.draw(text)?;
+
+        let mut due = Instant::now() + self.controls.delay;
+        loop {
+            let timeout = if self.controls.paused {
+                None
+            } else {
+                match due.checked_duration_since(Instant::now()) {
+                    Some(timeout) => Some(timeout),
+                    None => return Ok(Action::Advance),
+                }
+            };
+
+            let event = match timeout {
+                Some(timeout) if !event::poll(timeout)? => continue,
+                _ => event::read()?,
+            };
+            if let Event::Key(key) = event {
+                if key.kind == KeyEventKind::Release {
+                    continue;
+                }
+                let was_paused = self.controls.paused;
+                match self.controls.handle(key) {
+                    Action::Wait => {
+                        if self.controls.paused != was_paused {
+                            self.draw(text)?;
+                        }
+                        due = due.min(Instant::now() + self.controls.delay);
+                    }
+                    action => return Ok(action),
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Animator {
+    fn drop(&mut self) {
+        let _ = execute!(io::stderr(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+thread_local! {
+    static GLOBAL: RefCell<Option<Animator>> = const { RefCell::new(None) };
+}
+
+// Make `animator` the one `frame` draws with on this thread.
+pub fn install(animator: Animator) {
+    GLOBAL.with(|global| *global.borrow_mut() = Some(animator));
+}
+
+pub fn uninstall() -> Option<Animator> {
+    GLOBAL.with(|global| global.borrow_mut().take())
+}
+
+pub fn is_installed() -> bool {
+    GLOBAL.with(|global| global.borrow().is_some())
+}
+
+// Shows the next frame if an animator is installed. `render` is only called
+// when it is, so it's cheap to leave in a solution.
+pub fn frame(render: impl FnOnce() -> String) {
+    GLOBAL.with(|global| {
+        let mut global = global.borrow_mut();
+        let Some(animator) = global.as_mut() else {
+            return;
+        };
+        match animator.frame(&render()) {
+            Ok(Action::Quit) | Err(_) => *global = None,
+            Ok(_) => {}
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_controls() {
+        let mut controls = Controls::new(Duration::from_millis(100));
+        assert_eq!(controls.handle(key(KeyCode::Char('n'))), Action::Wait);
+
+        assert_eq!(controls.handle(key(KeyCode::Char(' '))), Action::Wait);
+        assert!(controls.paused);
+        assert_eq!(controls.handle(key(KeyCode::Char('n'))), Action::Advance);
+        assert_eq!(controls.handle(key(KeyCode::Right)), Action::Advance);
+        assert_eq!(controls.handle(key(KeyCode::Char(' '))), Action::Advance);
+        assert!(!controls.paused);
+
+        controls.handle(key(KeyCode::Char('+')));
+        assert_eq!(controls.delay, Duration::from_millis(50));
+        controls.handle(key(KeyCode::Char('-')));
+        controls.handle(key(KeyCode::Char('-')));
+        assert_eq!(controls.delay, Duration::from_millis(200));
+        for _ in 0..10 {
+            controls.handle(key(KeyCode::Char('-')));
+        }
+        assert_eq!(controls.delay, MAX_DELAY);
+
+        assert_eq!(controls.handle(key(KeyCode::Char('q'))), Action::Quit);
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(controls.handle(ctrl_c), Action::Quit);
+    }
+
+    #[test]
+    fn test_frame_without_animator() {
+        assert!(!is_installed());
+        frame(|| panic!("shouldn't render"));
+    }
+}
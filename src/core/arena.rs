@@ -0,0 +1,106 @@
+use std::ops::{Index, IndexMut};
+
+pub type NodeId = usize;
+
+// Nodes of a tree held in one `Vec` and linked by index, so building and
+// rewriting the tree doesn't allocate for every node.  Freed nodes are
+// reused by later allocations.
+#[derive(Debug, Clone)]
+pub struct Arena<T> {
+    nodes: Vec<T>,
+    free: Vec<NodeId>,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Arena {
+            nodes: vec![],
+            free: vec![],
+        }
+    }
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Arena::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Arena {
+            nodes: Vec::with_capacity(capacity),
+            free: vec![],
+        }
+    }
+
+    pub fn alloc(&mut self, node: T) -> NodeId {
+        if let Some(id) = self.free.pop() {
+            self.nodes[id] = node;
+            id
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    // Marks `id` as free for reuse.  Anything still pointing at it will see
+    // whatever gets allocated there next.
+    pub fn free(&mut self, id: NodeId) {
+        debug_assert!(!self.free.contains(&id), "node {} freed twice", id);
+        self.free.push(id);
+    }
+
+    // The number of nodes in use.
+    pub fn len(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+    }
+}
+
+impl<T> Index<NodeId> for Arena<T> {
+    type Output = T;
+
+    fn index(&self, id: NodeId) -> &T {
+        &self.nodes[id]
+    }
+}
+
+impl<T> IndexMut<NodeId> for Arena<T> {
+    fn index_mut(&mut self, id: NodeId) -> &mut T {
+        &mut self.nodes[id]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_alloc_and_free() {
+        let mut arena = Arena::new();
+        let a = arena.alloc("a");
+        let b = arena.alloc("b");
+        assert_eq!((arena[a], arena[b]), ("a", "b"));
+        assert_eq!(arena.len(), 2);
+
+        arena.free(a);
+        assert_eq!(arena.len(), 1);
+        let c = arena.alloc("c");
+        assert_eq!(c, a);
+        assert_eq!(arena[c], "c");
+        assert_eq!(arena.len(), 2);
+
+        arena[b] = "d";
+        assert_eq!(arena[b], "d");
+
+        arena.clear();
+        assert!(arena.is_empty());
+    }
+}
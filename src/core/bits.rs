@@ -0,0 +1,162 @@
+// Reads a stream of bits, most significant bit of each byte first.
+use crate::core::error::{AocError, Result};
+
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    // Both in bits from the start of `data`.
+    position: u64,
+    end: u64,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        BitReader {
+            data,
+            position: 0,
+            end: data.len() as u64 * 8,
+        }
+    }
+
+    // How many bits have been read from the whole stream, including by any
+    // reader this one was split from.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.end - self.position
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    fn check(&self, count: u64) -> Result<()> {
+        if count > self.remaining() {
+            Err(AocError::parse(format!(
+                "wanted {} bits at bit {} but only {} left",
+                count,
+                self.position,
+                self.remaining()
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    // The next `count` bits, up to 64, without reading them.
+    pub fn peek(&self, count: u32) -> Result<u64> {
+        if count > u64::BITS {
+            return Err(AocError::parse(format!(
+                "can't read {} bits into a u64",
+                count
+            )));
+        }
+        self.check(count.into())?;
+
+        let mut value = 0;
+        let mut position = self.position;
+        let mut left = count;
+        while left > 0 {
+            let byte = self.data[(position / 8) as usize];
+            let offset = (position % 8) as u32;
+            let take = (8 - offset).min(left);
+            let bits = (byte >> (8 - offset - take)) & (((1_u16 << take) - 1) as u8);
+            value = (value << take) | u64::from(bits);
+            position += u64::from(take);
+            left -= take;
+        }
+        Ok(value)
+    }
+
+    pub fn read_bits(&mut self, count: u32) -> Result<u64> {
+        let value = self.peek(count)?;
+        self.position += u64::from(count);
+        Ok(value)
+    }
+
+    pub fn read_bit(&mut self) -> Result<bool> {
+        Ok(self.read_bits(1)? == 1)
+    }
+
+    pub fn skip(&mut self, count: u64) -> Result<()> {
+        self.check(count)?;
+        self.position += count;
+        Ok(())
+    }
+
+    // Skips to the start of the next byte, unless already at one.
+    pub fn align(&mut self) -> Result<()> {
+        self.skip((8 - self.position % 8) % 8)
+    }
+
+    // Splits off the next `length` bits as a reader of their own, which
+    // can't read beyond them, and moves this one past them.
+    pub fn sub_reader(&mut self, length: u64) -> Result<BitReader<'a>> {
+        self.check(length)?;
+        let sub_reader = BitReader {
+            data: self.data,
+            position: self.position,
+            end: self.position + length,
+        };
+        self.position += length;
+        Ok(sub_reader)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read_bits() {
+        let data = [0b1010_1100, 0b0101_1011, 0xFF];
+        let mut reader = BitReader::new(&data);
+        assert_eq!(reader.peek(3).unwrap(), 0b101);
+        assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+        assert_eq!(reader.read_bits(12).unwrap(), 0b0110_0010_1101);
+        assert!(reader.read_bit().unwrap());
+        assert_eq!(reader.position(), 16);
+        assert_eq!(reader.read_bits(0).unwrap(), 0);
+        assert_eq!(reader.read_bits(8).unwrap(), 0xFF);
+        assert!(reader.is_empty());
+        assert!(reader.read_bit().is_err());
+
+        let data = [0xAB; 9];
+        let mut reader = BitReader::new(&data);
+        reader.skip(4).unwrap();
+        assert_eq!(reader.read_bits(64).unwrap(), 0xBABA_BABA_BABA_BABA);
+        assert!(reader.peek(65).is_err());
+    }
+
+    #[test]
+    fn test_align() {
+        let data = [0xFF, 0x0F];
+        let mut reader = BitReader::new(&data);
+        reader.align().unwrap();
+        assert_eq!(reader.position(), 0);
+        reader.read_bits(3).unwrap();
+        reader.align().unwrap();
+        assert_eq!(reader.position(), 8);
+        assert_eq!(reader.read_bits(8).unwrap(), 0x0F);
+    }
+
+    #[test]
+    fn test_sub_reader() {
+        let data = [0b1111_0000, 0b1010_1010];
+        let mut reader = BitReader::new(&data);
+        reader.read_bits(2).unwrap();
+
+        let mut sub_reader = reader.sub_reader(4).unwrap();
+        assert_eq!(reader.position(), 6);
+        assert_eq!(sub_reader.position(), 2);
+        assert_eq!(sub_reader.remaining(), 4);
+        assert!(sub_reader.read_bits(5).is_err());
+        assert_eq!(sub_reader.read_bits(4).unwrap(), 0b1100);
+        assert!(sub_reader.is_empty());
+
+        assert_eq!(reader.read_bits(10).unwrap(), 0b00_1010_1010);
+        assert!(reader.sub_reader(1).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+use crate::core::position::{Position, Position3};
+
+// An axis-aligned rectangle, including both corners.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Rect {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl Rect {
+    pub fn new(min: Position, max: Position) -> Self {
+        assert!(min.x <= max.x && min.y <= max.y, "empty rect");
+        Rect { min, max }
+    }
+
+    // The smallest rect containing all of `positions`, if there are any.
+    pub fn bounding(positions: impl IntoIterator<Item = Position>) -> Option<Self> {
+        positions
+            .into_iter()
+            .map(|position| Rect::new(position, position))
+            .reduce(|a, b| a.union_bounds(&b))
+    }
+
+    pub fn width(&self) -> i64 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i64 {
+        self.max.y - self.min.y + 1
+    }
+
+    pub fn area(&self) -> i64 {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        (self.min.x..=self.max.x).contains(&position.x)
+            && (self.min.y..=self.max.y).contains(&position.y)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Position::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Position::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+        (min.x <= max.x && min.y <= max.y).then_some(Rect { min, max })
+    }
+
+    // The smallest rect containing both.
+    pub fn union_bounds(&self, other: &Self) -> Self {
+        Rect::new(
+            Position::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            Position::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        )
+    }
+
+    // Every position in the rect, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = Position> {
+        let Rect { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Position::new(x, y)))
+    }
+}
+
+// An axis-aligned box, including both corners.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Cuboid {
+    pub min: Position3,
+    pub max: Position3,
+}
+
+impl Cuboid {
+    pub fn new(min: Position3, max: Position3) -> Self {
+        assert!(
+            min.x <= max.x && min.y <= max.y && min.z <= max.z,
+            "empty cuboid"
+        );
+        Cuboid { min, max }
+    }
+
+    // The cube of positions within `radius` of `centre` along every axis.
+    pub fn around(centre: Position3, radius: i64) -> Self {
+        let radius = Position3::new(radius, radius, radius);
+        Cuboid::new(centre - radius, centre + radius)
+    }
+
+    pub fn size(&self) -> Position3 {
+        self.max - self.min + Position3::new(1, 1, 1)
+    }
+
+    pub fn volume(&self) -> i64 {
+        let size = self.size();
+        size.x * size.y * size.z
+    }
+
+    pub fn contains(&self, position: Position3) -> bool {
+        (self.min.x..=self.max.x).contains(&position.x)
+            && (self.min.y..=self.max.y).contains(&position.y)
+            && (self.min.z..=self.max.z).contains(&position.z)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = self.min.max(other.min);
+        let max = self.max.min(other.max);
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Cuboid { min, max })
+    }
+
+    // The smallest cuboid containing both.
+    pub fn union_bounds(&self, other: &Self) -> Self {
+        Cuboid::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    pub fn translate(&self, offset: Position3) -> Self {
+        Cuboid {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    // Every position in the cuboid, varying x fastest.
+    pub fn iter(&self) -> impl Iterator<Item = Position3> {
+        let Cuboid { min, max } = *self;
+        (min.z..=max.z).flat_map(move |z| {
+            (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Position3::new(x, y, z)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rect() {
+        let a = Rect::new(Position::new(0, 0), Position::new(3, 2));
+        let b = Rect::new(Position::new(2, 1), Position::new(5, 5));
+        assert_eq!(a.area(), 12);
+        assert_eq!(a.iter().count(), 12);
+        assert!(a.contains(Position::new(3, 2)));
+        assert!(!a.contains(Position::new(4, 2)));
+        assert_eq!(
+            a.intersection(&b),
+            Some(Rect::new(Position::new(2, 1), Position::new(3, 2)))
+        );
+        assert_eq!(
+            a.union_bounds(&b),
+            Rect::new(Position::new(0, 0), Position::new(5, 5))
+        );
+        assert_eq!(
+            a.intersection(&Rect::new(Position::new(4, 0), Position::new(4, 0))),
+            None
+        );
+        assert_eq!(
+            Rect::bounding([Position::new(1, 5), Position::new(-2, 3)]),
+            Some(Rect::new(Position::new(-2, 3), Position::new(1, 5)))
+        );
+    }
+
+    #[test]
+    fn test_cuboid() {
+        let a = Cuboid::around(Position3::new(0, 0, 0), 1);
+        let b = Cuboid::new(Position3::new(1, 1, 1), Position3::new(4, 4, 4));
+        assert_eq!(a.volume(), 27);
+        assert_eq!(a.iter().count(), 27);
+        assert!(a.contains(Position3::new(-1, 1, 0)));
+        assert!(!a.contains(Position3::new(-2, 1, 0)));
+        assert_eq!(
+            a.intersection(&b),
+            Some(Cuboid::new(
+                Position3::new(1, 1, 1),
+                Position3::new(1, 1, 1)
+            ))
+        );
+        assert_eq!(a.union_bounds(&b).volume(), 6 * 6 * 6);
+        assert_eq!(a.translate(Position3::new(6, 0, 0)).intersection(&b), None);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::core::a_star::Frontier;
+use std::collections::VecDeque;
+
+// A priority queue for small integer priorities, with a bucket for each one
+// between the lowest and highest queued.  Pushing and popping are O(1) as
+// long as the priorities popped mostly go up in small steps, as they do in
+// A* when the costs are small.  Items with the same priority come out last
+// in, first out.
+#[derive(Debug, Clone)]
+pub struct BucketQueue<T> {
+    buckets: VecDeque<Vec<T>>,
+    lowest: usize,
+    len: usize,
+}
+
+impl<T> BucketQueue<T> {
+    pub fn new() -> Self {
+        BucketQueue {
+            buckets: VecDeque::new(),
+            lowest: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push(&mut self, priority: usize, item: T) {
+        if self.buckets.is_empty() {
+            self.lowest = priority;
+        }
+
+        while priority < self.lowest {
+            self.buckets.push_front(vec![]);
+            self.lowest -= 1;
+        }
+
+        let index = priority - self.lowest;
+        if index >= self.buckets.len() {
+            self.buckets.resize_with(index + 1, Vec::new);
+        }
+
+        self.buckets[index].push(item);
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<(usize, T)> {
+        while let Some(bucket) = self.buckets.front_mut() {
+            if let Some(item) = bucket.pop() {
+                self.len -= 1;
+                return Some((self.lowest, item));
+            }
+
+            self.buckets.pop_front();
+            self.lowest += 1;
+        }
+
+        None
+    }
+}
+
+impl<T> Default for BucketQueue<T> {
+    fn default() -> Self {
+        BucketQueue::new()
+    }
+}
+
+impl<T> Frontier<T> for BucketQueue<T> {
+    fn push(&mut self, priority: usize, item: T) {
+        BucketQueue::push(self, priority, item)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        BucketQueue::pop(self).map(|(_, item)| item)
+    }
+
+    fn len(&self) -> usize {
+        BucketQueue::len(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pops_in_priority_order() {
+        let mut queue = BucketQueue::new();
+        for (priority, item) in [(5, 'a'), (3, 'b'), (9, 'c'), (3, 'd'), (1, 'e')] {
+            queue.push(priority, item);
+        }
+        assert_eq!(queue.len(), 5);
+
+        let popped = std::iter::from_fn(|| queue.pop()).collect::<Vec<_>>();
+        assert_eq!(
+            popped,
+            vec![(1, 'e'), (3, 'd'), (3, 'b'), (5, 'a'), (9, 'c')]
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_push_below_lowest_after_pop() {
+        let mut queue = BucketQueue::new();
+        queue.push(10, 'a');
+        queue.push(12, 'b');
+        assert_eq!(queue.pop(), Some((10, 'a')));
+        queue.push(4, 'c');
+        assert_eq!(queue.pop(), Some((4, 'c')));
+        assert_eq!(queue.pop(), Some((12, 'b')));
+        assert_eq!(queue.pop(), None);
+    }
+}
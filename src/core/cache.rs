@@ -0,0 +1,104 @@
+use crate::core::answer::Answer;
+use crate::core::error::Result;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+
+// Answers are keyed by the solver version as well as the input, so that
+// after a change to the solvers the old answers are missed rather than
+// served up again.  The version defaults to the crate's.
+pub struct Cache {
+    dir: PathBuf,
+    version: String,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Cache {
+            dir: dir.into(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    pub fn key(&self, input: &[u8]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.version.as_bytes());
+        hasher.update([0]);
+        hasher.update(input);
+        hex::encode(hasher.finalize())
+    }
+
+    fn path(&self, year: u32, day: u32, part: u32, key: &str) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day{:02}", day))
+            .join(format!("{}.part{}.json", key, part))
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: u32, key: &str) -> Option<Answer> {
+        let data = fs::read(self.path(year, day, part, key)).ok()?;
+        serde_json::from_slice(&data).ok()
+    }
+
+    pub fn put(&self, year: u32, day: u32, part: u32, key: &str, answer: &Answer) -> Result<()> {
+        let path = self.path(year, day, part, key);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, serde_json::to_vec(answer).unwrap())?;
+        Ok(())
+    }
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Cache::new(".cache")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::process;
+
+    fn cache(name: &str) -> Cache {
+        let dir = std::env::temp_dir().join(format!("aoc2021-cache-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        Cache::new(dir)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cache = cache("round-trip");
+        let key = cache.key(b"3,4,3,1,2\n");
+        cache.put(2021, 6, 1, &key, &Answer::from(5934)).unwrap();
+        assert_eq!(cache.get(2021, 6, 1, &key), Some(Answer::from(5934)));
+        let _ = fs::remove_dir_all(&cache.dir);
+    }
+
+    #[test]
+    fn test_miss() {
+        let cache = cache("miss");
+        let key = cache.key(b"3,4,3,1,2\n");
+        cache.put(2021, 6, 1, &key, &Answer::from(5934)).unwrap();
+        assert_eq!(cache.get(2021, 6, 2, &key), None);
+        assert_eq!(cache.get(2021, 7, 1, &key), None);
+        assert_eq!(cache.get(2021, 6, 1, &cache.key(b"1,2\n")), None);
+        let _ = fs::remove_dir_all(&cache.dir);
+    }
+
+    #[test]
+    fn test_version() {
+        let old = cache("version").with_version("0.0.0");
+        let key = old.key(b"3,4,3,1,2\n");
+        old.put(2021, 6, 1, &key, &Answer::from(5934)).unwrap();
+
+        let new = Cache::new(&old.dir);
+        assert_ne!(new.key(b"3,4,3,1,2\n"), key);
+        assert_eq!(new.get(2021, 6, 1, &new.key(b"3,4,3,1,2\n")), None);
+        let _ = fs::remove_dir_all(&old.dir);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::core::error::{AocError, Result};
+use crate::core::registry::DEFAULT_YEAR;
+use crate::core::report;
+use crate::core::runner::Part;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const LOCAL_CONFIG: &str = "aoc2021.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DayConfig {
+    pub input: Option<PathBuf>,
+    pub part: Option<Part>,
+    pub time: Option<bool>,
+    pub cache: Option<bool>,
+}
+
+impl DayConfig {
+    fn merge(self, other: DayConfig) -> DayConfig {
+        DayConfig {
+            input: other.input.or(self.input),
+            part: other.part.or(self.part),
+            time: other.time.or(self.time),
+            cache: other.cache.or(self.cache),
+        }
+    }
+}
+
+fn merge_days(
+    mut days: HashMap<String, DayConfig>,
+    other: HashMap<String, DayConfig>,
+) -> HashMap<String, DayConfig> {
+    for (day, config) in other {
+        let merged = days.remove(&day).unwrap_or_default().merge(config);
+        days.insert(day, merged);
+    }
+    days
+}
+
+// Settings under [years.YYYY], for years other than the default one or to
+// override the top-level settings for it.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct YearConfig {
+    pub input_dir: Option<PathBuf>,
+    pub days: HashMap<String, DayConfig>,
+}
+
+impl YearConfig {
+    fn merge(self, other: YearConfig) -> YearConfig {
+        YearConfig {
+            input_dir: other.input_dir.or(self.input_dir),
+            days: merge_days(self.days, other.days),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub session: Option<String>,
+    // The year `aoc` works on when not given --year.
+    pub year: Option<u32>,
+    pub input_dir: Option<PathBuf>,
+    pub format: Option<OutputFormat>,
+    pub report_format: Option<report::Format>,
+    // Only apply to the default year.
+    pub days: HashMap<String, DayConfig>,
+    pub years: HashMap<String, YearConfig>,
+}
+
+impl Config {
+    pub fn global_path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+            .map(|dir| dir.join("aoc2021").join("config.toml"))
+    }
+
+    pub fn read(path: &Path) -> Result<Config> {
+        let text = fs::read_to_string(path).map_err(|source| AocError::Open {
+            path: path.to_owned(),
+            source,
+        })?;
+        toml::from_str(&text).map_err(|err| AocError::Config {
+            path: path.to_owned(),
+            message: err.message().to_string(),
+        })
+    }
+
+    fn read_if_exists(path: &Path) -> Result<Config> {
+        if path.exists() {
+            Config::read(path)
+        } else {
+            Ok(Config::default())
+        }
+    }
+
+    pub fn load() -> Result<Config> {
+        let global = match Config::global_path() {
+            Some(path) => Config::read_if_exists(&path)?,
+            None => Config::default(),
+        };
+        let local = Config::read_if_exists(Path::new(LOCAL_CONFIG))?;
+        Ok(global.merge(local))
+    }
+
+    fn merge(mut self, other: Config) -> Config {
+        for (year, config) in other.years {
+            let merged = self.years.remove(&year).unwrap_or_default().merge(config);
+            self.years.insert(year, merged);
+        }
+
+        Config {
+            session: other.session.or(self.session),
+            year: other.year.or(self.year),
+            input_dir: other.input_dir.or(self.input_dir),
+            format: other.format.or(self.format),
+            report_format: other.report_format.or(self.report_format),
+            days: merge_days(self.days, other.days),
+            years: self.years,
+        }
+    }
+
+    pub fn year(&self, year: u32) -> YearConfig {
+        self.years
+            .get(&year.to_string())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn day(&self, year: u32, day: u32) -> DayConfig {
+        let day = day.to_string();
+        let config = self.year(year).days.remove(&day).unwrap_or_default();
+        if year == DEFAULT_YEAR {
+            let base = self.days.get(&day).cloned().unwrap_or_default();
+            base.merge(config)
+        } else {
+            config
+        }
+    }
+
+    // Whether anything says where the inputs for `year` live, as opposed to
+    // falling back on the default directory.
+    pub fn has_input_dir(&self, year: u32) -> bool {
+        self.input_dir.is_some() || self.year(year).input_dir.is_some()
+    }
+
+    // input/ for the default year and input/YYYY/ for the others, unless
+    // configured otherwise.
+    pub fn input_dir(&self, year: u32) -> PathBuf {
+        if let Some(input_dir) = self.year(year).input_dir {
+            return input_dir;
+        }
+        let input_dir = self
+            .input_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("input"));
+        if year == DEFAULT_YEAR {
+            input_dir
+        } else {
+            input_dir.join(year.to_string())
+        }
+    }
+
+    pub fn input_path(&self, year: u32, day: u32) -> PathBuf {
+        self.day(year, day)
+            .input
+            .unwrap_or_else(|| self.input_dir(year).join(format!("day{:02}.txt", day)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_years() {
+        let config: Config = toml::from_str(
+            r#"
+            input_dir = "puzzles"
+
+            [days.1]
+            part = 1
+            time = true
+
+            [years.2021.days.1]
+            part = 2
+
+            [years.2022]
+            input_dir = "puzzles-2022"
+
+            [years.2022.days.3]
+            input = "three.txt"
+            "#,
+        )
+        .unwrap();
+
+        let day = config.day(2021, 1);
+        assert_eq!(day.part, Some(Part::Two));
+        assert_eq!(day.time, Some(true));
+        assert_eq!(config.day(2022, 1).time, None);
+
+        assert_eq!(config.input_path(2021, 2), Path::new("puzzles/day02.txt"));
+        assert_eq!(
+            config.input_path(2022, 2),
+            Path::new("puzzles-2022/day02.txt")
+        );
+        assert_eq!(config.input_path(2022, 3), Path::new("three.txt"));
+        assert_eq!(config.input_dir(2023), Path::new("puzzles/2023"));
+        assert_eq!(Config::default().input_dir(2021), Path::new("input"));
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign};
+
+// A multiset, counting how many times each item has been added.  Counts are
+// u64 rather than usize so they don't overflow on 32-bit targets like wasm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T: Eq + Hash> {
+    counts: HashMap<T, u64>,
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Self {
+        Counter {
+            counts: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, item: T) {
+        self.add(item, 1);
+    }
+
+    pub fn add(&mut self, item: T, count: u64) {
+        *self.counts.entry(item).or_default() += count;
+    }
+
+    pub fn get(&self, item: &T) -> u64 {
+        self.counts.get(item).copied().unwrap_or_default()
+    }
+
+    // The number of distinct items.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    // The number of items, counting repeats.
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&T, u64)> + '_ {
+        self.counts.iter().map(|(item, &count)| (item, count))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &T> + '_ {
+        self.counts.keys()
+    }
+
+    // Every item, most frequent first.
+    pub fn most_common(&self) -> Vec<(&T, u64)> {
+        let mut items = self.iter().collect::<Vec<_>>();
+        items.sort_by(|(_, a), (_, b)| b.cmp(a));
+        items
+    }
+
+    pub fn max(&self) -> Option<(&T, u64)> {
+        self.iter().max_by_key(|&(_, count)| count)
+    }
+
+    pub fn min(&self) -> Option<(&T, u64)> {
+        self.iter().min_by_key(|&(_, count)| count)
+    }
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<(T, u64)> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = (T, u64)>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
+
+impl<T: Eq + Hash> Extend<(T, u64)> for Counter<T> {
+    fn extend<I: IntoIterator<Item = (T, u64)>>(&mut self, iter: I) {
+        for (item, count) in iter {
+            self.add(item, count);
+        }
+    }
+}
+
+impl<T: Eq + Hash> AddAssign for Counter<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.extend(other.counts);
+    }
+}
+
+impl<T: Eq + Hash> Add for Counter<T> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_counter() {
+        let counter = "abracadabra".chars().collect::<Counter<_>>();
+        assert_eq!(counter.get(&'a'), 5);
+        assert_eq!(counter.get(&'z'), 0);
+        assert_eq!(counter.len(), 5);
+        assert_eq!(counter.total(), 11);
+        assert_eq!(counter.max(), Some((&'a', 5)));
+        assert_eq!(counter.min().map(|(_, count)| count), Some(1));
+        assert_eq!(counter.most_common()[0], (&'a', 5));
+        assert_eq!(counter.most_common()[4].1, 1);
+    }
+
+    #[test]
+    fn test_merge() {
+        let a = [1, 2, 2].into_iter().collect::<Counter<_>>();
+        let b = [(2, 3), (4, 1)].into_iter().collect::<Counter<_>>();
+        let sum = a + b;
+        assert_eq!(sum.get(&1), 1);
+        assert_eq!(sum.get(&2), 5);
+        assert_eq!(sum.get(&4), 1);
+        assert_eq!(sum.total(), 7);
+    }
+}
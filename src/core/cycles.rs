@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Where the states x0, x1 = step(x0), x2 = step(x1), ... start repeating:
+// `start` is the first step whose state comes round again, and it does so
+// every `period` steps after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    // The first step with the same state as step `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+// Floyd's tortoise and hare, finding the cycle with no more memory than a
+// few states.  Runs forever if the states never repeat.
+pub fn floyd<S: PartialEq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    // The hare is now a multiple of the period ahead, so they meet again at
+    // the start of the cycle if the tortoise starts over.
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+
+    Cycle { start, period }
+}
+
+// Brent's algorithm, which finds the same cycle as `floyd` with fewer calls to
+// `step`.  Runs forever if the states never repeat.
+pub fn brent<S: PartialEq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // Look for the period in windows of doubling length.
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, period }
+}
+
+// The state after `steps` steps, given the `cycle` the states go round.
+pub fn fast_forward<S>(initial: S, mut step: impl FnMut(&S) -> S, cycle: Cycle, steps: usize) -> S {
+    (0..cycle.equivalent_step(steps)).fold(initial, |state, _| step(&state))
+}
+
+// The state after `steps` steps, stopping to skip ahead as soon as a state
+// comes round again.  Keeps every state up to then.
+pub fn state_after<S: Eq + Hash + Clone>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    steps: usize,
+) -> S {
+    let mut seen = HashMap::new();
+    let mut history = vec![];
+    let mut state = initial;
+
+    for n in 0..steps {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                period: n - start,
+            };
+            return history.swap_remove(cycle.equivalent_step(steps));
+        }
+        seen.insert(state.clone(), n);
+        history.push(state.clone());
+        state = step(&state);
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // 0, 1, 2, then round 3, 4, 5, 6 forever.
+    fn lasso(x: &u64) -> u64 {
+        if *x < 6 {
+            x + 1
+        } else {
+            3
+        }
+    }
+
+    fn pseudo_random(x: &u64) -> u64 {
+        (x * x + 1) % 1009
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let cycle = Cycle {
+            start: 3,
+            period: 4,
+        };
+        assert_eq!(floyd(0, lasso), cycle);
+        assert_eq!(brent(0, lasso), cycle);
+
+        assert_eq!(floyd(3, lasso).start, 0);
+        assert_eq!(brent(7, pseudo_random), floyd(7, pseudo_random));
+    }
+
+    #[test]
+    fn test_equivalent_step() {
+        let cycle = Cycle {
+            start: 3,
+            period: 4,
+        };
+        assert_eq!(cycle.equivalent_step(2), 2);
+        assert_eq!(cycle.equivalent_step(3), 3);
+        assert_eq!(cycle.equivalent_step(8), 4);
+        assert_eq!(cycle.equivalent_step(1_000_000_000_000), 4);
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let steps = 1_000_000_007;
+        let cycle = brent(7, pseudo_random);
+        let expected = (0..cycle.equivalent_step(steps)).fold(7, |x, _| pseudo_random(&x));
+
+        assert_eq!(fast_forward(7, pseudo_random, cycle, steps), expected);
+        assert_eq!(state_after(7, pseudo_random, steps), expected);
+        assert_eq!(state_after(0, lasso, 2), 2);
+        assert_eq!(state_after(0, lasso, 1_000_000), 4);
+    }
+}
@@ -0,0 +1,142 @@
+use miette::{Diagnostic, SourceSpan};
+use std::fmt::Display;
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+// What the binaries exit with, so scripts can tell why they failed.
+pub mod exit_code {
+    pub const FAILURE: i32 = 1;
+    pub const BAD_ARGUMENTS: i32 = 2;
+    pub const BAD_INPUT: i32 = 3;
+    pub const NO_SOLUTION: i32 = 4;
+}
+
+#[derive(Debug, Error, Diagnostic)]
+pub enum AocError {
+    #[error("failed to open {}: {source}", path.display())]
+    #[diagnostic(code(aoc::open), help("pass `-` to read the input from stdin"))]
+    Open { path: PathBuf, source: io::Error },
+
+    #[error("failed to read input: {0}")]
+    #[diagnostic(code(aoc::io))]
+    Io(#[from] io::Error),
+
+    #[error("invalid input: {0}")]
+    #[diagnostic(code(aoc::parse))]
+    Parse(String),
+
+    #[error("invalid input: line {line}, column {column}: {message}")]
+    #[diagnostic(
+        code(aoc::parse),
+        help("check the input is the whole of this day's puzzle input")
+    )]
+    ParseAt {
+        line: usize,
+        column: usize,
+        message: String,
+        // The whole input, for showing the lines around where it failed.
+        #[source_code]
+        input: String,
+        #[label("{message}")]
+        span: SourceSpan,
+    },
+
+    #[error("failed to evaluate: {0}")]
+    #[diagnostic(code(aoc::evaluate))]
+    Evaluate(String),
+
+    #[error("invalid config {}: {message}", path.display())]
+    #[diagnostic(code(aoc::config))]
+    Config { path: PathBuf, message: String },
+
+    #[error("day {day}: {message}")]
+    #[diagnostic(code(aoc::day))]
+    Day { day: u32, message: String },
+
+    #[error("still running after {0} steps")]
+    #[diagnostic(code(aoc::no_solution), help("the input may not have a solution"))]
+    StepLimit(usize),
+
+    #[error("no solution found for part {part}: {message}")]
+    #[diagnostic(code(aoc::no_solution))]
+    NoSolution { part: u32, message: String },
+
+    #[error("failed to start thread pool: {0}")]
+    #[diagnostic(code(aoc::thread_pool))]
+    ThreadPool(String),
+
+    #[error("failed to start server: {0}")]
+    #[diagnostic(code(aoc::serve))]
+    Serve(String),
+
+    #[error("failed to set up day: {0}")]
+    #[diagnostic(code(aoc::scaffold))]
+    Scaffold(String),
+
+    #[error("panicked: {0}")]
+    #[diagnostic(code(aoc::panic), help("this is a bug in the solver"))]
+    Panic(String),
+}
+
+pub type Result<T, E = AocError> = std::result::Result<T, E>;
+
+impl AocError {
+    pub fn parse(message: impl Display) -> Self {
+        AocError::Parse(message.to_string())
+    }
+
+    pub fn no_solution(part: u32, message: impl Display) -> Self {
+        AocError::NoSolution {
+            part,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        use AocError::*;
+        match self {
+            Open { .. } | Config { .. } => exit_code::BAD_ARGUMENTS,
+            Parse(_) | ParseAt { .. } | Evaluate(_) => exit_code::BAD_INPUT,
+            StepLimit(_) | NoSolution { .. } => exit_code::NO_SOLUTION,
+            Io(_) | Day { .. } | ThreadPool(_) | Serve(_) | Scaffold(_) | Panic(_) => {
+                exit_code::FAILURE
+            }
+        }
+    }
+
+    // Prints the error to stderr, on one line if `plain` for whatever's
+    // reading it, otherwise with miette's rendering of any snippet and help.
+    pub fn report(self, prefix: &str, plain: bool) {
+        if plain {
+            eprintln!("{}: {}", prefix, self);
+        } else {
+            eprintln!("{}: {:?}", prefix, miette::Report::new(self));
+        }
+    }
+}
+
+impl From<nom::Err<nom::error::Error<&str>>> for AocError {
+    fn from(err: nom::Err<nom::error::Error<&str>>) -> Self {
+        match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => {
+                match err.input.lines().next().filter(|line| !line.is_empty()) {
+                    Some(line) => AocError::Parse(format!("unexpected \"{}\"", line)),
+                    None => AocError::Parse("unexpected end of input".to_string()),
+                }
+            }
+            nom::Err::Incomplete(_) => AocError::Parse("unexpected end of input".to_string()),
+        }
+    }
+}
+
+pub fn parse_value<T>(value: &str) -> Result<T>
+where
+    T: std::str::FromStr,
+    T::Err: Display,
+{
+    value
+        .trim()
+        .parse()
+        .map_err(|err| AocError::Parse(format!("\"{}\": {}", value, err)))
+}
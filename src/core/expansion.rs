@@ -0,0 +1,88 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+// Counts how many ways there are of reaching each terminal state from the
+// `initial` states, each of which can be reached `count` ways to start with.
+// `transitions` gives the states that follow on from a state, each with how
+// many ways it can be reached from there.  Every transition must go to a state
+// that's greater than the one it's from, so that by the time a state is
+// expanded all the ways of reaching it are known and it's only expanded once.
+pub fn weighted_state_expansion<S, I, F, T>(
+    initial: impl IntoIterator<Item = (S, u64)>,
+    mut transitions: F,
+    mut is_terminal: T,
+) -> HashMap<S, u64>
+where
+    S: Ord + Hash + Clone,
+    I: IntoIterator<Item = (S, u64)>,
+    F: FnMut(&S) -> I,
+    T: FnMut(&S) -> bool,
+{
+    let mut terminal = HashMap::new();
+    let mut pending = BTreeMap::new();
+
+    let mut add = |pending: &mut BTreeMap<S, u64>, state: S, count: u64| {
+        if is_terminal(&state) {
+            *terminal.entry(state).or_insert(0) += count;
+        } else {
+            *pending.entry(state).or_insert(0) += count;
+        }
+    };
+
+    for (state, count) in initial {
+        add(&mut pending, state, count);
+    }
+
+    while let Some((state, count)) = pending.pop_first() {
+        for (next, ways) in transitions(&state) {
+            debug_assert!(next > state, "transition doesn't lead to a greater state");
+            if ways > 0 {
+                add(&mut pending, next, count * ways);
+            }
+        }
+    }
+
+    terminal
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_dice() {
+        // The totals of two six-sided dice, as (dice rolled, total).
+        let totals = weighted_state_expansion(
+            [((0, 0), 1)],
+            |&(rolled, total)| (1..=6).map(move |roll| ((rolled + 1, total + roll), 1)),
+            |&(rolled, _)| rolled == 2,
+        );
+
+        assert_eq!(totals.values().sum::<u64>(), 36);
+        assert_eq!(totals[&(2, 7)], 6);
+        assert_eq!(totals[&(2, 12)], 1);
+        assert!(!totals.contains_key(&(2, 1)));
+    }
+
+    #[test]
+    fn test_paths() {
+        // Paths right and down across a grid, starting twice from the
+        // top-left and once from one to its right.
+        let paths = weighted_state_expansion(
+            [((0, 0), 2), ((1, 0), 1)],
+            |&(x, y)| [((x + 1, y), 1), ((x, y + 1), 1)],
+            |&(x, y)| x + y == 4,
+        );
+
+        assert_eq!(paths[&(2, 2)], 2 * 6 + 3);
+        assert_eq!(paths[&(4, 0)], 2 + 1);
+        assert_eq!(paths[&(0, 4)], 2);
+        assert_eq!(paths.values().sum::<u64>(), 2 * 16 + 8);
+    }
+
+    #[test]
+    fn test_terminal_start() {
+        let reached = weighted_state_expansion([(5, 3)], |&n| [(n + 1, 1)], |&n| n >= 5);
+        assert_eq!(reached, HashMap::from([(5, 3)]));
+    }
+}
@@ -0,0 +1,234 @@
+use crate::core::error::{AocError, Result};
+use crate::core::position::Position;
+use std::cmp::max;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+    Diagonal,
+}
+
+// A line segment that's horizontal, vertical or at 45 degrees, including both
+// ends.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Line {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Line {
+    pub fn new(start: Position, end: Position) -> Self {
+        Self::try_new(start, end).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    // As `new`, but an error rather than a panic if the ends aren't in line.
+    pub fn try_new(start: Position, end: Position) -> Result<Self> {
+        let (dx, dy) = ((end.x - start.x).abs(), (end.y - start.y).abs());
+        if dx == 0 || dy == 0 || dx == dy {
+            Ok(Line { start, end })
+        } else {
+            Err(AocError::parse(format!(
+                "line {},{} -> {},{} isn't horizontal, vertical or diagonal",
+                start.x, start.y, end.x, end.y
+            )))
+        }
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        if self.is_horizontal() {
+            Orientation::Horizontal
+        } else if self.is_vertical() {
+            Orientation::Vertical
+        } else {
+            Orientation::Diagonal
+        }
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        self.orientation() == Orientation::Diagonal
+    }
+
+    // The step from each point to the next, each of x and y being -1, 0 or 1.
+    pub fn direction(&self) -> (i64, i64) {
+        (
+            (self.end.x - self.start.x).signum(),
+            (self.end.y - self.start.y).signum(),
+        )
+    }
+
+    // The number of points on the line.
+    pub fn length(&self) -> i64 {
+        max(
+            (self.end.x - self.start.x).abs(),
+            (self.end.y - self.start.y).abs(),
+        ) + 1
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Position> {
+        let (dx, dy) = self.direction();
+        let start = self.start;
+        (0..self.length()).map(move |offset| start.offset(offset * dx, offset * dy))
+    }
+
+    // How many steps along the line `position` is, if it's on the line.
+    fn steps_to(&self, position: Position) -> Option<i64> {
+        let (dx, dy) = self.direction();
+        let (x, y) = (position.x - self.start.x, position.y - self.start.y);
+        if x * dy != y * dx {
+            return None;
+        }
+        let steps = if dx != 0 { x * dx } else { y * dy };
+        (0..self.length()).contains(&steps).then_some(steps)
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        if self.start == self.end {
+            position == self.start
+        } else {
+            self.steps_to(position).is_some()
+        }
+    }
+
+    // The points shared by both lines, which are always themselves a line.
+    pub fn intersection(&self, other: &Line) -> Option<Line> {
+        if self.start == self.end {
+            return other.contains(self.start).then_some(*self);
+        }
+        if other.start == other.end {
+            return self.contains(other.start).then_some(*other);
+        }
+
+        let (dx, dy) = self.direction();
+        let (other_dx, other_dy) = other.direction();
+        let cross = dx * other_dy - dy * other_dx;
+
+        if cross == 0 {
+            // Parallel, so they overlap along a stretch of `self` if at all.
+            let (x, y) = (other.start.x - self.start.x, other.start.y - self.start.y);
+            if x * dy != y * dx {
+                return None;
+            }
+            let project = |position: Position| {
+                if dx != 0 {
+                    (position.x - self.start.x) * dx
+                } else {
+                    (position.y - self.start.y) * dy
+                }
+            };
+            let (a, b) = (project(other.start), project(other.end));
+            let first = a.min(b).max(0);
+            let last = a.max(b).min(self.length() - 1);
+            (first <= last).then(|| Line {
+                start: self.start.offset(first * dx, first * dy),
+                end: self.start.offset(last * dx, last * dy),
+            })
+        } else {
+            // Solve start + steps * direction = other.start + s * other_direction.
+            let (x, y) = (other.start.x - self.start.x, other.start.y - self.start.y);
+            let numerator = x * other_dy - y * other_dx;
+            if numerator % cross != 0 {
+                return None;
+            }
+            let steps = numerator / cross;
+            let point = self.start.offset(steps * dx, steps * dy);
+            ((0..self.length()).contains(&steps) && other.contains(point)).then_some(Line {
+                start: point,
+                end: point,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn line(x1: i64, y1: i64, x2: i64, y2: i64) -> Line {
+        Line::new(Position::new(x1, y1), Position::new(x2, y2))
+    }
+
+    #[test]
+    fn test_points() {
+        let diagonal = line(3, 1, 1, 3);
+        assert_eq!(diagonal.orientation(), Orientation::Diagonal);
+        assert_eq!(
+            diagonal.points().collect::<Vec<_>>(),
+            vec![
+                Position::new(3, 1),
+                Position::new(2, 2),
+                Position::new(1, 3)
+            ]
+        );
+        assert_eq!(line(0, 4, 5, 4).orientation(), Orientation::Horizontal);
+        assert_eq!(line(2, 9, 2, 4).length(), 6);
+    }
+
+    #[test]
+    fn test_intersection() {
+        assert_eq!(
+            line(0, 0, 4, 0).intersection(&line(6, 0, 2, 0)),
+            Some(line(2, 0, 4, 0))
+        );
+        assert_eq!(line(0, 0, 4, 0).intersection(&line(5, 0, 6, 0)), None);
+        assert_eq!(line(0, 0, 4, 0).intersection(&line(0, 1, 4, 1)), None);
+        assert_eq!(
+            line(0, 0, 4, 4).intersection(&line(0, 4, 4, 0)),
+            Some(line(2, 2, 2, 2))
+        );
+        assert_eq!(line(0, 0, 1, 1).intersection(&line(1, 0, 0, 1)), None);
+        assert_eq!(
+            line(2, 0, 2, 5).intersection(&line(0, 3, 3, 3)),
+            Some(line(2, 3, 2, 3))
+        );
+        assert_eq!(
+            line(1, 1, 1, 1).intersection(&line(0, 0, 3, 3)),
+            Some(line(1, 1, 1, 1))
+        );
+    }
+
+    #[test]
+    fn test_intersection_matches_points() {
+        let lines = [
+            line(0, 9, 5, 9),
+            line(8, 0, 0, 8),
+            line(9, 4, 3, 4),
+            line(2, 2, 2, 1),
+            line(7, 0, 7, 4),
+            line(6, 4, 2, 0),
+            line(0, 9, 2, 9),
+            line(3, 4, 1, 4),
+            line(0, 0, 8, 8),
+            line(5, 5, 8, 2),
+        ];
+        for a in &lines {
+            for b in &lines {
+                let expected = a
+                    .points()
+                    .filter(|&p| b.contains(p))
+                    .collect::<HashSet<_>>();
+                let actual = a
+                    .intersection(b)
+                    .map(|overlap| overlap.points().collect::<HashSet<_>>())
+                    .unwrap_or_default();
+                assert_eq!(actual, expected, "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_new() {
+        let (start, end) = (Position::new(0, 0), Position::new(2, 1));
+        assert!(Line::try_new(start, end).is_err());
+        assert!(Line::try_new(start, Position::new(-2, 2)).is_ok());
+    }
+}
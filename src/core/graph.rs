@@ -0,0 +1,148 @@
+use crate::core::a_star;
+use crate::core::search;
+use std::collections::HashMap;
+
+pub type NodeId = usize;
+
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    edges: Vec<Vec<(NodeId, usize)>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Graph::default()
+    }
+
+    // The node with the given name, adding it if it's new.
+    pub fn node(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.edges.push(vec![]);
+        id
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        0..self.len()
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, cost: usize) {
+        self.edges[from].push((to, cost));
+    }
+
+    pub fn add_undirected_edge(&mut self, a: NodeId, b: NodeId, cost: usize) {
+        self.add_edge(a, b, cost);
+        self.add_edge(b, a, cost);
+    }
+
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, usize)] {
+        &self.edges[id]
+    }
+
+    pub fn neighbours(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges[id].iter().map(|&(to, _)| to)
+    }
+
+    pub fn shortest_distance(&self, from: NodeId, to: NodeId) -> Option<usize> {
+        a_star::dijkstra(from, |&id| self.edges(id).to_vec(), |&id| id == to)
+            .map(|(_, distance)| distance)
+    }
+
+    // Groups of nodes that can reach each other.  Edges are only followed the
+    // way they were added, so this expects them to be undirected.
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut component = vec![None; self.len()];
+        let mut components = vec![];
+
+        for id in self.nodes() {
+            if component[id].is_some() {
+                continue;
+            }
+
+            let members = search::dfs(id, |&id| self.neighbours(id), |&id| component[id].is_none());
+            for &member in &members {
+                component[member] = Some(components.len());
+            }
+            components.push(members);
+        }
+
+        components
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str, usize)]) -> Graph {
+        let mut graph = Graph::new();
+        for &(a, b, cost) in edges {
+            let (a, b) = (graph.node(a), graph.node(b));
+            graph.add_undirected_edge(a, b, cost);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_interning() {
+        let mut graph = Graph::new();
+        let a = graph.node("a");
+        let b = graph.node("b");
+        assert_ne!(a, b);
+        assert_eq!(graph.node("a"), a);
+        assert_eq!(graph.id("b"), Some(b));
+        assert_eq!(graph.id("c"), None);
+        assert_eq!(graph.name(b), "b");
+        assert_eq!(graph.len(), 2);
+    }
+
+    #[test]
+    fn test_shortest_distance() {
+        let graph = graph(&[("a", "b", 1), ("b", "c", 1), ("a", "c", 5), ("d", "e", 1)]);
+        let id = |name| graph.id(name).unwrap();
+        assert_eq!(graph.shortest_distance(id("a"), id("c")), Some(2));
+        assert_eq!(graph.shortest_distance(id("a"), id("e")), None);
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let graph = graph(&[("a", "b", 1), ("b", "c", 1), ("d", "e", 1)]);
+        let mut components = graph
+            .connected_components()
+            .into_iter()
+            .map(|component| {
+                let mut names = component
+                    .into_iter()
+                    .map(|id| graph.name(id))
+                    .collect::<Vec<_>>();
+                names.sort_unstable();
+                names
+            })
+            .collect::<Vec<_>>();
+        components.sort();
+        assert_eq!(components, vec![vec!["a", "b", "c"], vec!["d", "e"]]);
+    }
+}
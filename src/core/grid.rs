@@ -0,0 +1,486 @@
+use crate::core::a_star;
+use crate::core::error::{AocError, Result};
+use crate::core::position::Position;
+use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+use std::io::BufRead;
+use std::ops::{Index, IndexMut};
+
+// A rectangular field stored row by row, with (0, 0) in the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "grid is the wrong size");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    // One row per line, converting each character with `parse`.
+    pub fn from_lines_with<F>(reader: impl BufRead, mut parse: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Result<T>,
+    {
+        let mut width = None;
+        let mut cells = vec![];
+        let mut height = 0;
+
+        for line in reader.lines() {
+            let line = line?;
+            let row = line.chars().map(&mut parse).collect::<Result<Vec<_>>>()?;
+            match width {
+                None => width = Some(row.len()),
+                Some(width) if width != row.len() => {
+                    return Err(AocError::parse(format!(
+                        "row {} has {} cells, expected {}",
+                        height + 1,
+                        row.len(),
+                        width
+                    )))
+                }
+                Some(_) => {}
+            }
+            cells.extend(row);
+            height += 1;
+        }
+
+        Ok(Grid::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn from_lines(reader: impl BufRead) -> Result<Self>
+    where
+        T: TryFrom<char>,
+    {
+        Self::from_lines_with(reader, |c| {
+            T::try_from(c).map_err(|_| AocError::parse(format!("invalid cell {:?}", c)))
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        (0..self.width as i64).contains(&position.x)
+            && (0..self.height as i64).contains(&position.y)
+    }
+
+    fn index_of(&self, position: Position) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.y as usize * self.width + position.x as usize)
+    }
+
+    fn position_of(&self, index: usize) -> Position {
+        Position::new((index % self.width) as i64, (index / self.width) as i64)
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.index_of(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index_of(position).map(|index| &mut self.cells[index])
+    }
+
+    // Every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        (0..self.cells.len()).map(|index| self.position_of(index))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (self.position_of(index), value))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        self.cells.iter_mut()
+    }
+
+    // The grid as rows of characters, one per cell.
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width.max(1)) {
+            text.extend(row.iter().map(&cell));
+            text.push('\n');
+        }
+        text
+    }
+
+    // The up to four positions next to `position` that are in the grid.
+    pub fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        position
+            .adjacent()
+            .filter(move |&neighbour| self.contains(neighbour))
+    }
+
+    // As `neighbours`, but including diagonals.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        position
+            .adjacent8()
+            .filter(move |&neighbour| self.contains(neighbour))
+    }
+
+    // The positions from `start` going `delta` at a time, stopping at the edge
+    // of the grid or the first cell that isn't `clear`.
+    pub fn ray<'a>(
+        &'a self,
+        start: Position,
+        delta: (i64, i64),
+        mut clear: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Position> + 'a {
+        start
+            .ray(delta)
+            .take_while(move |&position| self.get(position).is_some_and(&mut clear))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    // Every cell along with the `width` by `height` block of cells centred on
+    // it, row by row.
+    pub fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = Window<'_, T>> + '_ {
+        assert!(
+            width % 2 == 1 && height % 2 == 1,
+            "windows need a centre cell"
+        );
+        self.positions().map(move |centre| Window {
+            grid: self,
+            centre,
+            radius_x: (width / 2) as i64,
+            radius_y: (height / 2) as i64,
+        })
+    }
+
+    // A copy of the grid surrounded by `border` cells of `value` on each side.
+    pub fn pad(&self, border: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        let offset = border as i64;
+        let (width, height) = (self.width + 2 * border, self.height + 2 * border);
+        let mut padded = Grid::filled(width, height, value);
+        for (position, cell) in self.iter() {
+            padded[position.offset(offset, offset)] = cell.clone();
+        }
+        padded
+    }
+
+    // A new grid of the given size, with each cell copied from the position
+    // `source` maps it to in this grid.
+    fn remap(&self, width: usize, height: usize, source: impl Fn(i64, i64) -> Position) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height as i64)
+            .flat_map(|y| (0..width as i64).map(move |x| (x, y)))
+            .map(|(x, y)| self[source(x, y)].clone())
+            .collect();
+        Grid::new(width, height, cells)
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |x, y| Position::new(y, x))
+    }
+
+    // Mirror left to right.
+    pub fn flip_x(&self) -> Self
+    where
+        T: Clone,
+    {
+        let max_x = self.width as i64 - 1;
+        self.remap(self.width, self.height, |x, y| Position::new(max_x - x, y))
+    }
+
+    // Mirror top to bottom.
+    pub fn flip_y(&self) -> Self
+    where
+        T: Clone,
+    {
+        let max_y = self.height as i64 - 1;
+        self.remap(self.width, self.height, |x, y| Position::new(x, max_y - y))
+    }
+
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let max_y = self.height as i64 - 1;
+        self.remap(self.height, self.width, |x, y| Position::new(y, max_y - x))
+    }
+
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let max_x = self.width as i64 - 1;
+        self.remap(self.height, self.width, |x, y| Position::new(max_x - y, x))
+    }
+}
+
+// A block of cells around one cell of a grid, from `Grid::windows`.
+pub struct Window<'a, T> {
+    grid: &'a Grid<T>,
+    centre: Position,
+    radius_x: i64,
+    radius_y: i64,
+}
+
+impl<'a, T> Window<'a, T> {
+    pub fn centre(&self) -> Position {
+        self.centre
+    }
+
+    pub fn value(&self) -> &'a T {
+        &self.grid[self.centre]
+    }
+
+    // The cell `dx`, `dy` from the centre, if it's in the grid.
+    pub fn get(&self, dx: i64, dy: i64) -> Option<&'a T> {
+        self.grid.get(self.centre.offset(dx, dy))
+    }
+
+    // Every cell of the window row by row, `None` where it hangs off the grid.
+    pub fn cells(&self) -> impl Iterator<Item = Option<&'a T>> + '_ {
+        (-self.radius_y..=self.radius_y)
+            .flat_map(move |dy| (-self.radius_x..=self.radius_x).map(move |dx| self.get(dx, dy)))
+    }
+
+    // The values of the up to four cells next to the centre.
+    pub fn adjacent(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.grid
+            .neighbours(self.centre)
+            .map(move |position| &self.grid[position])
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+// A search for the cheapest path across a grid, where moving onto a cell
+// costs its value.
+#[derive(Clone)]
+pub struct GridPath<'a> {
+    grid: &'a Grid<usize>,
+    connectivity: Connectivity,
+    min_cost: usize,
+    position: Position,
+    goal: Position,
+}
+
+impl<'a> GridPath<'a> {
+    pub fn new(
+        grid: &'a Grid<usize>,
+        start: Position,
+        goal: Position,
+        connectivity: Connectivity,
+    ) -> Self {
+        GridPath {
+            grid,
+            connectivity,
+            min_cost: grid.values().copied().min().unwrap_or(0),
+            position: start,
+            goal,
+        }
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    fn successor(&self, position: Position) -> Self {
+        GridPath {
+            position,
+            ..self.clone()
+        }
+    }
+}
+
+impl<'a> a_star::State for GridPath<'a> {
+    fn min_remaining_cost(&self) -> usize {
+        let distance = match self.connectivity {
+            Connectivity::Four => self.position.manhattan_distance(self.goal),
+            Connectivity::Eight => self.position.chebyshev_distance(self.goal),
+        };
+        distance as usize * self.min_cost
+    }
+
+    fn is_complete(&self) -> bool {
+        self.position == self.goal
+    }
+
+    fn successors(&self) -> Box<dyn Iterator<Item = (Self, usize)> + '_> {
+        let neighbours: Box<dyn Iterator<Item = Position>> = match self.connectivity {
+            Connectivity::Four => Box::new(self.grid.neighbours(self.position)),
+            Connectivity::Eight => Box::new(self.grid.neighbours8(self.position)),
+        };
+        Box::new(neighbours.map(|position| (self.successor(position), self.grid[position])))
+    }
+}
+
+// Paths in the same search only differ by position.
+impl<'a> Hash for GridPath<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.position.hash(state);
+    }
+}
+
+impl<'a> PartialEq for GridPath<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.position == other.position
+    }
+}
+
+impl<'a> Eq for GridPath<'a> {}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::position::{Diagonal, Direction};
+
+    #[test]
+    fn test_from_lines() {
+        let grid = Grid::<Direction>::from_lines("^>\nv<\n".as_bytes()).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid[Position::new(1, 0)], Direction::East);
+        assert_eq!(grid[Position::new(0, 1)], Direction::South);
+        assert_eq!(grid.get(Position::new(2, 0)), None);
+        assert_eq!(grid.get(Position::new(0, -1)), None);
+        assert_eq!(grid.render(|&direction| direction.into()), "^>\nv<\n");
+
+        assert!(Grid::<Direction>::from_lines("^>\nv\n".as_bytes()).is_err());
+        assert!(Grid::<Direction>::from_lines("^x\n".as_bytes()).is_err());
+    }
+
+    fn letters(text: &str) -> Grid<char> {
+        Grid::from_lines_with(text.as_bytes(), Ok).unwrap()
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = letters("abc\ndef\n");
+        assert_eq!(grid.transpose(), letters("ad\nbe\ncf\n"));
+        assert_eq!(grid.flip_x(), letters("cba\nfed\n"));
+        assert_eq!(grid.flip_y(), letters("def\nabc\n"));
+        assert_eq!(grid.rotate_cw(), letters("da\neb\nfc\n"));
+        assert_eq!(grid.rotate_ccw(), letters("cf\nbe\nad\n"));
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.rotate_cw().rotate_cw(), grid.flip_x().flip_y());
+    }
+
+    #[test]
+    fn test_windows() {
+        let grid = letters("abc\ndef\n");
+        let windows = grid
+            .windows(3, 3)
+            .map(|window| {
+                window
+                    .cells()
+                    .map(|cell| cell.copied().unwrap_or('.'))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(windows.len(), 6);
+        assert_eq!(windows[0], "....ab.de");
+        assert_eq!(windows[4], "abcdef...");
+    }
+
+    #[test]
+    fn test_pad() {
+        let grid = letters("ab\n").pad(1, '.');
+        assert_eq!(grid, letters("....\n.ab.\n....\n"));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::filled(3, 3, 0);
+        let corner = Position::new(0, 0);
+        let centre = Position::new(1, 1);
+        assert_eq!(grid.neighbours(corner).count(), 2);
+        assert_eq!(grid.neighbours8(corner).count(), 3);
+        assert_eq!(grid.neighbours(centre).count(), 4);
+        assert_eq!(grid.neighbours8(centre).count(), 8);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = letters("a.#\n...\n..#\n");
+        let east = Direction::East.delta();
+        assert_eq!(
+            grid.ray(Position::new(0, 0), east, |&c| c == '.').count(),
+            1
+        );
+        assert_eq!(
+            grid.ray(Position::new(0, 1), east, |&c| c == '.').count(),
+            2
+        );
+        let diagonal = Diagonal::SouthEast.delta();
+        assert_eq!(
+            grid.ray(Position::new(0, 0), diagonal, |_| true)
+                .collect::<Vec<_>>(),
+            vec![Position::new(1, 1), Position::new(2, 2)]
+        );
+    }
+
+    #[test]
+    fn test_grid_path() {
+        let grid = Grid::from_lines_with("1163\n1381\n2136\n".as_bytes(), |c| {
+            Ok(c.to_digit(10).unwrap() as usize)
+        })
+        .unwrap();
+        let goal = Position::new(3, 2);
+        let cost = |connectivity| {
+            let start = GridPath::new(&grid, Position::new(0, 0), goal, connectivity);
+            a_star::solve(start).map(|(path, cost)| (path.position(), cost))
+        };
+        assert_eq!(cost(Connectivity::Four), Some((goal, 13)));
+        assert_eq!(cost(Connectivity::Eight), Some((goal, 11)));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::core::error::{AocError, Result};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+use std::path::Path;
+
+pub fn is_stdin(path: Option<&Path>) -> bool {
+    path.is_none_or(|path| path == Path::new("-"))
+}
+
+fn open_raw(path: Option<&Path>) -> Result<Box<dyn BufRead>> {
+    let reader: Box<dyn BufRead> = match path {
+        Some(path) if !is_stdin(Some(path)) => {
+            let file = File::open(path).map_err(|source| AocError::Open {
+                path: path.to_owned(),
+                source,
+            })?;
+            Box::new(BufReader::new(file))
+        }
+        _ => Box::new(io::stdin().lock()),
+    };
+    Ok(reader)
+}
+
+// Reads the whole input, normalized so that parsers only have to deal with
+// one layout.
+pub fn open(path: Option<&Path>) -> Result<Box<dyn BufRead>> {
+    let text = normalize(&io::read_to_string(open_raw(path)?)?);
+    Ok(Box::new(Cursor::new(text.into_bytes())))
+}
+
+// As `open`, but normalizing a line at a time as it's read rather than
+// reading it all up front, for inputs too big to hold in memory.
+pub fn open_streaming(path: Option<&Path>) -> Result<Box<dyn BufRead>> {
+    Ok(Box::new(Normalized::new(open_raw(path)?)))
+}
+
+// A reader giving the same text as `normalize`, holding one line at a time.
+struct Normalized<R> {
+    reader: R,
+    line: String,
+    buffer: String,
+    position: usize,
+    // Blank lines seen but not yet passed on, as they're dropped if nothing
+    // but more blank lines follows.
+    blank_lines: usize,
+    started: bool,
+}
+
+impl<R: BufRead> Normalized<R> {
+    fn new(reader: R) -> Self {
+        Normalized {
+            reader,
+            line: String::new(),
+            buffer: String::new(),
+            position: 0,
+            blank_lines: 0,
+            started: false,
+        }
+    }
+}
+
+impl<R: BufRead> BufRead for Normalized<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.position == self.buffer.len() {
+            self.buffer.clear();
+            self.position = 0;
+
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                break;
+            }
+
+            let mut line = self.line.trim_end();
+            if !self.started {
+                line = line.strip_prefix('\u{feff}').unwrap_or(line);
+                self.started = true;
+            }
+
+            if line.is_empty() {
+                self.blank_lines += 1;
+            } else {
+                for _ in 0..self.blank_lines {
+                    self.buffer.push('\n');
+                }
+                self.blank_lines = 0;
+                self.buffer.push_str(line);
+                self.buffer.push('\n');
+            }
+        }
+
+        Ok(&self.buffer.as_bytes()[self.position..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position = (self.position + amount).min(self.buffer.len());
+    }
+}
+
+impl<R: BufRead> Read for Normalized<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let amount = available.len().min(buf.len());
+        buf[..amount].copy_from_slice(&available[..amount]);
+        self.consume(amount);
+        Ok(amount)
+    }
+}
+
+// Strips any byte order mark, converts CRLF line endings to LF, removes
+// trailing whitespace from each line and ends the text with exactly one
+// newline.
+pub fn normalize(text: &str) -> String {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut normalized = String::with_capacity(text.len() + 1);
+    for line in text.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    normalized.truncate(normalized.trim_end_matches('\n').len());
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("1\r\n2\r\n"), "1\n2\n");
+        assert_eq!(normalize("\u{feff}1\n2\n"), "1\n2\n");
+        assert_eq!(normalize("1\n2"), "1\n2\n");
+        assert_eq!(normalize("1 \n\n2\t\n\n\n"), "1\n\n2\n");
+        assert_eq!(normalize(" 1\n"), " 1\n");
+        assert_eq!(normalize("\r\n"), "");
+    }
+
+    #[test]
+    fn test_normalized() {
+        for text in [
+            "1\r\n2\r\n",
+            "\u{feff}1\n2\n",
+            "1\n2",
+            "1 \n\n2\t\n\n\n",
+            " 1\n",
+            "\r\n",
+            "\n\n1\n",
+            "",
+        ] {
+            let streamed = io::read_to_string(Normalized::new(text.as_bytes())).unwrap();
+            assert_eq!(streamed, normalize(text), "{:?}", text);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+// A map from the integer points of N-dimensional space to values, updated a
+// box at a time.  Each axis is split into sections where the contents don't
+// change, with each section holding a map over the remaining axes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalMap<T, const N: usize> {
+    root: Node<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node<T> {
+    // The same value throughout the rest of the axes.
+    Leaf(T),
+    // Sections along the next axis, each running from its start up to the
+    // next one's.  The first always starts at `i64::MIN`.
+    Split(Vec<(i64, Node<T>)>),
+}
+
+impl<T: Clone + PartialEq, const N: usize> IntervalMap<T, N> {
+    pub fn new(default: T) -> Self {
+        IntervalMap {
+            root: Node::Leaf(default),
+        }
+    }
+
+    // Sets every point from `min` to `max` inclusive to `value`.
+    pub fn update(&mut self, min: [i64; N], max: [i64; N], value: T) {
+        self.root.update(&min, &max, &value);
+    }
+
+    pub fn query(&self, point: [i64; N]) -> &T {
+        let mut node = &self.root;
+        for coord in point {
+            match node {
+                Node::Leaf(value) => return value,
+                Node::Split(sections) => node = &sections[find_section(sections, coord)].1,
+            }
+        }
+        match node {
+            Node::Leaf(value) => value,
+            Node::Split(_) => unreachable!("map is deeper than its dimension"),
+        }
+    }
+
+    // The number of points with each value, in blocks, leaving out the
+    // unbounded space around everything that's been updated.
+    pub fn regions(&self) -> impl Iterator<Item = (i64, &T)> + '_ {
+        self.root.regions(N)
+    }
+
+    // The number of points whose value matches `predicate`, within the
+    // bounds of everything that's been updated.
+    pub fn measure(&self, predicate: impl Fn(&T) -> bool) -> i64 {
+        self.regions()
+            .filter(|(_, value)| predicate(value))
+            .map(|(volume, _)| volume)
+            .sum()
+    }
+}
+
+impl<T: Clone + PartialEq + Default, const N: usize> Default for IntervalMap<T, N> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+// The index of the section containing `coord`.
+fn find_section<T>(sections: &[(i64, T)], coord: i64) -> usize {
+    sections.partition_point(|(start, _)| *start <= coord) - 1
+}
+
+impl<T: Clone + PartialEq> Node<T> {
+    fn update(&mut self, min: &[i64], max: &[i64], value: &T) {
+        if min.is_empty() {
+            *self = Node::Leaf(value.clone());
+            return;
+        }
+
+        if let Node::Leaf(contents) = self {
+            *self = Node::Split(vec![(i64::MIN, Node::Leaf(contents.clone()))]);
+        }
+        let Node::Split(sections) = self else {
+            unreachable!()
+        };
+
+        let start_index = split_at(sections, min[0]);
+        let end_index = split_at(sections, max[0] + 1);
+        for (_, section) in &mut sections[start_index..end_index] {
+            section.update(&min[1..], &max[1..], value);
+        }
+
+        sections.dedup_by(|(_, next), (_, section)| next == section);
+        if let [(_, Node::Leaf(contents))] = sections.as_slice() {
+            *self = Node::Leaf(contents.clone());
+        }
+    }
+
+    fn regions(&self, depth: usize) -> Box<dyn Iterator<Item = (i64, &T)> + '_> {
+        match self {
+            Node::Leaf(value) if depth == 0 => Box::new([(1, value)].into_iter()),
+            // Stretches to infinity along the remaining axes.
+            Node::Leaf(_) => Box::new(std::iter::empty()),
+            Node::Split(sections) => {
+                Box::new(sections.windows(2).skip(1).flat_map(move |window| {
+                    let width = window[1].0 - window[0].0;
+                    window[0]
+                        .1
+                        .regions(depth - 1)
+                        .map(move |(volume, value)| (volume * width, value))
+                }))
+            }
+        }
+    }
+}
+
+// Makes sure a section starts at `coord`, returning its index.
+fn split_at<T: Clone>(sections: &mut Vec<(i64, T)>, coord: i64) -> usize {
+    let index = find_section(sections, coord);
+    if sections[index].0 == coord {
+        index
+    } else {
+        let contents = sections[index].1.clone();
+        sections.insert(index + 1, (coord, contents));
+        index + 1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_one_dimension() {
+        let mut map = IntervalMap::<u8, 1>::default();
+        map.update([0], [9], 1);
+        map.update([5], [14], 2);
+        map.update([3], [6], 0);
+        assert_eq!(*map.query([-1]), 0);
+        assert_eq!(*map.query([2]), 1);
+        assert_eq!(*map.query([4]), 0);
+        assert_eq!(*map.query([7]), 2);
+        assert_eq!(*map.query([15]), 0);
+        assert_eq!(map.measure(|&value| value == 1), 3);
+        assert_eq!(map.measure(|&value| value == 2), 8);
+    }
+
+    #[test]
+    fn test_merges_sections() {
+        let mut map = IntervalMap::<bool, 2>::default();
+        map.update([0, 0], [4, 4], true);
+        map.update([0, 0], [4, 4], false);
+        assert_eq!(map, IntervalMap::default());
+    }
+
+    #[test]
+    fn test_matches_points() {
+        let updates = [
+            ([0, 0, 0], [3, 3, 3], true),
+            ([2, -1, 1], [5, 2, 2], false),
+            ([-2, 1, 0], [1, 4, 5], true),
+            ([1, 1, 1], [1, 1, 1], false),
+        ];
+        let mut map = IntervalMap::<bool, 3>::default();
+        for (min, max, value) in updates {
+            map.update(min, max, value);
+        }
+
+        let mut count = 0;
+        for x in -3..7 {
+            for y in -3..7 {
+                for z in -3..7 {
+                    let expected = updates
+                        .iter()
+                        .rev()
+                        .find(|(min, max, _)| {
+                            (0..3).all(|axis| (min[axis]..=max[axis]).contains(&[x, y, z][axis]))
+                        })
+                        .is_some_and(|(_, _, value)| *value);
+                    assert_eq!(*map.query([x, y, z]), expected);
+                    count += expected as i64;
+                }
+            }
+        }
+        assert_eq!(map.measure(|&on| on), count);
+    }
+}
@@ -0,0 +1,114 @@
+use std::ops::Mul;
+
+// An N by N matrix of non-negative integers, for fast-forwarding linear
+// recurrences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Matrix<const N: usize>(pub [[u128; N]; N]);
+
+impl<const N: usize> Matrix<N> {
+    pub fn zero() -> Self {
+        Matrix([[0; N]; N])
+    }
+
+    pub fn identity() -> Self {
+        let mut matrix = Self::zero();
+        for i in 0..N {
+            matrix.0[i][i] = 1;
+        }
+        matrix
+    }
+
+    // The matrix taking `vector` to each row's weighted sum of it.
+    pub fn apply(&self, vector: &[u128; N]) -> [u128; N] {
+        let mut result = [0; N];
+        for (value, row) in result.iter_mut().zip(&self.0) {
+            *value = row.iter().zip(vector).map(|(a, b)| a * b).sum();
+        }
+        result
+    }
+
+    fn mul_with(&self, other: &Self, reduce: impl Fn(u128) -> u128) -> Self {
+        let mut result = Self::zero();
+        for i in 0..N {
+            for k in 0..N {
+                if self.0[i][k] == 0 {
+                    continue;
+                }
+                for j in 0..N {
+                    result.0[i][j] = reduce(result.0[i][j] + reduce(self.0[i][k] * other.0[k][j]));
+                }
+            }
+        }
+        result
+    }
+
+    fn pow_with(&self, mut exponent: u64, reduce: impl Fn(u128) -> u128 + Copy) -> Self {
+        let mut result = Self::identity();
+        for row in result.0.iter_mut() {
+            for value in row.iter_mut() {
+                *value = reduce(*value);
+            }
+        }
+        let mut square = *self;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.mul_with(&square, reduce);
+            }
+            exponent >>= 1;
+            // Don't square past what's needed, in case it overflows.
+            if exponent > 0 {
+                square = square.mul_with(&square, reduce);
+            }
+        }
+        result
+    }
+
+    // The matrix multiplied by itself `exponent` times, by repeated squaring.
+    pub fn pow(&self, exponent: u64) -> Self {
+        self.pow_with(exponent, |value| value)
+    }
+
+    // As `pow`, but with every entry reduced modulo `modulus`, for when the
+    // exact answer would overflow.
+    pub fn pow_mod(&self, exponent: u64, modulus: u128) -> Self {
+        assert!(modulus > 0, "modulus must be positive");
+        self.pow_with(exponent, |value| value % modulus)
+    }
+}
+
+impl<const N: usize> Mul for Matrix<N> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.mul_with(&other, |value| value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const FIBONACCI: Matrix<2> = Matrix([[1, 1], [1, 0]]);
+
+    #[test]
+    fn test_pow() {
+        assert_eq!(FIBONACCI.pow(0), Matrix::identity());
+        assert_eq!(FIBONACCI.pow(1), FIBONACCI);
+        assert_eq!(
+            FIBONACCI.pow(5),
+            (0..5).fold(Matrix::identity(), |m, _| m * FIBONACCI)
+        );
+        assert_eq!(FIBONACCI.pow(90).apply(&[1, 0])[1], 2880067194370816120);
+    }
+
+    #[test]
+    fn test_pow_mod() {
+        let exact = FIBONACCI.pow(150).0[0][1];
+        assert_eq!(
+            FIBONACCI.pow_mod(150, 1_000_000_007).0[0][1],
+            exact % 1_000_000_007
+        );
+        // Far too many steps to do one at a time.
+        assert!(FIBONACCI.pow_mod(u64::MAX, 1_000).0[0][1] < 1_000);
+    }
+}
@@ -0,0 +1,148 @@
+use rand::{Rng, RngExt};
+
+// How long simulated annealing runs for and how it cools: the temperature
+// falls geometrically from `start` to `end` over the steps.  At temperature
+// T a neighbour that's worse by d is taken with probability e^(-d/T).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Schedule {
+    steps: usize,
+    start: f64,
+    end: f64,
+}
+
+impl Schedule {
+    pub fn new(steps: usize) -> Self {
+        Schedule {
+            steps,
+            start: 10.0,
+            end: 0.01,
+        }
+    }
+
+    pub fn temperature(mut self, start: f64, end: f64) -> Self {
+        assert!(
+            start >= end && end > 0.0,
+            "invalid temperatures {} to {}",
+            start,
+            end
+        );
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    fn temperature_at(&self, step: usize) -> f64 {
+        let progress = step as f64 / self.steps.max(1) as f64;
+        self.start * (self.end / self.start).powf(progress)
+    }
+}
+
+// Repeatedly moves to a random neighbour of the current state, as long as it's
+// no worse, for `steps` steps.  Returns the best state found and its energy.
+pub fn hill_climb<S, R, N, E>(
+    initial: S,
+    steps: usize,
+    rng: &mut R,
+    mut neighbour: N,
+    mut energy: E,
+) -> (S, f64)
+where
+    R: Rng,
+    N: FnMut(&S, &mut R) -> S,
+    E: FnMut(&S) -> f64,
+{
+    let mut current_energy = energy(&initial);
+    let mut current = initial;
+
+    for _ in 0..steps {
+        let next = neighbour(&current, rng);
+        let next_energy = energy(&next);
+        if next_energy <= current_energy {
+            current = next;
+            current_energy = next_energy;
+        }
+    }
+
+    (current, current_energy)
+}
+
+// Like `hill_climb`, but sometimes taking worse neighbours too, less often
+// as the temperature falls, so it can climb out of local minima.  Returns the
+// best state seen along the way and its energy.
+pub fn anneal<S, R, N, E>(
+    initial: S,
+    schedule: Schedule,
+    rng: &mut R,
+    mut neighbour: N,
+    mut energy: E,
+) -> (S, f64)
+where
+    S: Clone,
+    R: Rng,
+    N: FnMut(&S, &mut R) -> S,
+    E: FnMut(&S) -> f64,
+{
+    let mut current_energy = energy(&initial);
+    let mut best = (initial.clone(), current_energy);
+    let mut current = initial;
+
+    for step in 0..schedule.steps {
+        let next = neighbour(&current, rng);
+        let next_energy = energy(&next);
+        let worse_by = next_energy - current_energy;
+
+        if worse_by <= 0.0
+            || rng.random::<f64>() < (-worse_by / schedule.temperature_at(step)).exp()
+        {
+            current = next;
+            current_energy = next_energy;
+            if current_energy < best.1 {
+                best = (current.clone(), current_energy);
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // Lowest at 70, but only multiples of 10 are any good, so stepping by one
+    // from 0 means getting worse first.
+    fn bumpy(x: &i64) -> f64 {
+        (x - 70).abs() as f64 + if x % 10 == 0 { 0.0 } else { 20.0 }
+    }
+
+    fn step(x: &i64, rng: &mut StdRng) -> i64 {
+        (x + [-1, 1][rng.random_range(0..2)]).clamp(0, 100)
+    }
+
+    #[test]
+    fn test_hill_climb() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let energy = |x: &i64| ((x - 37) * (x - 37)) as f64;
+        assert_eq!(hill_climb(0, 1000, &mut rng, step, energy), (37, 0.0));
+
+        // Stuck where it started.
+        assert_eq!(hill_climb(0, 1000, &mut rng, step, bumpy), (0, 70.0));
+    }
+
+    #[test]
+    fn test_anneal() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let schedule = Schedule::new(20_000).temperature(20.0, 0.1);
+        assert_eq!(anneal(0, schedule, &mut rng, step, bumpy), (70, 0.0));
+    }
+
+    #[test]
+    fn test_temperature() {
+        let schedule = Schedule::new(100).temperature(100.0, 1.0);
+        assert_eq!(schedule.temperature_at(0), 100.0);
+        assert!((schedule.temperature_at(50) - 10.0).abs() < 1e-9);
+        assert!((schedule.temperature_at(100) - 1.0).abs() < 1e-9);
+    }
+}
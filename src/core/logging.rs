@@ -0,0 +1,61 @@
+use crate::core::tracker;
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use tracing::Level;
+use tracing_subscriber::EnvFilter;
+
+// Takes precedence over -v when set, e.g. RUST_LOG=aoc2021::y2021::day19=debug.
+pub const FILTER_VAR: &str = "RUST_LOG";
+
+// Warnings only by default, then info, debug and trace for each -v.
+pub fn level(verbose: u8) -> Level {
+    match verbose {
+        0 => Level::WARN,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    }
+}
+
+// Stderr, but moving any progress bars out of the way first.
+struct Stderr;
+
+impl Write for Stderr {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        tracker::suspend(|| io::stderr().write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stderr().flush()
+    }
+}
+
+// Send `tracing` events to stderr so they never end up mixed in with the
+// answers on stdout.
+pub fn init(verbose: u8) {
+    let filter = match env::var(FILTER_VAR) {
+        Ok(filter) => EnvFilter::new(filter),
+        Err(_) => EnvFilter::new(level(verbose).as_str()),
+    };
+    // Only fails if a subscriber is already set, which is fine to keep.
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(|| Stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .without_time()
+        .try_init();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_level() {
+        assert_eq!(level(0), Level::WARN);
+        assert_eq!(level(1), Level::INFO);
+        assert_eq!(level(2), Level::DEBUG);
+        assert_eq!(level(3), Level::TRACE);
+        assert_eq!(level(10), Level::TRACE);
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// A cache of results for a recursive function, which gets passed a callback
+// to recurse through the cache.
+pub struct Memo<A, R> {
+    cache: HashMap<A, R>,
+    limit: Option<usize>,
+}
+
+impl<A: Eq + Hash + Clone, R: Clone> Memo<A, R> {
+    pub fn new() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            limit: None,
+        }
+    }
+
+    // Stop caching new results once `limit` are stored.
+    pub fn with_limit(limit: usize) -> Self {
+        Memo {
+            cache: HashMap::new(),
+            limit: Some(limit),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn get<F>(&mut self, args: A, f: &F) -> R
+    where
+        F: Fn(&A, &mut dyn FnMut(A) -> R) -> R,
+    {
+        if let Some(result) = self.cache.get(&args) {
+            return result.clone();
+        }
+
+        let result = f(&args, &mut |args| self.get(args, f));
+        if self.limit.is_none_or(|limit| self.cache.len() < limit) {
+            self.cache.insert(args, result.clone());
+        }
+        result
+    }
+}
+
+impl<A: Eq + Hash + Clone, R: Clone> Default for Memo<A, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Calls `f` on `args`, where `f` recurses by calling the function it's given
+// rather than itself so that repeated calls are only worked out once.
+pub fn memoize<A, R>(args: A, f: impl Fn(&A, &mut dyn FnMut(A) -> R) -> R) -> R
+where
+    A: Eq + Hash + Clone,
+    R: Clone,
+{
+    Memo::new().get(args, &f)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+
+    fn fibonacci(&n: &u64, recurse: &mut dyn FnMut(u64) -> u128) -> u128 {
+        if n < 2 {
+            n as u128
+        } else {
+            recurse(n - 1) + recurse(n - 2)
+        }
+    }
+
+    #[test]
+    fn test_memoize() {
+        assert_eq!(memoize(150, fibonacci), 9969216677189303386214405760200);
+
+        let calls = Cell::new(0);
+        let mut memo = Memo::new();
+        let counted = |n: &u64, recurse: &mut dyn FnMut(u64) -> u128| {
+            calls.set(calls.get() + 1);
+            fibonacci(n, recurse)
+        };
+        assert_eq!(memo.get(30, &counted), 832040);
+        assert_eq!(calls.get(), 31);
+        assert_eq!(memo.get(30, &counted), 832040);
+        assert_eq!(calls.get(), 31);
+    }
+
+    #[test]
+    fn test_limit() {
+        let mut memo = Memo::with_limit(10);
+        assert_eq!(memo.get(20, &fibonacci), 6765);
+        assert_eq!(memo.len(), 10);
+    }
+}
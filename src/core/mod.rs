@@ -0,0 +1,49 @@
+pub mod a_star;
+pub mod allocation;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod animate;
+// There's no terminal to animate in the browser, so frames go nowhere.
+#[cfg(target_arch = "wasm32")]
+pub mod animate {
+    pub fn frame(_render: impl FnOnce() -> String) {}
+}
+pub mod answer;
+pub mod arena;
+pub mod bits;
+pub mod bounds;
+pub mod bucket_queue;
+pub mod cache;
+pub mod config;
+pub mod counter;
+pub mod cycles;
+pub mod error;
+pub mod expansion;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod interval_map;
+pub mod linalg;
+pub mod local_search;
+pub mod logging;
+pub mod memo;
+pub mod ocr;
+pub mod parallel;
+pub mod parsing;
+pub mod position;
+pub mod recorder;
+pub mod registry;
+pub mod report;
+pub mod rotations;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod serve;
+pub mod simulate;
+pub mod substitution;
+pub mod timing;
+pub mod tracker;
+pub mod viz;
+pub mod watch;
+pub mod windows;
@@ -0,0 +1,250 @@
+use crate::core::bounds::Rect;
+use crate::core::grid::Grid;
+use crate::core::position::Position;
+use std::collections::HashSet;
+
+// The letters AoC draws in dots, with each glyph `width` columns wide and the
+// next one starting `pitch` columns along.
+struct Font {
+    height: i64,
+    width: i64,
+    pitch: i64,
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+const SMALL: Font = Font {
+    height: 6,
+    width: 4,
+    pitch: 5,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+const LARGE: Font = Font {
+    height: 10,
+    width: 6,
+    pitch: 8,
+    glyphs: &[
+        (
+            'A',
+            &[
+                "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'B',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+                "#....#", "#####.",
+            ],
+        ),
+        (
+            'C',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#....#", ".####.",
+            ],
+        ),
+        (
+            'E',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'F',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'G',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+                "#...##", ".###.#",
+            ],
+        ),
+        (
+            'H',
+            &[
+                "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'J',
+            &[
+                "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+                "#...#.", ".###..",
+            ],
+        ),
+        (
+            'K',
+            &[
+                "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+                "#...#.", "#....#",
+            ],
+        ),
+        (
+            'L',
+            &[
+                "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'N',
+            &[
+                "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+                "#...##", "#....#",
+            ],
+        ),
+        (
+            'P',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'R',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'X',
+            &[
+                "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'Z',
+            &[
+                "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+    ],
+};
+
+// Reads the letters drawn by `points`, if they're all ones we know.
+pub fn recognize(points: impl IntoIterator<Item = Position>) -> Option<String> {
+    let points = points.into_iter().collect::<HashSet<_>>();
+    let bounds = Rect::bounding(points.iter().copied())?;
+    let font = [SMALL, LARGE]
+        .into_iter()
+        .find(|font| font.height == bounds.height())?;
+
+    let letters = (bounds.width() + font.pitch - 1) / font.pitch;
+    (0..letters)
+        .map(|index| {
+            let left = bounds.min.x + index * font.pitch;
+            let rows = (0..font.height)
+                .map(|y| {
+                    (0..font.width)
+                        .map(|x| {
+                            let position = Position::new(left + x, bounds.min.y + y);
+                            if points.contains(&position) {
+                                '#'
+                            } else {
+                                '.'
+                            }
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>();
+            font.glyphs
+                .iter()
+                .find(|(_, glyph)| *glyph == rows)
+                .map(|&(letter, _)| letter)
+        })
+        .collect()
+}
+
+pub fn recognize_grid(grid: &Grid<bool>) -> Option<String> {
+    recognize(
+        grid.iter()
+            .filter(|(_, &on)| on)
+            .map(|(position, _)| position),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The points for `text` written in `font`, the way AoC draws it.
+    fn draw(font: &Font, text: &str) -> Vec<Position> {
+        let mut points = vec![];
+        for (index, letter) in text.chars().enumerate() {
+            let (_, glyph) = font.glyphs.iter().find(|(c, _)| *c == letter).unwrap();
+            for (y, row) in glyph.iter().enumerate() {
+                for (x, c) in row.chars().enumerate() {
+                    if c == '#' {
+                        let x = index as i64 * font.pitch + x as i64;
+                        points.push(Position::new(x + 3, y as i64 - 2));
+                    }
+                }
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn test_recognize() {
+        assert_eq!(recognize(draw(&SMALL, "HELLO")), Some("HELLO".to_string()));
+        assert_eq!(recognize(draw(&SMALL, "ZJPR")), Some("ZJPR".to_string()));
+        assert_eq!(recognize(draw(&LARGE, "BLANK")), Some("BLANK".to_string()));
+        assert_eq!(recognize(vec![]), None);
+
+        let mut smudged = draw(&SMALL, "ABC");
+        smudged.push(Position::new(5, 0));
+        assert_eq!(recognize(smudged), None);
+    }
+
+    #[test]
+    fn test_glyphs() {
+        for font in [SMALL, LARGE] {
+            for (letter, glyph) in font.glyphs {
+                assert_eq!(glyph.len() as i64, font.height, "{}", letter);
+                assert!(glyph.iter().all(|row| row.len() as i64 == font.width));
+            }
+        }
+    }
+
+    #[test]
+    fn test_recognize_grid() {
+        let rows = [
+            "#..#.####",
+            "#..#.#...",
+            "####.###.",
+            "#..#.#...",
+            "#..#.#...",
+            "#..#.####",
+        ];
+        let grid = Grid::from_lines_with(rows.join("\n").as_bytes(), |c| Ok(c == '#')).unwrap();
+        assert_eq!(recognize_grid(&grid), Some("HE".to_string()));
+    }
+}
@@ -0,0 +1,73 @@
+use crate::core::error::{AocError, Result};
+use rayon::prelude::*;
+use std::env;
+
+// Read by `configure` when no thread count is given on the command line.
+pub const THREADS_VAR: &str = "AOC_THREADS";
+
+// Size the global thread pool: `threads` if given, else $AOC_THREADS, else
+// one per core.  Has to happen before anything uses the pool.
+pub fn configure(threads: Option<usize>) -> Result<()> {
+    let threads = match threads {
+        Some(threads) => Some(threads),
+        None => match env::var(THREADS_VAR) {
+            Ok(value) => Some(value.trim().parse().map_err(|_| {
+                AocError::ThreadPool(format!("invalid {} {:?}", THREADS_VAR, value))
+            })?),
+            Err(_) => None,
+        },
+    };
+
+    let mut builder = rayon::ThreadPoolBuilder::new();
+    if let Some(threads) = threads {
+        builder = builder.num_threads(threads);
+    }
+    builder
+        .build_global()
+        .map_err(|err| AocError::ThreadPool(err.to_string()))
+}
+
+pub fn threads() -> usize {
+    rayon::current_num_threads()
+}
+
+// `items.map(map)` in parallel, keeping the order.
+pub fn par_map<I, R>(items: I, map: impl Fn(I::Item) -> R + Sync + Send) -> Vec<R>
+where
+    I: IntoParallelIterator,
+    I::Iter: IndexedParallelIterator,
+    R: Send,
+{
+    items.into_par_iter().map(map).collect()
+}
+
+// Maps each item in parallel and combines the results with `reduce`, which
+// must be associative with `identity` as its identity.
+pub fn par_map_reduce<I, R>(
+    items: I,
+    map: impl Fn(I::Item) -> R + Sync + Send,
+    identity: impl Fn() -> R + Sync + Send,
+    reduce: impl Fn(R, R) -> R + Sync + Send,
+) -> R
+where
+    I: IntoParallelIterator,
+    R: Send,
+{
+    items.into_par_iter().map(map).reduce(identity, reduce)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_par_map_reduce() {
+        let squares = par_map(0..100_u32, |n| n * n);
+        assert_eq!(squares, (0..100).map(|n| n * n).collect::<Vec<_>>());
+        assert_eq!(par_map_reduce(1..=100_u64, |n| n, || 0, |a, b| a + b), 5050);
+        assert_eq!(
+            par_map_reduce(vec![3, 1, 2], Some, || None, |a, b| a.max(b)),
+            Some(3)
+        );
+    }
+}
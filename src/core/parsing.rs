@@ -0,0 +1,199 @@
+use crate::core::error::{AocError, Result};
+use crate::core::grid::Grid;
+use nom::character::complete::{char, digit1, line_ending, multispace0, satisfy};
+use nom::combinator::{all_consuming, map, map_res, opt, recognize};
+use nom::error::{Error, ErrorKind};
+use nom::multi::{many1, separated_list1};
+use nom::sequence::{pair, terminated};
+use nom::{IResult, Parser};
+use std::str::FromStr;
+
+pub fn integer<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+// An integer with an optional leading minus sign.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(char('-')), digit1)), str::parse)(input)
+}
+
+// One or more lines matching `parser`, not including the final newline.
+pub fn lines_of<'a, O>(
+    parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(line_ending, parser)
+}
+
+pub fn blank_line(input: &str) -> IResult<&str, ()> {
+    map(pair(line_ending, line_ending), |_| ())(input)
+}
+
+// One or more blocks of lines matching `parser`, with a blank line between
+// each.
+pub fn blank_line_separated_blocks<'a, O>(
+    parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>> {
+    separated_list1(blank_line, parser)
+}
+
+// A rectangular grid with one cell matching `cell` per character.
+pub fn grid<'a, T>(
+    cell: impl Parser<&'a str, T, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Grid<T>> {
+    map_res(lines_of(many1(cell)), |rows: Vec<Vec<T>>| {
+        let width = rows[0].len();
+        if rows.iter().any(|row| row.len() != width) {
+            return Err(AocError::parse("rows have different lengths"));
+        }
+        let height = rows.len();
+        Ok(Grid::new(
+            width,
+            height,
+            rows.into_iter().flatten().collect(),
+        ))
+    })
+}
+
+// A grid of single digits.
+pub fn digit_grid(input: &str) -> IResult<&str, Grid<usize>> {
+    grid(map(satisfy(|c| c.is_ascii_digit()), |c| {
+        c.to_digit(10).unwrap() as usize
+    }))(input)
+}
+
+// Runs `parser` over the whole of `input`, allowing trailing whitespace.
+pub fn parse_all<'a, O>(
+    parser: impl Parser<&'a str, O, Error<&'a str>>,
+    input: &'a str,
+) -> Result<O> {
+    let (_, output) = all_consuming(terminated(parser, multispace0))(input)
+        .map_err(|err| error_at(input, err))?;
+    Ok(output)
+}
+
+// Describes where in `input` parsing failed and roughly why.
+fn error_at(input: &str, err: nom::Err<Error<&str>>) -> AocError {
+    let err = match err {
+        nom::Err::Error(err) | nom::Err::Failure(err) => err,
+        nom::Err::Incomplete(_) => return AocError::parse("unexpected end of input"),
+    };
+
+    // Leftover input usually means the line after the last one that parsed
+    // is wrong, so point at that rather than the newline before it.
+    let remaining = match err.code {
+        ErrorKind::Eof => err.input.trim_start_matches(['\r', '\n']),
+        _ => err.input,
+    };
+    let message = match err.code {
+        _ if remaining.is_empty() => "unexpected end of input".to_string(),
+        ErrorKind::Eof => format!(
+            "unexpected \"{}\"",
+            remaining.lines().next().unwrap_or_default()
+        ),
+        ErrorKind::Digit => "expected number".to_string(),
+        ErrorKind::MapRes | ErrorKind::MapOpt | ErrorKind::Verify => "invalid value".to_string(),
+        ErrorKind::Tag | ErrorKind::Char | ErrorKind::OneOf | ErrorKind::Satisfy => format!(
+            "unexpected {:?}",
+            remaining.chars().next().unwrap_or_default()
+        ),
+        kind => format!("{} failed", kind.description()),
+    };
+
+    let consumed = &input[..input.len() - remaining.len()];
+    let line_start = consumed.rfind('\n').map_or(0, |index| index + 1);
+    let width = remaining
+        .chars()
+        .next()
+        .filter(|&c| c != '\r' && c != '\n')
+        .map_or(0, char::len_utf8);
+    AocError::ParseAt {
+        line: consumed.matches('\n').count() + 1,
+        column: consumed[line_start..].chars().count() + 1,
+        message,
+        input: input.to_string(),
+        span: (consumed.len(), width).into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nom::sequence::separated_pair;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(integer::<u32>("123,4"), Ok((",4", 123)));
+        assert!(integer::<u32>("-5").is_err());
+        assert_eq!(signed::<i64>("-5 "), Ok((" ", -5)));
+        assert_eq!(signed::<i64>("7"), Ok(("", 7)));
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "1\n2\n\n3\n";
+        let blocks = parse_all(blank_line_separated_blocks(lines_of(integer::<u8>)), input);
+        assert_eq!(blocks.unwrap(), vec![vec![1, 2], vec![3]]);
+
+        let pairs = lines_of(separated_pair(signed::<i8>, char(','), signed::<i8>));
+        assert_eq!(
+            parse_all(pairs, "1,-2\n3,4").unwrap(),
+            vec![(1, -2), (3, 4)]
+        );
+        assert!(parse_all(lines_of(integer::<u8>), "1\nx\n").is_err());
+    }
+
+    #[test]
+    fn test_grid() {
+        let grid = parse_all(digit_grid, "12\n34\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid.values().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert!(parse_all(digit_grid, "12\n3\n").is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |input| {
+            parse_all(
+                lines_of(separated_pair(signed::<i64>, char(','), integer::<u8>)),
+                input,
+            )
+            .unwrap_err()
+            .to_string()
+        };
+        assert_eq!(
+            error("1,2\n3,4\n5;6\n"),
+            "invalid input: line 3, column 1: unexpected \"5;6\""
+        );
+        assert_eq!(
+            error("1,2\n3,x"),
+            "invalid input: line 2, column 1: unexpected \"3,x\""
+        );
+        assert_eq!(
+            error("-,2"),
+            "invalid input: line 1, column 2: expected number"
+        );
+        assert_eq!(
+            error("1,300"),
+            "invalid input: line 1, column 3: invalid value"
+        );
+        assert_eq!(
+            error(""),
+            "invalid input: line 1, column 1: unexpected end of input"
+        );
+    }
+
+    #[test]
+    fn test_error_snippet() {
+        let parser = lines_of(separated_pair(signed::<i64>, char(','), integer::<u8>));
+        match parse_all(parser, "1,2\r\n-,2\r\n") {
+            Err(AocError::ParseAt {
+                line, input, span, ..
+            }) => {
+                assert_eq!(line, 2);
+                assert_eq!(&input[span.offset()..], "-,2\r\n");
+                assert_eq!(span.len(), 1);
+            }
+            result => panic!("unexpected {:?}", result),
+        }
+    }
+}
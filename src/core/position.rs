@@ -0,0 +1,566 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::ops::{Add, Index, Neg, Sub};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub struct Position {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Position {
+    pub fn new(x: i64, y: i64) -> Self {
+        Position { x, y }
+    }
+
+    pub fn offset(self, dx: i64, dy: i64) -> Self {
+        Position {
+            x: self.x + dx,
+            y: self.y + dy,
+        }
+    }
+
+    // The four positions sharing an edge with this one.
+    pub fn adjacent(self) -> impl Iterator<Item = Position> {
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| self.step(direction))
+    }
+
+    // The eight positions around this one, including diagonals.
+    pub fn adjacent8(self) -> impl Iterator<Item = Position> {
+        self.adjacent().chain(
+            Diagonal::ALL
+                .into_iter()
+                .map(move |diagonal| self.step_diagonal(diagonal)),
+        )
+    }
+
+    pub fn manhattan_distance(self, other: Position) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    // The number of king's moves between the two positions.
+    pub fn chebyshev_distance(self, other: Position) -> i64 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    pub fn step(self, direction: Direction) -> Self {
+        let (dx, dy) = direction.delta();
+        self.offset(dx, dy)
+    }
+
+    pub fn step_diagonal(self, diagonal: Diagonal) -> Self {
+        let (dx, dy) = diagonal.delta();
+        self.offset(dx, dy)
+    }
+
+    // Every position from here going `dx`, `dy` at a time, not including
+    // this one.
+    pub fn ray(self, (dx, dy): (i64, i64)) -> impl Iterator<Item = Position> {
+        std::iter::successors(Some(self.offset(dx, dy)), move |position| {
+            Some(position.offset(dx, dy))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    // Clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn all() -> impl Iterator<Item = Direction> {
+        Self::ALL.into_iter()
+    }
+
+    // The change in x and y from one step, with y increasing southwards.
+    pub fn delta(self) -> (i64, i64) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        }
+    }
+
+    fn rotate(self, quarter_turns: usize) -> Self {
+        Self::ALL[(self as usize + quarter_turns) % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.rotate(3)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Diagonal {
+    NorthEast,
+    SouthEast,
+    SouthWest,
+    NorthWest,
+}
+
+impl Diagonal {
+    // Clockwise from north east.
+    pub const ALL: [Diagonal; 4] = [
+        Diagonal::NorthEast,
+        Diagonal::SouthEast,
+        Diagonal::SouthWest,
+        Diagonal::NorthWest,
+    ];
+
+    pub fn all() -> impl Iterator<Item = Diagonal> {
+        Self::ALL.into_iter()
+    }
+
+    pub fn delta(self) -> (i64, i64) {
+        match self {
+            Diagonal::NorthEast => (1, -1),
+            Diagonal::SouthEast => (1, 1),
+            Diagonal::SouthWest => (-1, 1),
+            Diagonal::NorthWest => (-1, -1),
+        }
+    }
+
+    fn rotate(self, quarter_turns: usize) -> Self {
+        Self::ALL[(self as usize + quarter_turns) % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.rotate(3)
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' => Ok(Direction::North),
+            '>' => Ok(Direction::East),
+            'v' => Ok(Direction::South),
+            '<' => Ok(Direction::West),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<Direction> for char {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+}
+
+// How a `TorusMap` stores its contents.  Sparse suits maps that are mostly
+// empty; dense is faster once a good fraction of the cells are filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Backing {
+    Sparse,
+    Dense,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+enum Cells<T> {
+    Sparse(#[serde(with = "sparse")] HashMap<Position, T>),
+    Dense(Vec<Option<T>>),
+}
+
+// JSON only allows string keys, so store sparse maps as a list of entries.
+mod sparse {
+    use super::Position;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+
+    pub fn serialize<T: Serialize, S: Serializer>(
+        map: &HashMap<Position, T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<Position, T>, D::Error> {
+        let entries = Vec::<(Position, T)>::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TorusMap<T> {
+    cells: Cells<T>,
+    width: i64,
+    height: i64,
+}
+
+impl<T> TorusMap<T> {
+    pub fn new(map: HashMap<Position, T>, width: i64, height: i64) -> Self {
+        Self::with_backing(map, width, height, Backing::Sparse)
+    }
+
+    pub fn with_backing(
+        map: HashMap<Position, T>,
+        width: i64,
+        height: i64,
+        backing: Backing,
+    ) -> Self {
+        let cells = match backing {
+            Backing::Sparse => Cells::Sparse(HashMap::new()),
+            Backing::Dense => Cells::Dense(
+                std::iter::repeat_with(|| None)
+                    .take((width * height) as usize)
+                    .collect(),
+            ),
+        };
+        let mut torus_map = TorusMap {
+            cells,
+            width,
+            height,
+        };
+        for (position, contents) in map {
+            torus_map.insert(position, contents);
+        }
+        torus_map
+    }
+
+    pub fn backing(&self) -> Backing {
+        match self.cells {
+            Cells::Sparse(_) => Backing::Sparse,
+            Cells::Dense(_) => Backing::Dense,
+        }
+    }
+
+    pub fn iter(&self) -> Box<dyn Iterator<Item = (Position, &T)> + '_> {
+        match &self.cells {
+            Cells::Sparse(map) => Box::new(map.iter().map(|(&position, value)| (position, value))),
+            Cells::Dense(cells) => {
+                Box::new(cells.iter().enumerate().filter_map(|(index, value)| {
+                    let index = index as i64;
+                    value
+                        .as_ref()
+                        .map(|value| (Position::new(index % self.width, index / self.width), value))
+                }))
+            }
+        }
+    }
+
+    fn wrap(&self, position: &Position) -> Position {
+        Position {
+            x: position.x.rem_euclid(self.width),
+            y: position.y.rem_euclid(self.height),
+        }
+    }
+
+    fn index(&self, position: &Position) -> usize {
+        let position = self.wrap(position);
+        (position.y * self.width + position.x) as usize
+    }
+
+    pub fn width(&self) -> i64 {
+        self.width
+    }
+
+    pub fn height(&self) -> i64 {
+        self.height
+    }
+
+    pub fn get(&self, position: &Position) -> Option<&T> {
+        match &self.cells {
+            Cells::Sparse(map) => map.get(&self.wrap(position)),
+            Cells::Dense(cells) => cells[self.index(position)].as_ref(),
+        }
+    }
+
+    pub fn insert(&mut self, position: Position, contents: T) -> Option<T> {
+        let wrapped = self.wrap(&position);
+        let index = self.index(&position);
+        match &mut self.cells {
+            Cells::Sparse(map) => map.insert(wrapped, contents),
+            Cells::Dense(cells) => cells[index].replace(contents),
+        }
+    }
+
+    pub fn remove(&mut self, position: &Position) -> Option<T> {
+        let wrapped = self.wrap(position);
+        let index = self.index(position);
+        match &mut self.cells {
+            Cells::Sparse(map) => map.remove(&wrapped),
+            Cells::Dense(cells) => cells[index].take(),
+        }
+    }
+
+    pub fn contains_key(&self, position: &Position) -> bool {
+        self.get(position).is_some()
+    }
+
+    pub fn map<F>(&self, update: F) -> Self
+    where
+        F: FnMut((Position, &T)) -> (Position, T),
+    {
+        let map = self.iter().map(update).collect();
+        Self::with_backing(map, self.width, self.height, self.backing())
+    }
+
+    // The positions from `start` going `delta` at a time while `clear` holds
+    // for their contents, stopping before coming back round to `start`.
+    pub fn ray<'a>(
+        &'a self,
+        start: Position,
+        delta: (i64, i64),
+        mut clear: impl FnMut(Option<&T>) -> bool + 'a,
+    ) -> impl Iterator<Item = Position> + 'a {
+        let start = self.wrap(&start);
+        start
+            .ray(delta)
+            .map(|position| self.wrap(&position))
+            .take_while(move |&position| position != start && clear(self.get(&position)))
+    }
+
+    pub fn make_moves<I>(&mut self, moves: I)
+    where
+        I: IntoIterator<Item = (Position, Position)>,
+    {
+        for (from, to) in moves {
+            if let Some(contents) = self.remove(&from) {
+                self.insert(to, contents);
+            }
+        }
+    }
+
+    // The map as rows of characters, with `.` for empty cells.
+    pub fn render(&self) -> String
+    where
+        T: Clone + Into<char>,
+    {
+        self.to_string()
+    }
+}
+
+impl<T: Clone + Into<char>> Display for TorusMap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let c = self
+                    .get(&Position::new(x, y))
+                    .cloned()
+                    .map(Into::into)
+                    .unwrap_or('.');
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default, PartialOrd, Ord)]
+pub struct Position3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Position3 {
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Position3 { x, y, z }
+    }
+
+    pub fn coords(self) -> [i64; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn zip_with(self, other: Self, f: impl Fn(i64, i64) -> i64) -> Self {
+        Position3::new(f(self.x, other.x), f(self.y, other.y), f(self.z, other.z))
+    }
+
+    // The component-wise minimum, i.e. the low corner of a box around both.
+    pub fn min(self, other: Self) -> Self {
+        self.zip_with(other, i64::min)
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        self.zip_with(other, i64::max)
+    }
+
+    // The distance from the origin moving along the axes.
+    pub fn manhattan(self) -> i64 {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> i64 {
+        (self - other).manhattan()
+    }
+}
+
+impl From<[i64; 3]> for Position3 {
+    fn from([x, y, z]: [i64; 3]) -> Self {
+        Position3::new(x, y, z)
+    }
+}
+
+impl Index<usize> for Position3 {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &i64 {
+        match axis {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("invalid axis {}", axis),
+        }
+    }
+}
+
+impl Add for Position3 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a + b)
+    }
+}
+
+impl Sub for Position3 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a - b)
+    }
+}
+
+impl Neg for Position3 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Position3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_adjacent8() {
+        let origin = Position::new(0, 0);
+        let adjacent = origin.adjacent8().collect::<Vec<_>>();
+        assert_eq!(adjacent.len(), 8);
+        assert!(!adjacent.contains(&origin));
+        assert!(adjacent
+            .iter()
+            .all(|&position| origin.chebyshev_distance(position) == 1));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Position::new(1, -2);
+        let b = Position::new(-3, 4);
+        assert_eq!(a.manhattan_distance(b), 10);
+        assert_eq!(a.chebyshev_distance(b), 6);
+        assert_eq!(b.manhattan_distance(a), 10);
+    }
+
+    #[test]
+    fn test_position3() {
+        let a = Position3::new(1, -2, 3);
+        let b = Position3::new(-4, 5, 0);
+        assert_eq!(a + b, Position3::new(-3, 3, 3));
+        assert_eq!(a - b, Position3::new(5, -7, 3));
+        assert_eq!(a.min(b), Position3::new(-4, -2, 0));
+        assert_eq!(a.max(b), Position3::new(1, 5, 3));
+        assert_eq!(a.manhattan_distance(b), 15);
+        assert_eq!(a[2], 3);
+    }
+
+    #[test]
+    fn test_torus_map() {
+        for backing in [Backing::Sparse, Backing::Dense] {
+            let map = [(Position::new(0, 0), Direction::East)]
+                .into_iter()
+                .collect();
+            let mut map = TorusMap::with_backing(map, 3, 2, backing);
+            map.make_moves([(Position::new(0, 0), Position::new(-1, 3))]);
+            assert_eq!(map.get(&Position::new(2, 1)), Some(&Direction::East));
+            assert_eq!(map.iter().count(), 1);
+            assert_eq!(map.render(), "...\n..>\n");
+        }
+    }
+
+    #[test]
+    fn test_turning() {
+        for direction in Direction::all() {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            let origin = Position::new(0, 0);
+            let back = origin.step(direction).step(direction.opposite());
+            assert_eq!(back, origin);
+        }
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Diagonal::NorthEast.turn_right(), Diagonal::SouthEast);
+        assert_eq!(Diagonal::NorthWest.opposite(), Diagonal::SouthEast);
+    }
+
+    #[test]
+    fn test_torus_ray() {
+        let map = [(Position::new(3, 0), Direction::East)]
+            .into_iter()
+            .collect();
+        let map = TorusMap::new(map, 5, 2);
+        let clear = map
+            .ray(Position::new(1, 0), Direction::West.delta(), |cell| {
+                cell.is_none()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(clear, vec![Position::new(0, 0), Position::new(4, 0)]);
+        assert_eq!(
+            map.ray(Position::new(0, 0), Diagonal::SouthEast.delta(), |_| true)
+                .count(),
+            9
+        );
+    }
+
+    #[test]
+    fn test_serde() {
+        let map = [(Position::new(1, 0), Direction::South)]
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+        for backing in [Backing::Sparse, Backing::Dense] {
+            let map = TorusMap::with_backing(map.clone(), 2, 2, backing);
+            let json = serde_json::to_string(&map).unwrap();
+            assert!(serde_json::from_str::<TorusMap<Direction>>(&json).unwrap() == map);
+        }
+    }
+}
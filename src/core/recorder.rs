@@ -0,0 +1,147 @@
+use crate::core::tracker;
+use metrics::{
+    Counter, CounterFn, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// A `metrics` recorder that reports to the global tracker of whichever thread
+// records the value, so code instrumented with the `metrics` macros shows up
+// in the same reports and exports as `track!` spans.
+//
+// Histogram values are taken to be durations in seconds, as the `metrics`
+// exporters expect.
+#[derive(Default)]
+pub struct TrackerRecorder {
+    names: Mutex<HashSet<&'static str>>,
+}
+
+impl TrackerRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Tracker paths are made of static strings, so each distinct metric name
+    // is leaked once.
+    fn intern(&self, key: &Key) -> &'static str {
+        let mut name = key.name().to_string();
+        let labels = key
+            .labels()
+            .map(|label| format!("{}={}", label.key(), label.value()))
+            .collect::<Vec<_>>();
+        if !labels.is_empty() {
+            name += &format!("{{{}}}", labels.join(","));
+        }
+
+        let mut names = self.names.lock().unwrap();
+        match names.get(name.as_str()) {
+            Some(name) => name,
+            None => {
+                let name = Box::leak(name.into_boxed_str());
+                names.insert(name);
+                name
+            }
+        }
+    }
+}
+
+struct Handle {
+    name: &'static str,
+}
+
+impl Handle {
+    fn new(name: &'static str) -> Arc<Self> {
+        Arc::new(Handle { name })
+    }
+}
+
+impl CounterFn for Handle {
+    fn increment(&self, value: u64) {
+        if let Some(tracker) = tracker::global() {
+            tracker.increment_counter(self.name, value);
+        }
+    }
+
+    fn absolute(&self, value: u64) {
+        if let Some(tracker) = tracker::global() {
+            tracker.raise_counter(self.name, value);
+        }
+    }
+}
+
+impl GaugeFn for Handle {
+    fn increment(&self, value: f64) {
+        if let Some(tracker) = tracker::global() {
+            tracker.adjust_gauge(self.name, value);
+        }
+    }
+
+    fn decrement(&self, value: f64) {
+        if let Some(tracker) = tracker::global() {
+            tracker.adjust_gauge(self.name, -value);
+        }
+    }
+
+    fn set(&self, value: f64) {
+        if let Some(tracker) = tracker::global() {
+            tracker.set_gauge(self.name, value);
+        }
+    }
+}
+
+impl HistogramFn for Handle {
+    fn record(&self, value: f64) {
+        if let Some(tracker) = tracker::global() {
+            let duration = Duration::try_from_secs_f64(value).unwrap_or_default();
+            tracker.record_duration(self.name, duration);
+        }
+    }
+}
+
+impl Recorder for TrackerRecorder {
+    fn describe_counter(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_gauge(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_histogram(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+        Counter::from_arc(Handle::new(self.intern(key)))
+    }
+
+    fn register_gauge(&self, key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+        Gauge::from_arc(Handle::new(self.intern(key)))
+    }
+
+    fn register_histogram(&self, key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+        Histogram::from_arc(Handle::new(self.intern(key)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::tracker::Tracker;
+    use std::io;
+
+    #[test]
+    fn test_recorder_reports_to_tracker() {
+        let recorder = TrackerRecorder::new();
+        tracker::install(Tracker::new(0, io::sink()));
+        metrics::with_local_recorder(&recorder, || {
+            metrics::counter!("expanded").increment(3);
+            metrics::counter!("expanded").increment(2);
+            metrics::gauge!("heap", "day" => "23").set(7.0);
+            metrics::histogram!("solve").record(0.5);
+        });
+        let tracker = tracker::uninstall().unwrap();
+
+        assert_eq!(tracker.counters(), vec![("expanded".to_string(), 5)]);
+        assert_eq!(tracker.gauges(), vec![("heap{day=23}".to_string(), 7.0)]);
+        let metrics = tracker.metrics();
+        assert_eq!(metrics[0].operation, "solve");
+        assert_eq!(metrics[0].total_nanos, 500_000_000);
+    }
+}
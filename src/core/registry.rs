@@ -0,0 +1,163 @@
+use crate::core::error::{AocError, Result};
+use crate::core::input;
+use crate::core::runner::{Answers, Part};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// The year the crate started with.  Its days keep the plain `dayNN` binary
+// names and the top-level settings in aoc2021.toml.
+pub const DEFAULT_YEAR: u32 = 2021;
+
+pub struct Year {
+    pub number: u32,
+    pub days: &'static [Day],
+}
+
+pub struct Day {
+    pub year: u32,
+    pub number: u32,
+    // The module under src/yYYYY/.
+    pub name: &'static str,
+    pub bin: &'static str,
+    pub solver: fn(&str, Part) -> Result<Answers>,
+    pub timed_solver: fn(&str, Part) -> Result<Answers>,
+}
+
+// Declares a year's `DAYS` from the `Solution` for each day, naming each
+// day's binary after its module plus `$prefix` to keep it apart from the same
+// day in other years.
+#[macro_export]
+macro_rules! days {
+    ($year:literal, $prefix:literal; $($number:literal => $name:ident::$solution:ident),* $(,)?) => {
+        pub const DAYS: &[$crate::core::registry::Day] = &[$(
+            $crate::core::registry::Day {
+                year: $year,
+                number: $number,
+                name: stringify!($name),
+                bin: concat!($prefix, stringify!($name)),
+                solver: $crate::core::runner::solve_part_str::<$name::$solution>,
+                timed_solver: $crate::core::runner::solve_timed_str::<$name::$solution>,
+            }
+        ),*];
+    };
+}
+
+pub const YEARS: &[Year] = &[Year {
+    number: 2021,
+    days: crate::y2021::DAYS,
+}];
+
+pub fn year(number: u32) -> Option<&'static Year> {
+    YEARS.iter().find(|year| year.number == number)
+}
+
+pub fn find(year: u32, number: u32) -> Option<&'static Day> {
+    self::year(year)?
+        .days
+        .iter()
+        .find(|day| day.number == number)
+}
+
+pub fn source_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src")
+}
+
+impl Day {
+    pub fn source_path(&self) -> PathBuf {
+        source_dir()
+            .join(format!("y{}", self.year))
+            .join(format!("{}.rs", self.name))
+    }
+
+    pub fn build(&self) -> Result<()> {
+        let release = env::current_exe()?
+            .parent()
+            .and_then(Path::file_name)
+            .is_some_and(|profile| profile == "release");
+
+        let mut command = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+        command
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .args(["build", "--quiet", "--bin", self.bin]);
+        if release {
+            command.arg("--release");
+        }
+
+        if command.status()?.success() {
+            Ok(())
+        } else {
+            Err(self.error("build failed"))
+        }
+    }
+
+    pub fn input_path(&self, input_dir: &Path) -> PathBuf {
+        input_dir.join(format!("{}.txt", self.name))
+    }
+
+    fn binary(&self) -> Result<PathBuf> {
+        let binary = env::current_exe()?.with_file_name(self.bin);
+        if binary.exists() {
+            Ok(binary)
+        } else {
+            Err(self.error(format!("no binary at {}", binary.display())))
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> AocError {
+        AocError::Day {
+            day: self.number,
+            message: message.into(),
+        }
+    }
+
+    // Solve `input` in this process rather than with the day's binary, for
+    // when there's no binary to run, like in the browser.
+    pub fn solve(&self, input: &str, part: Part) -> Result<Answers> {
+        (self.solver)(&input::normalize(input), part)
+    }
+
+    // Like `solve`, but timing each part.  Not for the browser, which has no
+    // clock to time with.
+    pub fn solve_timed(&self, input: &str, part: Part) -> Result<Answers> {
+        (self.timed_solver)(&input::normalize(input), part)
+    }
+
+    pub fn run(&self, input: &Path, cache: bool) -> Result<Answers> {
+        let mut command = Command::new(self.binary()?);
+        command.arg("--json").arg("--time");
+        if cache {
+            command.arg("--cache");
+        }
+        let output = command.arg(input).output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let prefix = format!("day{:02}: ", self.number);
+            let message = stderr.trim_end();
+            return Err(self.error(message.strip_prefix(&prefix).unwrap_or(message)));
+        }
+
+        serde_json::from_slice(&output.stdout).map_err(|err| self.error(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let day = find(2021, 1).unwrap();
+        assert_eq!(day.bin, "day01");
+        let answers = day
+            .solve("199\r\n200\r\n208\r\n210\r\n", Part::All)
+            .unwrap();
+        assert_eq!(answers.part1, Some(3_usize.into()));
+        assert_eq!(answers.part2, Some(1_usize.into()));
+        let answers = day.solve("199\n200\n", Part::Two).unwrap();
+        assert_eq!(answers.part1, None);
+        assert!(find(2021, 26).is_none());
+        assert!(find(1999, 1).is_none());
+    }
+}
@@ -0,0 +1,126 @@
+use crate::core::answer::Answer;
+use crate::core::runner::Answers;
+use serde::Deserialize;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    #[serde(alias = "md")]
+    Markdown,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(Format::Markdown),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Invalid format {:?}, expected markdown or csv", s)),
+        }
+    }
+}
+
+pub enum Row {
+    Solved(Answers),
+    Failed { day: u32, message: String },
+}
+
+impl Row {
+    fn day(&self) -> u32 {
+        match self {
+            Row::Solved(answers) => answers.day,
+            Row::Failed { day, .. } => *day,
+        }
+    }
+
+    fn answers(&self) -> [String; 2] {
+        match self {
+            Row::Solved(answers) => [&answers.part1, &answers.part2]
+                .map(|answer| answer.as_ref().map_or(String::new(), Answer::to_string)),
+            Row::Failed { message, .. } => [message.clone(), String::new()],
+        }
+    }
+
+    fn runtime(&self) -> Option<Duration> {
+        match self {
+            Row::Solved(answers) => answers.timings.as_ref().map(|timings| timings.total()),
+            Row::Failed { .. } => None,
+        }
+    }
+}
+
+fn total_runtime(rows: &[Row]) -> Duration {
+    rows.iter().filter_map(Row::runtime).sum()
+}
+
+fn markdown_cell(value: &str) -> String {
+    value.trim_end().replace('|', "\\|").replace('\n', "<br>")
+}
+
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_markdown(rows: &[Row]) -> String {
+    let mut report = String::new();
+    writeln!(report, "| Day | Part 1 | Part 2 | Time |").unwrap();
+    writeln!(report, "|----:|-------:|-------:|-----:|").unwrap();
+
+    for row in rows {
+        let [part1, part2] = row.answers();
+        let runtime = row
+            .runtime()
+            .map_or(String::new(), |runtime| format!("{:.3?}", runtime));
+        writeln!(
+            report,
+            "| {} | {} | {} | {} |",
+            row.day(),
+            markdown_cell(&part1),
+            markdown_cell(&part2),
+            runtime
+        )
+        .unwrap();
+    }
+
+    writeln!(report, "| **Total** | | | {:.3?} |", total_runtime(rows)).unwrap();
+    report
+}
+
+fn render_csv(rows: &[Row]) -> String {
+    let mut report = String::new();
+    writeln!(report, "day,part1,part2,seconds").unwrap();
+
+    for row in rows {
+        let [part1, part2] = row.answers();
+        let runtime = row
+            .runtime()
+            .map_or(String::new(), |runtime| runtime.as_secs_f64().to_string());
+        writeln!(
+            report,
+            "{},{},{},{}",
+            row.day(),
+            csv_cell(&part1),
+            csv_cell(&part2),
+            runtime
+        )
+        .unwrap();
+    }
+
+    report
+}
+
+pub fn render(rows: &[Row], format: Format) -> String {
+    match format {
+        Format::Markdown => render_markdown(rows),
+        Format::Csv => render_csv(rows),
+    }
+}
@@ -0,0 +1,107 @@
+use crate::core::position::Position3;
+use std::sync::OnceLock;
+
+// A rotation of the axes onto each other, as a matrix of 0s and ±1s.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Rotation([[i64; 3]; 3]);
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    // Quarter turns anticlockwise looking down each axis towards the origin.
+    pub const X: Rotation = Rotation([[1, 0, 0], [0, 0, -1], [0, 1, 0]]);
+    pub const Y: Rotation = Rotation([[0, 0, 1], [0, 1, 0], [-1, 0, 0]]);
+    pub const Z: Rotation = Rotation([[0, -1, 0], [1, 0, 0], [0, 0, 1]]);
+
+    pub fn apply(&self, position: Position3) -> Position3 {
+        let coords = position.coords();
+        let row = |row: &[i64; 3]| row.iter().zip(coords).map(|(a, b)| a * b).sum();
+        Position3::new(row(&self.0[0]), row(&self.0[1]), row(&self.0[2]))
+    }
+
+    // The rotation that applies `self` and then `next`.
+    pub fn then(&self, next: &Rotation) -> Rotation {
+        let mut matrix = [[0; 3]; 3];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| next.0[i][k] * self.0[k][j]).sum();
+            }
+        }
+        Rotation(matrix)
+    }
+
+    // The rotation that undoes `self`.
+    pub fn inverse(&self) -> Rotation {
+        let mut matrix = [[0; 3]; 3];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = self.0[j][i];
+            }
+        }
+        Rotation(matrix)
+    }
+
+    // Applies `self` `turns` times.
+    pub fn pow(&self, turns: usize) -> Rotation {
+        (0..turns).fold(Rotation::IDENTITY, |rotation, _| rotation.then(self))
+    }
+
+    // The 24 ways of orienting a cube, found by turning about z, then y,
+    // then x.  Worked out the first time they're asked for.
+    pub fn all() -> &'static [Rotation] {
+        static ALL: OnceLock<Vec<Rotation>> = OnceLock::new();
+        ALL.get_or_init(|| {
+            let mut rotations = vec![];
+            for x in 0..4 {
+                for y in 0..4 {
+                    for z in 0..4 {
+                        let rotation = Rotation::Z
+                            .pow(z)
+                            .then(&Rotation::Y.pow(y))
+                            .then(&Rotation::X.pow(x));
+                        if !rotations.contains(&rotation) {
+                            rotations.push(rotation);
+                        }
+                    }
+                }
+            }
+            rotations
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_rotations() {
+        let rotations = Rotation::all();
+        assert_eq!(rotations.len(), 24);
+        assert!(std::ptr::eq(rotations, Rotation::all()));
+
+        let position = Position3::new(1, 2, 3);
+        let rotated = rotations
+            .iter()
+            .map(|rotation| rotation.apply(position))
+            .collect::<HashSet<_>>();
+        assert_eq!(rotated.len(), 24);
+        assert!(rotated.iter().all(|p| p.manhattan() == 6));
+
+        assert_eq!(
+            Rotation::Z.apply(Position3::new(1, 0, 0)),
+            Position3::new(0, 1, 0)
+        );
+        let half = Rotation::X.then(&Rotation::X);
+        assert_eq!(half.then(&half), Rotation::IDENTITY);
+        for rotation in rotations {
+            assert_eq!(rotation.then(&rotation.inverse()), Rotation::IDENTITY);
+            assert!(rotations.contains(&rotation.then(&Rotation::Y)));
+        }
+        assert_eq!(
+            Rotation::X.then(&Rotation::Y).apply(position),
+            Rotation::Y.apply(Rotation::X.apply(position))
+        );
+    }
+}
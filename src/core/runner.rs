@@ -0,0 +1,648 @@
+#[cfg(not(target_arch = "wasm32"))]
+use crate::core::animate::{self, Animator};
+use crate::core::answer::Answer;
+use crate::core::cache::Cache;
+use crate::core::config::{Config, OutputFormat};
+use crate::core::error::{exit_code, AocError, Result};
+use crate::core::input;
+use crate::core::logging;
+use crate::core::parallel;
+use crate::core::recorder::TrackerRecorder;
+use crate::core::timing::{self, Summary, Timing};
+use crate::core::tracker::{self, MetricsFormat, Tracker};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fs::File;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::IsTerminal;
+use std::io::{self, BufRead, BufWriter};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+use std::time::Duration;
+use structopt::StructOpt;
+
+pub trait Solution {
+    const YEAR: u32;
+    const DAY: u32;
+    type Input;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input>;
+    // Either part can fail with `AocError::NoSolution` if the input doesn't
+    // have an answer.
+    fn part1(input: &Self::Input) -> Result<Answer>;
+    fn part2(input: &Self::Input) -> Result<Answer>;
+
+    // Parses the input in the file at `path`, or stdin for `-`.
+    fn read(path: impl AsRef<Path>) -> Result<Self::Input> {
+        Self::parse(input::open(Some(path.as_ref()))?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    All,
+}
+
+impl Part {
+    pub fn includes(self, part: u32) -> bool {
+        match self {
+            Part::One => part == 1,
+            Part::Two => part == 2,
+            Part::All => true,
+        }
+    }
+
+    fn from_parts(part1: bool, part2: bool) -> Option<Part> {
+        match (part1, part2) {
+            (true, true) => Some(Part::All),
+            (true, false) => Some(Part::One),
+            (false, true) => Some(Part::Two),
+            (false, false) => None,
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            "all" => Ok(Part::All),
+            _ => Err(format!("Invalid part {:?}, expected 1, 2 or all", s)),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PartValue {
+    Number(u32),
+    Text(String),
+}
+
+impl<'de> Deserialize<'de> for Part {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = match PartValue::deserialize(deserializer)? {
+            PartValue::Number(part) => part.to_string(),
+            PartValue::Text(part) => part,
+        };
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+pub fn parse_count(value: &str) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(0) => Err("must be at least 1".to_string()),
+        Ok(runs) => Ok(runs),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+// The options every binary takes.  Binaries with more of their own flatten
+// these into them, so that adding one here adds it everywhere.
+#[derive(Debug, StructOpt)]
+pub struct CommonOpts {
+    /// Puzzle input file; `-` reads from stdin. If omitted, uses the input
+    /// configured in aoc2021.toml, or stdin if there is none
+    #[structopt(parse(from_os_str))]
+    pub input: Option<PathBuf>,
+
+    /// Which part to run: 1, 2 or all [default: all]
+    #[structopt(long)]
+    pub part: Option<Part>,
+
+    /// Print the answers as a single JSON object
+    #[structopt(long)]
+    pub json: bool,
+
+    /// Log what the solutions are doing to stderr; repeat for more detail.
+    /// RUST_LOG overrides this if set
+    #[structopt(short, long, parse(from_occurrences))]
+    pub verbose: u8,
+
+    /// Threads for the days that run in parallel [default: $AOC_THREADS, or
+    /// one per core]
+    #[structopt(long, value_name = "N", parse(try_from_str = parse_count))]
+    pub threads: Option<usize>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct Opt {
+    #[structopt(flatten)]
+    pub common: CommonOpts,
+
+    /// Report the time and peak allocations of parsing and each part
+    #[structopt(long)]
+    pub time: bool,
+
+    /// Reuse answers cached under .cache/ for the same input
+    #[structopt(long)]
+    pub cache: bool,
+
+    /// Run N more times after a warm-up and report min/median/max runtime
+    #[structopt(long, value_name = "N", parse(try_from_str = parse_count))]
+    pub repeat: Option<usize>,
+
+    /// With --repeat, read the input once up front so I/O isn't measured
+    #[structopt(long, requires = "repeat")]
+    pub no_io: bool,
+
+    /// Write the metrics recorded by `track!` spans to this file
+    #[structopt(long, value_name = "FILE", parse(from_os_str))]
+    pub metrics_out: Option<PathBuf>,
+
+    /// Format for --metrics-out: json or csv [default: json]
+    #[structopt(long, requires = "metrics-out")]
+    pub metrics_format: Option<MetricsFormat>,
+
+    /// Write a Chrome trace of the `track!` spans to this file
+    #[structopt(long, value_name = "FILE", parse(from_os_str))]
+    pub trace_out: Option<PathBuf>,
+
+    /// Show progress bars on stderr for the days' long-running loops
+    #[structopt(long)]
+    pub progress: bool,
+
+    /// Redraw the simulation in place each step, for the days that support
+    /// it. Keys: space pauses, n steps, +/- change speed, q stops animating
+    #[structopt(long)]
+    pub animate: bool,
+
+    /// Milliseconds between frames with --animate [default: 100]
+    #[structopt(long, value_name = "MS", requires = "animate")]
+    pub animate_delay: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Answers {
+    pub day: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timings: Option<Timings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repeat: Option<Summary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Timings {
+    pub parse: Timing,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<Timing>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<Timing>,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        [Some(&self.parse), self.part1.as_ref(), self.part2.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(|timing| timing.elapsed)
+            .sum()
+    }
+
+    fn print(&self) {
+        eprintln!("parse {}", self.parse);
+        if let Some(timing) = &self.part1 {
+            eprintln!("part1 {}", timing);
+        }
+        if let Some(timing) = &self.part2 {
+            eprintln!("part2 {}", timing);
+        }
+    }
+}
+
+impl Answers {
+    pub fn print_text(&self) {
+        for answer in [&self.part1, &self.part2].into_iter().flatten() {
+            if *answer != Answer::None {
+                println!("{}", answer);
+            }
+        }
+
+        if let Some(timings) = &self.timings {
+            timings.print();
+        }
+
+        if let Some(summary) = &self.repeat {
+            eprintln!("{}", summary);
+        }
+    }
+
+    pub fn print_json(&self) {
+        println!("{}", serde_json::to_string(self).unwrap());
+    }
+}
+
+fn timed_part(
+    run: bool,
+    f: impl FnOnce() -> Result<Answer>,
+) -> Result<(Option<Answer>, Option<Timing>)> {
+    if run {
+        let (answer, timing) = timing::time(f);
+        Ok((Some(answer?), Some(timing)))
+    } else {
+        Ok((None, None))
+    }
+}
+
+pub fn solve<S: Solution>(reader: impl BufRead, part: Part) -> Result<Answers> {
+    let (input, parse_timing) = timing::time(|| crate::track!("parse", S::parse(reader)));
+    let input = input?;
+
+    let (part1, part1_timing) = timed_part(part.includes(1), || {
+        crate::track!("part1", S::part1(&input))
+    })?;
+    let (part2, part2_timing) = timed_part(part.includes(2), || {
+        crate::track!("part2", S::part2(&input))
+    })?;
+
+    Ok(Answers {
+        day: S::DAY,
+        part1,
+        part2,
+        timings: Some(Timings {
+            parse: parse_timing,
+            part1: part1_timing,
+            part2: part2_timing,
+        }),
+        repeat: None,
+    })
+}
+
+// Runs `f`, turning a panic into an error so the binary still exits with a
+// code saying it failed.  The panic hook has already printed where it was.
+fn catch_panic<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "no message".to_string()
+        };
+        Err(AocError::Panic(message))
+    })
+}
+
+// Both answers for `input`, for calling a day from other code.
+pub fn solve_str<S: Solution>(input: &str) -> Result<(Answer, Answer)> {
+    let input = S::parse(input.as_bytes())?;
+    Ok((S::part1(&input)?, S::part2(&input)?))
+}
+
+// The answers to `part` of `input`, without any of the timing `solve` does,
+// which isn't available everywhere the library runs.
+pub fn solve_part_str<S: Solution>(input: &str, part: Part) -> Result<Answers> {
+    let input = S::parse(input.as_bytes())?;
+    Ok(Answers {
+        day: S::DAY,
+        part1: part.includes(1).then(|| S::part1(&input)).transpose()?,
+        part2: part.includes(2).then(|| S::part2(&input)).transpose()?,
+        timings: None,
+        repeat: None,
+    })
+}
+
+pub fn solve_timed_str<S: Solution>(input: &str, part: Part) -> Result<Answers> {
+    solve::<S>(input.as_bytes(), part)
+}
+
+pub fn solve_cached<S: Solution>(
+    mut reader: impl BufRead,
+    part: Part,
+    cache: &Cache,
+) -> Result<Answers> {
+    let mut input = vec![];
+    reader.read_to_end(&mut input)?;
+    let key = cache.key(&input);
+
+    let cached = |n| {
+        part.includes(n)
+            .then(|| cache.get(S::YEAR, S::DAY, n, &key))
+            .flatten()
+    };
+    let (part1, part2) = (cached(1), cached(2));
+
+    let remaining = Part::from_parts(
+        part.includes(1) && part1.is_none(),
+        part.includes(2) && part2.is_none(),
+    );
+    let mut answers = match remaining {
+        Some(remaining) => solve::<S>(&input[..], remaining)?,
+        None => Answers {
+            day: S::DAY,
+            part1: None,
+            part2: None,
+            timings: None,
+            repeat: None,
+        },
+    };
+
+    for (n, answer) in [(1, &answers.part1), (2, &answers.part2)] {
+        if let Some(answer) = answer {
+            cache.put(S::YEAR, S::DAY, n, &key, answer)?;
+        }
+    }
+
+    answers.part1 = answers.part1.or(part1);
+    answers.part2 = answers.part2.or(part2);
+    Ok(answers)
+}
+
+fn input_path(opt: &Opt, config: &Config, year: u32, day: u32) -> Option<PathBuf> {
+    if opt.common.input.is_some() {
+        return opt.common.input.clone();
+    }
+
+    let configured = config.day(year, day).input.is_some() || config.has_input_dir(year);
+    configured.then(|| config.input_path(year, day))
+}
+
+fn repeat<S: Solution>(
+    path: Option<&Path>,
+    part: Part,
+    runs: usize,
+    no_io: bool,
+) -> Result<Answers> {
+    let (answers, summary) = if no_io || input::is_stdin(path) {
+        let mut input = vec![];
+        input::open(path)?.read_to_end(&mut input)?;
+        timing::repeat(runs, || solve::<S>(&input[..], part))
+    } else {
+        timing::repeat(runs, || solve::<S>(input::open(path)?, part))
+    };
+
+    let mut answers = answers?;
+    answers.repeat = Some(summary);
+    Ok(answers)
+}
+
+fn run<S: Solution>(opt: &Opt, config: &Config) -> Result<Answers> {
+    let day_config = config.day(S::YEAR, S::DAY);
+    let part = opt.common.part.or(day_config.part).unwrap_or(Part::All);
+    let path = input_path(opt, config, S::YEAR, S::DAY);
+
+    if let Some(runs) = opt.repeat {
+        let mut answers = repeat::<S>(path.as_deref(), part, runs, opt.no_io)?;
+        answers.timings = None;
+        return Ok(answers);
+    }
+
+    let reader = input::open(path.as_deref())?;
+    let mut answers = if opt.cache || day_config.cache == Some(true) {
+        solve_cached::<S>(reader, part, &Cache::default())?
+    } else {
+        solve::<S>(reader, part)?
+    };
+
+    if !(opt.time || day_config.time == Some(true)) {
+        answers.timings = None;
+    }
+
+    Ok(answers)
+}
+
+fn write_metrics(opt: &Opt, tracker: &Tracker) -> Result<()> {
+    if let Some(path) = &opt.metrics_out {
+        let format = opt.metrics_format.unwrap_or(MetricsFormat::Json);
+        tracker.export(format, BufWriter::new(File::create(path)?))?;
+    }
+    if let Some(path) = &opt.trace_out {
+        tracker.export_trace(BufWriter::new(File::create(path)?))?;
+    }
+    Ok(())
+}
+
+// Like `StructOpt::from_args`, but exiting with `BAD_ARGUMENTS` if they're
+// wrong rather than clap's usual 1.
+pub fn parse_args<T: StructOpt>() -> T {
+    T::from_args_safe().unwrap_or_else(|err| {
+        if err.use_stderr() {
+            eprintln!("{}", err.message);
+            process::exit(exit_code::BAD_ARGUMENTS);
+        }
+        err.exit()
+    })
+}
+
+// With --json whatever's reading the output wants the error on one line.
+fn exit_on_error<T>(day: u32, plain: bool, result: Result<T>) -> T {
+    result.unwrap_or_else(|err| {
+        let code = err.exit_code();
+        err.report(&format!("day{:02}", day), plain);
+        process::exit(code);
+    })
+}
+
+// For binaries with modes of their own that want the input but not the
+// answers: opens the input the options point at, exiting if it can't.
+pub fn open_input<S: Solution>(opt: &Opt) -> Box<dyn BufRead> {
+    let config = exit_on_error(S::DAY, opt.common.json, Config::load());
+    let path = input_path(opt, &config, S::YEAR, S::DAY);
+    exit_on_error(S::DAY, opt.common.json, input::open(path.as_deref()))
+}
+
+// As `open_input`, but reading a line at a time as it's consumed.
+pub fn stream_input<S: Solution>(opt: &Opt) -> Box<dyn BufRead> {
+    let config = exit_on_error(S::DAY, opt.common.json, Config::load());
+    let path = input_path(opt, &config, S::YEAR, S::DAY);
+    exit_on_error(
+        S::DAY,
+        opt.common.json,
+        input::open_streaming(path.as_deref()),
+    )
+}
+
+// As `open_input`, but parsed.
+pub fn input<S: Solution>(opt: &Opt) -> S::Input {
+    exit_on_error(S::DAY, opt.common.json, S::parse(open_input::<S>(opt)))
+}
+
+// The `Opt` flags that only mean anything to a normal run of the parts, for
+// binaries' own modes to conflict with rather than quietly ignore.
+pub const RUN_ONLY: &[&str] = &[
+    "time",
+    "cache",
+    "repeat",
+    "metrics-out",
+    "trace-out",
+    "progress",
+    "animate",
+];
+
+// Which parts to run, from --part or the config as a normal run decides.
+pub fn part<S: Solution>(opt: &Opt) -> Part {
+    let config = exit_on_error(S::DAY, opt.common.json, Config::load());
+    let day_config = config.day(S::YEAR, S::DAY);
+    opt.common.part.or(day_config.part).unwrap_or(Part::All)
+}
+
+// Whether to print JSON, from --json or the config's format as a normal run
+// decides.
+pub fn json<S: Solution>(opt: &Opt) -> bool {
+    let config = exit_on_error(S::DAY, opt.common.json, Config::load());
+    opt.common.json || config.format == Some(OutputFormat::Json)
+}
+
+// Exits with the right code for the error if there is one, the same as the
+// normal run does.
+pub fn or_exit<S: Solution, T>(opt: &Opt, result: Result<T>) -> T {
+    exit_on_error(S::DAY, opt.common.json, result)
+}
+
+pub fn main<S: Solution>() {
+    main_with::<S>(parse_args())
+}
+
+// For binaries with options of their own, which flatten `Opt` into them and
+// pass it on once they've dealt with the rest.
+pub fn main_with<S: Solution>(opt: Opt) {
+    logging::init(opt.common.verbose);
+
+    let config = exit_on_error(S::DAY, opt.common.json, Config::load());
+    exit_on_error(
+        S::DAY,
+        opt.common.json,
+        parallel::configure(opt.common.threads),
+    );
+
+    if opt.metrics_out.is_some() || opt.trace_out.is_some() || opt.progress {
+        let tracker = Tracker::new(0, io::sink());
+        if opt.trace_out.is_some() {
+            tracker.record_trace();
+        }
+        if opt.progress {
+            tracker.show_progress();
+        }
+        tracker::install(tracker);
+        // Only fails if a recorder is already set, which is fine to keep.
+        let _ = metrics::set_global_recorder(TrackerRecorder::new());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    if opt.animate {
+        if io::stderr().is_terminal() {
+            let delay = Duration::from_millis(opt.animate_delay.unwrap_or(100));
+            let animator = exit_on_error(
+                S::DAY,
+                opt.common.json,
+                Animator::new(delay).map_err(Into::into),
+            );
+            animate::install(animator);
+        } else {
+            eprintln!("day{:02}: --animate needs stderr to be a terminal", S::DAY);
+        }
+    }
+
+    let answers = catch_panic(|| run::<S>(&opt, &config));
+    // Give the terminal back before printing anything.
+    #[cfg(not(target_arch = "wasm32"))]
+    animate::uninstall();
+    let answers = exit_on_error(S::DAY, opt.common.json, answers);
+
+    if let Some(tracker) = tracker::uninstall() {
+        exit_on_error(S::DAY, opt.common.json, write_metrics(&opt, &tracker));
+    }
+
+    if opt.common.json || config.format == Some(OutputFormat::Json) {
+        answers.print_json();
+    } else {
+        answers.print_text();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::y2021::day01::Day01;
+    use crate::y2021::day18::Day18;
+    use std::fs;
+
+    #[test]
+    fn test_read() {
+        let path = std::env::temp_dir().join(format!("aoc2021-read-{}.txt", process::id()));
+        fs::write(&path, "199\r\n200\r\n208\r\n").unwrap();
+        let input = Day01::read(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(Day01::part1(&input.unwrap()).unwrap(), Answer::from(2));
+
+        assert!(Day01::read("/nonexistent/day01.txt").is_err());
+    }
+
+    struct Panicky;
+
+    impl Solution for Panicky {
+        const YEAR: u32 = 2021;
+        const DAY: u32 = 0;
+        type Input = Vec<u64>;
+
+        fn parse(_reader: impl BufRead) -> Result<Self::Input> {
+            Ok(vec![])
+        }
+
+        fn part1(input: &Self::Input) -> Result<Answer> {
+            // A bug rather than an input without an answer.
+            Ok(input[0].into())
+        }
+
+        fn part2(_input: &Self::Input) -> Result<Answer> {
+            Ok(Answer::None)
+        }
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        // There's nothing to add up without any numbers.
+        let error = solve::<Day18>("".as_bytes(), Part::One).unwrap_err();
+        assert!(matches!(error, AocError::NoSolution { part: 1, .. }));
+        assert_eq!(error.exit_code(), exit_code::NO_SOLUTION);
+
+        let error = solve::<Day01>("1\nx\n".as_bytes(), Part::All).unwrap_err();
+        assert_eq!(error.exit_code(), exit_code::BAD_INPUT);
+    }
+
+    #[test]
+    fn test_panic() {
+        let error = catch_panic(|| solve::<Panicky>("".as_bytes(), Part::One)).unwrap_err();
+        assert!(matches!(error, AocError::Panic(_)));
+        assert_eq!(error.exit_code(), exit_code::FAILURE);
+    }
+
+    #[test]
+    fn test_flatten_opts() {
+        #[derive(StructOpt)]
+        struct DayOpt {
+            #[structopt(flatten)]
+            opt: Opt,
+
+            #[structopt(long)]
+            window: usize,
+        }
+
+        let args = [
+            "day01",
+            "--window",
+            "5",
+            "--part",
+            "2",
+            "-vv",
+            "input.txt",
+            "--time",
+        ];
+        let day_opt = DayOpt::from_iter_safe(args).unwrap();
+        assert_eq!(day_opt.window, 5);
+        assert_eq!(day_opt.opt.common.part, Some(Part::Two));
+        assert_eq!(day_opt.opt.common.verbose, 2);
+        assert_eq!(day_opt.opt.common.input, Some(PathBuf::from("input.txt")));
+        assert!(day_opt.opt.time);
+    }
+}
@@ -0,0 +1,359 @@
+// Sets up a new day: its module, wired into its year's `DAYS` (and the year
+// into `YEARS` if it's new), its binary, an empty input and a place for its
+// answers in answers.toml.
+use crate::core::config::Config;
+use crate::core::error::{AocError, Result};
+use crate::core::registry::{self, DEFAULT_YEAR};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// rustfmt's line width and the longest items it puts several to a line, for
+// laying out lists the way it would.
+const MAX_WIDTH: usize = 100;
+const SHORT_ITEM: usize = 10;
+
+fn module_template(year: u32, day: u32) -> String {
+    format!(
+        "use crate::core::answer::Answer;
+use crate::core::error::Result;
+use crate::core::runner::{{self, Solution}};
+use std::io::{{self, BufRead}};
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    const YEAR: u32 = {year};
+    const DAY: u32 = {day};
+    type Input = String;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {{
+        Ok(io::read_to_string(reader)?)
+    }}
+
+    fn part1(_input: &Self::Input) -> Result<Answer> {{
+        Ok(Answer::None)
+    }}
+
+    fn part2(_input: &Self::Input) -> Result<Answer> {{
+        Ok(Answer::None)
+    }}
+}}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {{
+    runner::solve_str::<Day{day:02}>(input)
+}}
+"
+    )
+}
+
+fn binary_template(year: u32, day: u32) -> String {
+    format!(
+        "use aoc2021::core::runner;
+use aoc2021::y{year}::day{day:02}::Day{day:02};
+
+fn main() {{
+    runner::main::<Day{day:02}>();
+}}
+"
+    )
+}
+
+// What goes before `dayNN` in the binary names for `year`.
+fn bin_prefix(year: u32) -> String {
+    if year == DEFAULT_YEAR {
+        String::new()
+    } else {
+        format!("y{}_", year)
+    }
+}
+
+// The modules go above, as days are added.
+fn year_module_template(year: u32) -> String {
+    format!("\ncrate::days!({}, \"{}\";\n);\n", year, bin_prefix(year))
+}
+
+// Adds `pub mod name;` to the `pub mod` lines of `text`, keeping them in
+// order.
+fn add_module(text: &str, name: &str) -> String {
+    let line = format!("pub mod {};", name);
+    let mut lines = text.lines().collect::<Vec<_>>();
+    let modules = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index, line.strip_prefix("pub mod ")?)));
+
+    let mut position = 0;
+    for (index, module) in modules {
+        if module.trim_end_matches(';') > name {
+            position = index;
+            break;
+        }
+        position = index + 1;
+    }
+    lines.insert(position, &line);
+    lines.join("\n") + "\n"
+}
+
+// Fills `items` into lines indented by `indent`, as many to a line as fit
+// unless any are long, each line ending in a comma.
+fn fill(items: &[String], indent: &str) -> String {
+    let vertical = items.iter().any(|item| item.len() > SHORT_ITEM);
+    let mut text = String::new();
+    let mut line = String::new();
+    for item in items {
+        if !line.is_empty() && (vertical || indent.len() + line.len() + item.len() + 3 > MAX_WIDTH)
+        {
+            text += &format!("{}{},\n", indent, line);
+            line.clear();
+        }
+        if !line.is_empty() {
+            line += ", ";
+        }
+        line += item;
+    }
+    if !line.is_empty() {
+        text += &format!("{}{},\n", indent, line);
+    }
+    text
+}
+
+// The text between `start` and the `end` that follows it.
+fn span(text: &str, start: &str, end: &str) -> Result<(usize, usize)> {
+    let from = text
+        .find(start)
+        .ok_or_else(|| AocError::Scaffold(format!("no {} to add to", start)))?
+        + start.len();
+    let to = from
+        + text[from..]
+            .find(end)
+            .ok_or_else(|| AocError::Scaffold(format!("no end to {}", start)))?;
+    Ok((from, to))
+}
+
+// Adds `day` to the `days!` in a year's mod.rs, laying the days out four to
+// a line in order.
+fn add_day(text: &str, day: u32) -> Result<String> {
+    let (from, to) = span(text, "crate::days!(", ");")?;
+    let (header, entries) = text[from..to]
+        .split_once(';')
+        .ok_or_else(|| AocError::Scaffold("no year in days!".to_string()))?;
+
+    let mut days = entries
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (number, _) = entry
+                .split_once("=>")
+                .ok_or_else(|| AocError::Scaffold(format!("bad entry {}", entry)))?;
+            let number = number
+                .trim()
+                .parse::<u32>()
+                .map_err(|err| AocError::Scaffold(format!("bad entry {}: {}", entry, err)))?;
+            Ok((number, entry.to_string()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    days.push((day, format!("{} => day{:02}::Day{:02}", day, day, day)));
+    days.sort_by_key(|&(number, _)| number);
+
+    let mut lines = String::new();
+    for chunk in days.chunks(4) {
+        let entries = chunk
+            .iter()
+            .map(|(_, entry)| &entry[..])
+            .collect::<Vec<_>>();
+        lines += &format!("    {},\n", entries.join(", "));
+    }
+    Ok(format!(
+        "{}{};\n{}{}",
+        &text[..from],
+        header,
+        lines,
+        &text[to..]
+    ))
+}
+
+// Adds `year` to `YEARS` in registry.rs.
+fn add_year(text: &str, year: u32) -> Result<String> {
+    let (from, to) = span(text, "pub const YEARS: &[Year] = &[", "];")?;
+    let mut years = text[from..to]
+        .split("crate::y")
+        .skip(1)
+        .filter_map(|rest| rest.split("::").next()?.parse::<u32>().ok())
+        .collect::<Vec<_>>();
+    years.push(year);
+    years.sort_unstable();
+
+    let entries = years
+        .iter()
+        .map(|year| {
+            format!(
+                "    Year {{\n        number: {},\n        days: crate::y{}::DAYS,\n    }},\n",
+                year, year
+            )
+        })
+        .collect::<String>();
+    Ok(format!("{}\n{}{}", &text[..from], entries, &text[to..]))
+}
+
+// Adds `bin` to the `days!` in tests/answers.rs.
+fn add_answers_test(text: &str, bin: &str) -> Result<String> {
+    let (from, to) = span(text, "days!(\n", ");")?;
+    let mut bins = text[from..to]
+        .split(',')
+        .map(str::trim)
+        .filter(|bin| !bin.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    bins.push(bin.to_string());
+    bins.sort_by_key(|bin| (bin.starts_with('y'), bin.clone()));
+    Ok(format!(
+        "{}{}{}",
+        &text[..from],
+        fill(&bins, "    "),
+        &text[to..]
+    ))
+}
+
+fn answers_entry(bin: &str) -> String {
+    format!("\n[{}]\n# part1 =\n# part2 =\n", bin)
+}
+
+fn update(path: &Path, edit: impl FnOnce(&str) -> Result<String>) -> Result<()> {
+    let text = fs::read_to_string(path).map_err(|source| AocError::Open {
+        path: path.to_owned(),
+        source,
+    })?;
+    write(path, &edit(&text)?)
+}
+
+fn write(path: &Path, text: &str) -> Result<()> {
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, text))
+        .map_err(|err| AocError::Scaffold(format!("failed to write {}: {}", path.display(), err)))
+}
+
+// Creates `day` of `year` in the crate at `root`, returning the files it
+// created or changed.
+pub fn create(root: &Path, config: &Config, year: u32, day: u32) -> Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(AocError::Day {
+            day,
+            message: "there are only 25 days".to_string(),
+        });
+    }
+
+    let year_dir = root.join("src").join(format!("y{}", year));
+    let module = year_dir.join(format!("day{:02}.rs", day));
+    if registry::find(year, day).is_some() || module.exists() {
+        return Err(AocError::Day {
+            day,
+            message: format!("already exists in {}", year),
+        });
+    }
+
+    let mut changed = vec![];
+    let year_module = year_dir.join("mod.rs");
+    if !year_module.exists() {
+        write(&year_module, &year_module_template(year))?;
+        let lib = root.join("src").join("lib.rs");
+        update(&lib, |text| Ok(add_module(text, &format!("y{}", year))))?;
+        let registry = root.join("src").join("core").join("registry.rs");
+        update(&registry, |text| add_year(text, year))?;
+        changed.extend([lib, registry]);
+    }
+
+    write(&module, &module_template(year, day))?;
+    update(&year_module, |text| {
+        add_day(&add_module(text, &format!("day{:02}", day)), day)
+    })?;
+    changed.extend([module, year_module]);
+
+    let bin = format!("{}day{:02}", bin_prefix(year), day);
+    let binary = root.join("src").join("bin").join(format!("{}.rs", bin));
+    write(&binary, &binary_template(year, day))?;
+    changed.push(binary);
+
+    let answers_test = root.join("tests").join("answers.rs");
+    update(&answers_test, |text| add_answers_test(text, &bin))?;
+    let answers = root.join("answers.toml");
+    update(&answers, |text| Ok(text.to_string() + &answers_entry(&bin)))?;
+    changed.extend([answers_test, answers]);
+
+    let input = root.join(config.input_path(year, day));
+    if !input.exists() {
+        write(&input, "")?;
+        changed.push(input);
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_add_module() {
+        let text = "pub mod day01;\npub mod day03;\npub mod gen;\n\ncrate::days!(2021, \"\";\n);\n";
+        assert_eq!(
+            add_module(text, "day02"),
+            "pub mod day01;\npub mod day02;\npub mod day03;\npub mod gen;\n\ncrate::days!(2021, \"\";\n);\n"
+        );
+        assert_eq!(
+            add_module("// Years.\npub mod y2021;\n", "y2022"),
+            "// Years.\npub mod y2021;\npub mod y2022;\n"
+        );
+        assert_eq!(add_module("", "day01"), "pub mod day01;\n");
+    }
+
+    #[test]
+    fn test_add_day() {
+        let current = include_str!("../y2021/mod.rs");
+        let without = current.replace(" 24 => day24::Day24,", "");
+        assert_eq!(add_day(&without, 24).unwrap(), current);
+
+        assert_eq!(
+            add_day(&year_module_template(2022), 1).unwrap(),
+            "\ncrate::days!(2022, \"y2022_\";\n    1 => day01::Day01,\n);\n"
+        );
+    }
+
+    #[test]
+    fn test_add_year() {
+        let one = "pub const YEARS: &[Year] = &[Year {
+    number: 2021,
+    days: crate::y2021::DAYS,
+}];
+";
+        let two = "pub const YEARS: &[Year] = &[
+    Year {
+        number: 2021,
+        days: crate::y2021::DAYS,
+    },
+    Year {
+        number: 2022,
+        days: crate::y2022::DAYS,
+    },
+];
+";
+        assert_eq!(add_year(one, 2022).unwrap(), two);
+    }
+
+    #[test]
+    fn test_add_answers_test() {
+        let short = "days!(
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14,
+);
+";
+        let without = short.replace("    day14,\n", "");
+        assert_eq!(add_answers_test(&without, "day14").unwrap(), short);
+
+        assert_eq!(
+            add_answers_test("days!(\n    day01, day02,\n);\n", "y2022_day01").unwrap(),
+            "days!(\n    day01,\n    day02,\n    y2022_day01,\n);\n"
+        );
+    }
+}
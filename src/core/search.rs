@@ -0,0 +1,223 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+// Every state reachable from `start` through states accepted by `can_visit`,
+// in breadth-first order, along with the number of steps taken to reach it.
+pub fn bfs<S, I, F, P>(start: S, mut successors: F, mut can_visit: P) -> Vec<(S, usize)>
+where
+    S: Eq + Hash + Clone,
+    I: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    P: FnMut(&S) -> bool,
+{
+    let mut visited = vec![];
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    if can_visit(&start) {
+        seen.insert(start.clone());
+        queue.push_back((start, 0));
+    }
+
+    while let Some((state, steps)) = queue.pop_front() {
+        for next in successors(&state) {
+            if !seen.contains(&next) && can_visit(&next) {
+                seen.insert(next.clone());
+                queue.push_back((next, steps + 1));
+            }
+        }
+        visited.push((state, steps));
+    }
+
+    visited
+}
+
+// Every state reachable from `start` through states accepted by `can_visit`,
+// in depth-first order.
+pub fn dfs<S, I, F, P>(start: S, mut successors: F, mut can_visit: P) -> Vec<S>
+where
+    S: Eq + Hash + Clone,
+    I: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    P: FnMut(&S) -> bool,
+{
+    let mut visited = vec![];
+    let mut seen = HashSet::new();
+    let mut stack = vec![start];
+
+    while let Some(state) = stack.pop() {
+        if seen.contains(&state) || !can_visit(&state) {
+            continue;
+        }
+
+        seen.insert(state.clone());
+
+        let mut next_states = successors(&state).into_iter().collect::<Vec<_>>();
+        next_states.reverse();
+        stack.extend(next_states);
+
+        visited.push(state);
+    }
+
+    visited
+}
+
+// The region of cells accepted by `passable` that can be reached from
+// `start`, including `start` itself if it's passable.
+pub fn flood_fill<P, I, F, Q>(start: P, mut neighbours: F, mut passable: Q) -> HashSet<P>
+where
+    P: Eq + Hash + Clone,
+    I: IntoIterator<Item = P>,
+    F: FnMut(&P) -> I,
+    Q: FnMut(&P) -> bool,
+{
+    let mut region = HashSet::new();
+    let mut stack = vec![start];
+
+    while let Some(cell) = stack.pop() {
+        if region.contains(&cell) || !passable(&cell) {
+            continue;
+        }
+        stack.extend(neighbours(&cell));
+        region.insert(cell);
+    }
+
+    region
+}
+
+// Split the passable `cells` into connected regions, numbered from 0 in the
+// order their first cell appears.  Impassable cells aren't labelled.
+pub fn label_regions<P, C, I, F, Q>(
+    cells: C,
+    mut neighbours: F,
+    mut passable: Q,
+) -> HashMap<P, usize>
+where
+    P: Eq + Hash + Clone,
+    C: IntoIterator<Item = P>,
+    I: IntoIterator<Item = P>,
+    F: FnMut(&P) -> I,
+    Q: FnMut(&P) -> bool,
+{
+    let mut labels = HashMap::new();
+    let mut num_regions = 0;
+
+    for cell in cells {
+        if labels.contains_key(&cell) || !passable(&cell) {
+            continue;
+        }
+        for member in flood_fill(cell, &mut neighbours, &mut passable) {
+            labels.insert(member, num_regions);
+        }
+        num_regions += 1;
+    }
+
+    labels
+}
+
+// The number of distinct paths from `start` to a goal.  States aren't
+// deduplicated, so `successors` has to stop paths going round in circles,
+// e.g. by keeping track of where they've been in the state.
+pub fn count_paths<S, I, F, G>(start: S, mut successors: F, mut is_goal: G) -> usize
+where
+    I: IntoIterator<Item = S>,
+    F: FnMut(&S) -> I,
+    G: FnMut(&S) -> bool,
+{
+    let mut stack = vec![start];
+    let mut num_paths = 0;
+
+    while let Some(state) = stack.pop() {
+        if is_goal(&state) {
+            num_paths += 1;
+        } else {
+            stack.extend(successors(&state));
+        }
+    }
+
+    num_paths
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn neighbours(&(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        vec![(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+    }
+
+    fn in_grid(&(x, y): &(i32, i32)) -> bool {
+        (0..3).contains(&x) && (0..3).contains(&y)
+    }
+
+    #[test]
+    fn test_bfs_steps() {
+        let visited = bfs((0, 0), neighbours, in_grid);
+        assert_eq!(visited.len(), 9);
+        assert_eq!(visited[0], ((0, 0), 0));
+        assert_eq!(visited.last(), Some(&((2, 2), 4)));
+        assert!(visited.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    #[test]
+    fn test_bfs_start_not_visitable() {
+        assert!(bfs((5, 5), neighbours, in_grid).is_empty());
+    }
+
+    #[test]
+    fn test_dfs_order() {
+        let successors = |&n: &u32| {
+            if n < 4 {
+                vec![2 * n, 2 * n + 1]
+            } else {
+                vec![]
+            }
+        };
+        let visited = dfs(1, successors, |_| true);
+        assert_eq!(visited, vec![1, 2, 4, 5, 3, 6, 7]);
+    }
+
+    #[test]
+    fn test_dfs_flood_fill() {
+        let walls = [(1, 0), (1, 1), (1, 2)];
+        let visited = dfs((0, 0), neighbours, |pos| {
+            in_grid(pos) && !walls.contains(pos)
+        });
+        assert_eq!(visited.len(), 3);
+        assert!(visited.iter().all(|&(x, _)| x == 0));
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let walls = [(1, 0), (1, 1), (1, 2)];
+        let passable = |pos: &(i32, i32)| in_grid(pos) && !walls.contains(pos);
+        let region = flood_fill((2, 1), neighbours, passable);
+        assert_eq!(region.len(), 3);
+        assert!(region.iter().all(|&(x, _)| x == 2));
+        assert!(flood_fill((1, 1), neighbours, passable).is_empty());
+    }
+
+    #[test]
+    fn test_label_regions() {
+        let walls = [(1, 0), (1, 1), (1, 2)];
+        let cells = (0..3).flat_map(|y| (0..3).map(move |x| (x, y)));
+        let labels = label_regions(cells, neighbours, |pos| {
+            in_grid(pos) && !walls.contains(pos)
+        });
+        assert_eq!(labels.len(), 6);
+        assert_eq!(labels[&(0, 0)], 0);
+        assert_eq!(labels[&(2, 2)], 1);
+        assert_eq!(labels.get(&(1, 1)), None);
+    }
+
+    #[test]
+    fn test_count_paths() {
+        // Paths moving only right or down across a 3x3 grid of points.
+        let successors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x, y + 1)]
+                .into_iter()
+                .filter(|&(x, y)| x <= 2 && y <= 2)
+        };
+        assert_eq!(count_paths((0, 0), successors, |&pos| pos == (2, 2)), 6);
+    }
+}
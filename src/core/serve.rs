@@ -0,0 +1,172 @@
+use crate::core::error::{AocError, Result};
+use crate::core::registry::{self, Year};
+use crate::core::runner::Part;
+use serde_json::{json, Value};
+use std::io::Read;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use tiny_http::{Header, Request, Response, Server};
+
+// Far more than any puzzle input.
+const MAX_BODY: u64 = 16 * 1024 * 1024;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Reply {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Reply {
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Reply {
+            status,
+            body: json!({ "error": message.into() }).to_string(),
+        }
+    }
+}
+
+// Answers `POST /solve/{day}/{part}` with the input as the body, where part
+// is 1, 2 or all.  The reply has the same JSON as `--json --time`, less the
+// peak allocations: the allocation counter is shared by every request being
+// solved at once, so the peaks would be meaningless.
+pub fn handle(year: &Year, method: &str, path: &str, body: &str) -> Reply {
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+    let (day, part) = match segments[..] {
+        ["solve", day, part] => (day, part),
+        _ => return Reply::error(404, format!("no such path {}", path)),
+    };
+    if method != "POST" {
+        return Reply::error(405, "use POST");
+    }
+
+    let Some(day) = day
+        .parse::<u32>()
+        .ok()
+        .and_then(|day| registry::find(year.number, day))
+    else {
+        return Reply::error(404, format!("no such day {}", day));
+    };
+    let part = match part.parse::<Part>() {
+        Ok(part) => part,
+        Err(err) => return Reply::error(400, err),
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| day.solve_timed(body, part))) {
+        Ok(Ok(answers)) => {
+            let mut body = serde_json::to_value(&answers).unwrap();
+            if let Some(timings) = body.get_mut("timings").and_then(Value::as_object_mut) {
+                for timing in timings.values_mut().filter_map(Value::as_object_mut) {
+                    timing.remove("peak_bytes");
+                }
+            }
+            Reply {
+                status: 200,
+                body: body.to_string(),
+            }
+        }
+        Ok(Err(err)) => Reply::error(400, err.to_string()),
+        Err(_) => Reply::error(500, "solver panicked"),
+    }
+}
+
+fn respond(year: &Year, mut request: Request) {
+    let mut body = String::new();
+    let reply = match request
+        .as_reader()
+        .take(MAX_BODY + 1)
+        .read_to_string(&mut body)
+    {
+        Ok(_) if body.len() as u64 > MAX_BODY => Reply::error(413, "input too large"),
+        Ok(_) => handle(year, request.method().as_str(), request.url(), &body),
+        Err(err) => Reply::error(400, err.to_string()),
+    };
+    tracing::info!(
+        method = %request.method(),
+        url = request.url(),
+        status = reply.status,
+        "request"
+    );
+
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(reply.body)
+        .with_status_code(reply.status)
+        .with_header(content_type);
+    if let Err(err) = request.respond(response) {
+        tracing::warn!(%err, "failed to send response");
+    }
+}
+
+pub fn listen(address: &str) -> Result<Server> {
+    Server::http(address).map_err(|err| AocError::Serve(err.to_string()))
+}
+
+// Serves requests for `year`'s days until the process is killed, on a
+// worker per core so a slow day doesn't hold up the rest without a flood of
+// requests starting a thread each.
+pub fn serve(server: Server, year: &Year) {
+    let workers = thread::available_parallelism().map_or(1, usize::from);
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                for request in server.incoming_requests() {
+                    respond(year, request);
+                }
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpStream;
+
+    const INPUT: &str = "3,4,3,1,2\n";
+
+    #[test]
+    fn test_handle() {
+        let year = registry::year(2021).unwrap();
+
+        let reply = handle(year, "POST", "/solve/6/1", INPUT);
+        assert_eq!(reply.status, 200);
+        let answers: serde_json::Value = serde_json::from_str(&reply.body).unwrap();
+        assert_eq!(answers["part1"], 5934);
+        assert!(answers.get("part2").is_none());
+        assert!(answers["timings"]["part1"]["seconds"].is_number());
+        assert!(answers["timings"]["part1"].get("peak_bytes").is_none());
+
+        let reply = handle(year, "POST", "/solve/6/all", INPUT);
+        let answers: serde_json::Value = serde_json::from_str(&reply.body).unwrap();
+        assert_eq!(answers["part2"], 26984457539_u64);
+
+        assert_eq!(handle(year, "GET", "/solve/6/1", INPUT).status, 405);
+        assert_eq!(handle(year, "POST", "/solve/26/1", INPUT).status, 404);
+        assert_eq!(handle(year, "POST", "/solve/six/1", INPUT).status, 404);
+        assert_eq!(handle(year, "POST", "/solve/6/3", INPUT).status, 400);
+        assert_eq!(handle(year, "POST", "/solve/6", INPUT).status, 404);
+        assert_eq!(handle(year, "POST", "/solve/4/1", "nonsense").status, 400);
+    }
+
+    #[test]
+    fn test_serve() {
+        let server = listen("127.0.0.1:0").unwrap();
+        let address = server.server_addr().to_ip().unwrap();
+        thread::spawn(move || serve(server, registry::year(2021).unwrap()));
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "POST /solve/6/1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            INPUT.len(),
+            INPUT
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.contains("application/json"), "{}", response);
+        assert!(response.contains("\"part1\":5934"), "{}", response);
+    }
+}
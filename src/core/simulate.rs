@@ -0,0 +1,42 @@
+use crate::core::error::{AocError, Result};
+
+// Applies `step` to `state` until `stop` holds for the state and whatever
+// the step returned, giving the number of steps taken and the final state.
+// Fails if that takes more than `max_steps`.
+pub fn simulate_until<S, O>(
+    mut state: S,
+    mut step: impl FnMut(&mut S) -> O,
+    mut stop: impl FnMut(&S, &O) -> bool,
+    max_steps: usize,
+) -> Result<(usize, S)> {
+    for steps in 1..=max_steps {
+        let output = step(&mut state);
+        if stop(&state, &output) {
+            return Ok((steps, state));
+        }
+    }
+    Err(AocError::StepLimit(max_steps))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn collatz(n: &mut u64) -> u64 {
+        *n = if n.is_multiple_of(2) {
+            *n / 2
+        } else {
+            3 * *n + 1
+        };
+        *n
+    }
+
+    #[test]
+    fn test_simulate_until() {
+        let (steps, state) = simulate_until(27, collatz, |_, &n| n == 1, 1000).unwrap();
+        assert_eq!((steps, state), (111, 1));
+
+        let result = simulate_until(27, collatz, |&n, _| n == 0, 1000);
+        assert!(matches!(result, Err(AocError::StepLimit(1000))));
+    }
+}
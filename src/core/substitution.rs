@@ -0,0 +1,141 @@
+use crate::core::counter::Counter;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// The element to insert between each adjacent pair it's given for.
+pub type Rules<T> = HashMap<(T, T), T>;
+
+// A sequence that grows by inserting an element between every adjacent pair
+// with a rule at each step.  The sequence doubles in length each step, so
+// only how many times each pair appears is kept, along with the ends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairCounts<T: Eq + Hash> {
+    pairs: Counter<(T, T)>,
+    first: T,
+    last: T,
+}
+
+impl<T: Eq + Hash + Copy> PairCounts<T> {
+    pub fn new(sequence: &[T]) -> Self {
+        assert!(
+            !sequence.is_empty(),
+            "can't substitute in an empty sequence"
+        );
+        PairCounts {
+            pairs: sequence.windows(2).map(|pair| (pair[0], pair[1])).collect(),
+            first: sequence[0],
+            last: sequence[sequence.len() - 1],
+        }
+    }
+
+    // Inserting never happens at the ends, so they stay the same.
+    pub fn step(&mut self, rules: &Rules<T>) {
+        let mut pairs = Counter::new();
+
+        for (&(a, b), num) in self.pairs.iter() {
+            if let Some(&c) = rules.get(&(a, b)) {
+                pairs.add((a, c), num);
+                pairs.add((c, b), num);
+            } else {
+                pairs.add((a, b), num);
+            }
+        }
+
+        self.pairs = pairs;
+    }
+
+    pub fn steps(&mut self, rules: &Rules<T>, steps: usize) {
+        for _ in 0..steps {
+            self.step(rules);
+        }
+    }
+
+    pub fn pairs(&self) -> &Counter<(T, T)> {
+        &self.pairs
+    }
+
+    pub fn len(&self) -> u64 {
+        self.pairs.total() + 1
+    }
+
+    // There's always at least the first element.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    // How many times each element appears in the sequence.  Every element is
+    // the first of one pair and the last of another, except for the ends
+    // which only appear in one pair, so adding one for each of those makes
+    // the pair counts exactly twice the element counts.
+    pub fn elements(&self) -> Counter<T> {
+        let mut doubled = self
+            .pairs
+            .iter()
+            .flat_map(|(&(a, b), num)| [(a, num), (b, num)])
+            .collect::<Counter<_>>();
+        doubled.insert(self.first);
+        doubled.insert(self.last);
+
+        doubled
+            .iter()
+            .map(|(&element, num)| (element, num / 2))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rules() -> Rules<char> {
+        [
+            "CH B", "HH N", "CB H", "NH C", "HB C", "HC B", "HN C", "NN C", "BH H", "NC B", "NB B",
+            "BN B", "BB N", "BC B", "CC N", "CN C",
+        ]
+        .iter()
+        .map(|rule| {
+            let rule = rule.chars().collect::<Vec<_>>();
+            ((rule[0], rule[1]), rule[3])
+        })
+        .collect()
+    }
+
+    fn substitute(sequence: &[char], rules: &Rules<char>) -> Vec<char> {
+        let mut result = vec![sequence[0]];
+        for pair in sequence.windows(2) {
+            result.extend(rules.get(&(pair[0], pair[1])));
+            result.push(pair[1]);
+        }
+        result
+    }
+
+    #[test]
+    fn test_matches_substituting() {
+        let rules = rules();
+        let mut sequence = "NNCB".chars().collect::<Vec<_>>();
+        let mut counts = PairCounts::new(&sequence);
+
+        for _ in 0..8 {
+            sequence = substitute(&sequence, &rules);
+            counts.step(&rules);
+            assert_eq!(counts.len(), sequence.len() as u64);
+            assert_eq!(counts.elements(), sequence.iter().copied().collect());
+        }
+    }
+
+    #[test]
+    fn test_elements() {
+        let mut counts = PairCounts::new(&"NNCB".chars().collect::<Vec<_>>());
+        counts.steps(&rules(), 10);
+        let elements = counts.elements();
+        assert_eq!(counts.len(), 3073);
+        assert_eq!(elements.get(&'B'), 1749);
+        assert_eq!(elements.get(&'H'), 161);
+
+        // Without any pairs it's only ever the one element.
+        let mut counts = PairCounts::new(&['A']);
+        counts.step(&rules());
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts.elements().get(&'A'), 1);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::core::allocation;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Timing {
+    #[serde(
+        rename = "seconds",
+        serialize_with = "as_seconds",
+        deserialize_with = "from_seconds"
+    )]
+    pub elapsed: Duration,
+    pub peak_bytes: usize,
+}
+
+fn as_seconds<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64())
+}
+
+fn from_seconds<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    f64::deserialize(deserializer).map(Duration::from_secs_f64)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Summary {
+    pub runs: usize,
+    #[serde(serialize_with = "as_seconds", deserialize_with = "from_seconds")]
+    pub min: Duration,
+    #[serde(serialize_with = "as_seconds", deserialize_with = "from_seconds")]
+    pub median: Duration,
+    #[serde(serialize_with = "as_seconds", deserialize_with = "from_seconds")]
+    pub max: Duration,
+}
+
+impl Summary {
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "No samples to summarize");
+        samples.sort();
+
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+
+        Summary {
+            runs: samples.len(),
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} runs: min {:.3?}, median {:.3?}, max {:.3?}",
+            self.runs, self.min, self.median, self.max
+        )
+    }
+}
+
+pub fn repeat<T>(runs: usize, mut f: impl FnMut() -> T) -> (T, Summary) {
+    let result = f();
+
+    let samples = (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect();
+
+    (result, Summary::new(samples))
+}
+
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Timing) {
+    allocation::reset_peak();
+    let start_bytes = allocation::current();
+    let start = Instant::now();
+
+    let result = f();
+
+    let timing = Timing {
+        elapsed: start.elapsed(),
+        peak_bytes: allocation::peak().saturating_sub(start_bytes),
+    };
+    (result, timing)
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+impl Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>12.3?} {:>12} peak",
+            self.elapsed,
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
@@ -0,0 +1,1056 @@
+use crate::core::allocation;
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+// Each power of two of nanoseconds is split into this many buckets, so
+// durations are recorded to within about 6%.
+const SUB_BUCKETS: u64 = 16;
+const SUB_BUCKET_BITS: u32 = SUB_BUCKETS.trailing_zeros();
+
+#[derive(Default)]
+struct Histogram {
+    buckets: Vec<usize>,
+    count: usize,
+}
+
+impl Histogram {
+    fn index(nanos: u64) -> usize {
+        if nanos < SUB_BUCKETS {
+            nanos as usize
+        } else {
+            let shift = 63 - nanos.leading_zeros() - SUB_BUCKET_BITS;
+            let sub_bucket = (nanos >> shift) - SUB_BUCKETS;
+            ((shift as u64 + 1) * SUB_BUCKETS + sub_bucket) as usize
+        }
+    }
+
+    fn lowest_value(index: usize) -> u64 {
+        let index = index as u64;
+        if index < SUB_BUCKETS {
+            index
+        } else {
+            let shift = index / SUB_BUCKETS - 1;
+            (SUB_BUCKETS + index % SUB_BUCKETS) << shift
+        }
+    }
+
+    fn record(&mut self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        let index = Self::index(nanos);
+        if index >= self.buckets.len() {
+            self.buckets.resize(index + 1, 0);
+        }
+        self.buckets[index] += 1;
+        self.count += 1;
+    }
+
+    fn percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let rank = ((percentile / 100.0 * self.count as f64).ceil() as usize).max(1);
+        let mut seen = 0;
+        self.buckets.iter().enumerate().find_map(|(index, &count)| {
+            seen += count;
+            (seen >= rank).then(|| Duration::from_nanos(Self::lowest_value(index)))
+        })
+    }
+
+    fn reset(&mut self) {
+        self.buckets.clear();
+        self.count = 0;
+    }
+}
+
+#[derive(Default)]
+struct Count {
+    count: usize,
+    duration: Duration,
+    histogram: Histogram,
+    allocations: usize,
+    peak_bytes: usize,
+}
+
+impl Count {
+    fn update(&mut self, duration: Duration, usage: Option<AllocationUsage>) {
+        self.count += 1;
+        self.duration += duration;
+        self.histogram.record(duration);
+        if let Some(usage) = usage {
+            self.allocations += usage.allocations;
+            self.peak_bytes = self.peak_bytes.max(usage.peak_bytes);
+        }
+    }
+
+    fn nanos_per_op(&self) -> Option<u128> {
+        if self.count > 0 {
+            Some(self.duration.as_nanos() / self.count as u128)
+        } else {
+            None
+        }
+    }
+
+    fn percentiles(&self) -> String {
+        [50.0, 95.0, 99.0]
+            .iter()
+            .map(|&percentile| {
+                let value = self
+                    .histogram
+                    .percentile(percentile)
+                    .map(|duration| format!("{}ns", duration.as_nanos()))
+                    .unwrap_or_else(|| "n/a".to_string());
+                format!("p{} {}", percentile, value)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn reset(&mut self) {
+        self.count = 0;
+        self.duration = Duration::from_millis(0);
+        self.histogram.reset();
+        self.allocations = 0;
+        self.peak_bytes = 0;
+    }
+}
+
+// Where the allocator was when a span started, so its own usage can be
+// worked out when it ends.
+struct AllocationStart {
+    allocations: usize,
+    bytes: usize,
+    outer_peak: usize,
+}
+
+impl AllocationStart {
+    fn new() -> Self {
+        let outer_peak = allocation::peak();
+        allocation::reset_peak();
+        AllocationStart {
+            allocations: allocation::allocations(),
+            bytes: allocation::current(),
+            outer_peak,
+        }
+    }
+
+    fn finish(self) -> AllocationUsage {
+        let usage = AllocationUsage {
+            allocations: allocation::allocations() - self.allocations,
+            peak_bytes: allocation::peak().saturating_sub(self.bytes),
+        };
+        allocation::restore_peak(self.outer_peak);
+        usage
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AllocationUsage {
+    allocations: usize,
+    peak_bytes: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsFormat {
+    Json,
+    Csv,
+}
+
+impl FromStr for MetricsFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(MetricsFormat::Json),
+            "csv" => Ok(MetricsFormat::Csv),
+            _ => Err(format!(
+                "Invalid metrics format {:?}, expected json or csv",
+                s
+            )),
+        }
+    }
+}
+
+// An operation and the spans it was started within, outermost first.
+type Path = Vec<&'static str>;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Metric {
+    pub operation: String,
+    pub count: usize,
+    pub total_nanos: u128,
+    pub mean_nanos: Option<u128>,
+    pub p50_nanos: Option<u128>,
+    pub p95_nanos: Option<u128>,
+    pub p99_nanos: Option<u128>,
+    // Only counted once `Tracker::track_allocations` is called.
+    pub allocations: usize,
+    pub peak_bytes: usize,
+}
+
+impl Metric {
+    fn new(path: &[&'static str], count: &Count) -> Self {
+        let percentile = |percentile| {
+            count
+                .histogram
+                .percentile(percentile)
+                .map(|duration| duration.as_nanos())
+        };
+
+        Metric {
+            operation: path.join("/"),
+            count: count.count,
+            total_nanos: count.duration.as_nanos(),
+            mean_nanos: count.nanos_per_op(),
+            p50_nanos: percentile(50.0),
+            p95_nanos: percentile(95.0),
+            p99_nanos: percentile(99.0),
+            allocations: count.allocations,
+            peak_bytes: count.peak_bytes,
+        }
+    }
+}
+
+// A begin or end event in the Chrome trace-event format, which Perfetto and
+// chrome://tracing can show as a timeline.
+#[derive(Debug, Clone, Serialize)]
+struct TraceEvent {
+    name: &'static str,
+    ph: &'static str,
+    ts: f64,
+    pid: u32,
+    tid: u32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Trace<'a> {
+    trace_events: &'a [TraceEvent],
+    display_time_unit: &'static str,
+}
+
+struct Span {
+    path: Path,
+    start: Instant,
+    allocation: Option<AllocationStart>,
+    tracker: Rc<Tracker>,
+}
+
+impl Span {
+    fn new(path: Path, tracker: Rc<Tracker>) -> Self {
+        tracker.trace_event(path.last().unwrap(), "B");
+        Span {
+            path,
+            start: Instant::now(),
+            allocation: tracker.track_allocations.get().then(AllocationStart::new),
+            tracker,
+        }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let duration = self.start.elapsed();
+        let usage = self.allocation.take().map(AllocationStart::finish);
+        let path = std::mem::take(&mut self.path);
+        self.tracker.trace_event(path.last().unwrap(), "E");
+        self.tracker.report_duration(path, duration, usage)
+    }
+}
+
+// Spans within an operation that wasn't sampled aren't recorded at all.
+pub struct DurationTracker(Option<Span>);
+
+pub struct OperationTracker {
+    tracker: Rc<Tracker>,
+    sampled: bool,
+}
+
+impl OperationTracker {
+    fn new(tracker: Rc<Tracker>) -> Self {
+        let sampled = tracker
+            .count
+            .get()
+            .is_multiple_of(tracker.sample_every.get());
+        OperationTracker { tracker, sampled }
+    }
+}
+
+impl Drop for OperationTracker {
+    fn drop(&mut self) {
+        self.tracker.done()
+    }
+}
+
+pub struct Tracker {
+    report_interval: usize,
+    report_period: Cell<Option<Duration>>,
+    last_report: Cell<Instant>,
+    sample_every: Cell<usize>,
+    output: RefCell<Box<dyn Write>>,
+    count: Cell<usize>,
+    epoch: Instant,
+    trace: RefCell<Option<Vec<TraceEvent>>>,
+    track_allocations: Cell<bool>,
+    show_progress: Cell<bool>,
+    durations: RefCell<HashMap<Path, Count>>,
+    totals: RefCell<HashMap<Path, Count>>,
+    counters: RefCell<HashMap<String, u64>>,
+    gauges: RefCell<HashMap<String, f64>>,
+}
+
+impl Tracker {
+    // Reports go to `output` every `report_interval` operations (0 for never)
+    // and when the tracker is dropped.  Pass `io::stderr()` to keep them out
+    // of the answers on stdout, or `io::sink()` to only collect metrics.
+    pub fn new(report_interval: usize, output: impl Write + 'static) -> Rc<Self> {
+        Rc::new(Tracker {
+            report_interval,
+            report_period: Cell::new(None),
+            last_report: Cell::new(Instant::now()),
+            sample_every: Cell::new(1),
+            output: RefCell::new(Box::new(output)),
+            count: Cell::new(0),
+            epoch: Instant::now(),
+            trace: RefCell::new(None),
+            track_allocations: Cell::new(false),
+            show_progress: Cell::new(false),
+            durations: RefCell::new(Default::default()),
+            totals: RefCell::new(Default::default()),
+            counters: RefCell::new(Default::default()),
+            gauges: RefCell::new(Default::default()),
+        })
+    }
+
+    // Also report when `period` has passed since the last report.
+    pub fn report_every(&self, period: Duration) {
+        self.report_period.set(Some(period));
+    }
+
+    // Only record spans for one in every `every` operations, to cut the
+    // overhead on hot loops.  The counts in the metrics are then samples too.
+    pub fn sample(&self, every: usize) {
+        assert!(every > 0, "sample rate must be at least 1");
+        self.sample_every.set(every);
+    }
+
+    // Everything recorded since the tracker was created, by operation.
+    pub fn metrics(&self) -> Vec<Metric> {
+        let totals = self.totals.borrow();
+        let mut metrics = totals.iter().collect::<Vec<_>>();
+        metrics.sort_by_key(|(path, _)| *path);
+        metrics
+            .into_iter()
+            .map(|(path, count)| Metric::new(path, count))
+            .collect()
+    }
+
+    pub fn export(&self, format: MetricsFormat, mut out: impl Write) -> io::Result<()> {
+        let metrics = self.metrics();
+
+        match format {
+            MetricsFormat::Json => {
+                serde_json::to_writer_pretty(&mut out, &metrics)?;
+                writeln!(out)
+            }
+            MetricsFormat::Csv => {
+                let optional =
+                    |value: Option<u128>| value.map(|value| value.to_string()).unwrap_or_default();
+                writeln!(
+                    out,
+                    "operation,count,total_nanos,mean_nanos,p50_nanos,p95_nanos,p99_nanos,allocations,peak_bytes"
+                )?;
+                for metric in metrics {
+                    writeln!(
+                        out,
+                        "{},{},{},{},{},{},{},{},{}",
+                        metric.operation,
+                        metric.count,
+                        metric.total_nanos,
+                        optional(metric.mean_nanos),
+                        optional(metric.p50_nanos),
+                        optional(metric.p95_nanos),
+                        optional(metric.p99_nanos),
+                        metric.allocations,
+                        metric.peak_bytes,
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    // Record a duration measured elsewhere, as if it was a span on its own.
+    pub fn record_duration(&self, operation: &'static str, duration: Duration) {
+        self.report_duration(vec![operation], duration, None)
+    }
+
+    pub fn increment_counter(&self, name: &str, value: u64) {
+        *self
+            .counters
+            .borrow_mut()
+            .entry(name.to_string())
+            .or_default() += value;
+    }
+
+    // Raise the counter to `value`, if it's not already past it.
+    pub fn raise_counter(&self, name: &str, value: u64) {
+        let mut counters = self.counters.borrow_mut();
+        let counter = counters.entry(name.to_string()).or_default();
+        *counter = (*counter).max(value);
+    }
+
+    pub fn adjust_gauge(&self, name: &str, delta: f64) {
+        *self
+            .gauges
+            .borrow_mut()
+            .entry(name.to_string())
+            .or_default() += delta;
+    }
+
+    pub fn set_gauge(&self, name: &str, value: f64) {
+        self.gauges.borrow_mut().insert(name.to_string(), value);
+    }
+
+    pub fn counters(&self) -> Vec<(String, u64)> {
+        let mut counters = self
+            .counters
+            .borrow()
+            .clone()
+            .into_iter()
+            .collect::<Vec<_>>();
+        counters.sort_by(|(a, _), (b, _)| a.cmp(b));
+        counters
+    }
+
+    pub fn gauges(&self) -> Vec<(String, f64)> {
+        let mut gauges = self.gauges.borrow().clone().into_iter().collect::<Vec<_>>();
+        gauges.sort_by(|(a, _), (b, _)| a.cmp(b));
+        gauges
+    }
+
+    // Start keeping a begin and end event for every span from now on, for
+    // `export_trace`.  This costs memory for each span, so it's off by default.
+    pub fn record_trace(&self) {
+        self.trace.borrow_mut().get_or_insert_with(Vec::new);
+    }
+
+    // Count allocations and the peak bytes allocated within each span from now
+    // on, using the crate's counting allocator.
+    pub fn track_allocations(&self) {
+        self.track_allocations.set(true);
+    }
+
+    // Draw a bar on stderr for each `progress` started from now on.
+    pub fn show_progress(&self) {
+        self.show_progress.set(true);
+    }
+
+    pub fn export_trace(&self, mut out: impl Write) -> io::Result<()> {
+        let trace = self.trace.borrow();
+        let trace = Trace {
+            trace_events: trace.as_deref().unwrap_or_default(),
+            display_time_unit: "ns",
+        };
+        serde_json::to_writer(&mut out, &trace)?;
+        writeln!(out)
+    }
+
+    fn trace_event(&self, name: &'static str, ph: &'static str) {
+        if let Some(trace) = self.trace.borrow_mut().as_mut() {
+            trace.push(TraceEvent {
+                name,
+                ph,
+                ts: self.epoch.elapsed().as_nanos() as f64 / 1000.0,
+                pid: std::process::id(),
+                tid: 0,
+            });
+        }
+    }
+
+    fn report_duration(&self, path: Path, duration: Duration, usage: Option<AllocationUsage>) {
+        self.durations
+            .borrow_mut()
+            .entry(path.clone())
+            .or_default()
+            .update(duration, usage);
+        self.totals
+            .borrow_mut()
+            .entry(path)
+            .or_default()
+            .update(duration, usage)
+    }
+
+    fn done(&self) {
+        let count = self.count.get() + 1;
+        self.count.set(count);
+
+        let period_elapsed = self
+            .report_period
+            .get()
+            .is_some_and(|period| self.last_report.get().elapsed() >= period);
+
+        if count.is_multiple_of(self.report_interval) || period_elapsed {
+            self.write_report(&format!("{}", count), &self.durations.borrow());
+            self.last_report.set(Instant::now());
+
+            self.durations
+                .borrow_mut()
+                .values_mut()
+                .for_each(|count| count.reset());
+        }
+    }
+
+    fn write_report(&self, title: &str, counts: &HashMap<Path, Count>) {
+        let mut counts = counts.iter().collect::<Vec<_>>();
+        counts.sort_by_key(|(path, _)| *path);
+
+        let mut report = format!("{}:\n", title);
+        for (path, duration_count) in counts {
+            report += &format!(
+                "{:indent$}{} {} (x{}, {})",
+                "",
+                path.last().unwrap(),
+                duration_count.count,
+                duration_count
+                    .nanos_per_op()
+                    .map(|val| format!("{}ns", val))
+                    .unwrap_or_else(|| "n/a".to_string()),
+                duration_count.percentiles(),
+                indent = 2 * path.len()
+            );
+            if self.track_allocations.get() {
+                report += &format!(
+                    " {} allocations, peak {}B",
+                    duration_count.allocations, duration_count.peak_bytes
+                );
+            }
+            report.push('\n');
+        }
+
+        for (name, value) in self.counters() {
+            report += &format!("  {} = {}\n", name, value);
+        }
+        for (name, value) in self.gauges() {
+            report += &format!("  {} = {}\n", name, value);
+        }
+
+        // Reporting is best effort, a closed pipe shouldn't stop the
+        // work being tracked.
+        let _ = self.output.borrow_mut().write_all(report.as_bytes());
+    }
+}
+
+// Finish with a summary of the whole run, so even runs too short to hit a
+// periodic report say something.
+impl Drop for Tracker {
+    fn drop(&mut self) {
+        let count = self.count.get();
+        if count > 0 {
+            self.write_report(&format!("{} total", count), &self.totals.borrow());
+        }
+    }
+}
+
+thread_local! {
+    static GLOBAL: RefCell<Option<Rc<Tracker>>> = const { RefCell::new(None) };
+    // The paths of the open `track!` spans, innermost last; `None` for spans
+    // in an operation that wasn't sampled.
+    static OPEN_SPANS: RefCell<Vec<Option<Path>>> = const { RefCell::new(Vec::new()) };
+}
+
+// Make `tracker` the one `track!` reports to on this thread.
+pub fn install(tracker: Rc<Tracker>) {
+    GLOBAL.with(|global| *global.borrow_mut() = Some(tracker));
+}
+
+pub fn uninstall() -> Option<Rc<Tracker>> {
+    GLOBAL.with(|global| global.borrow_mut().take())
+}
+
+pub fn global() -> Option<Rc<Tracker>> {
+    GLOBAL.with(|global| global.borrow().clone())
+}
+
+// A span on the installed tracker, nested in whichever `track!` span is open.
+// An outermost span is also an operation.
+pub struct GlobalSpan {
+    _span: DurationTracker,
+    _operation: Option<OperationTracker>,
+    open: bool,
+}
+
+pub fn span(operation: &'static str) -> GlobalSpan {
+    let tracker = match global() {
+        Some(tracker) => tracker,
+        None => {
+            return GlobalSpan {
+                _span: DurationTracker(None),
+                _operation: None,
+                open: false,
+            }
+        }
+    };
+
+    OPEN_SPANS.with(|open_spans| {
+        let mut open_spans = open_spans.borrow_mut();
+        let (outer, new_operation) = match open_spans.last() {
+            Some(outer) => (outer.clone(), None),
+            None => {
+                let new_operation = tracker.track_operation();
+                (new_operation.sampled.then(Vec::new), Some(new_operation))
+            }
+        };
+
+        let path = outer.map(|mut path| {
+            path.push(operation);
+            path
+        });
+        open_spans.push(path.clone());
+
+        GlobalSpan {
+            _span: DurationTracker(path.map(|path| Span::new(path, tracker))),
+            _operation: new_operation,
+            open: true,
+        }
+    })
+}
+
+impl Drop for GlobalSpan {
+    fn drop(&mut self) {
+        if self.open {
+            OPEN_SPANS.with(|open_spans| open_spans.borrow_mut().pop());
+        }
+    }
+}
+
+// How far through a long loop the solution is, drawn as a bar if the
+// installed tracker shows progress.  The count is also added to the counter
+// `name` when it's dropped.
+pub struct Progress {
+    name: &'static str,
+    count: Cell<u64>,
+    tracker: Option<Rc<Tracker>>,
+    bar: Option<ProgressBar>,
+}
+
+impl Progress {
+    pub fn inc(&self, delta: u64) {
+        self.set_position(self.count.get() + delta);
+    }
+
+    pub fn set_position(&self, position: u64) {
+        self.count.set(position);
+        if let Some(bar) = &self.bar {
+            bar.set_position(position);
+        }
+    }
+
+    pub fn set_message(&self, message: impl Into<Cow<'static, str>>) {
+        if let Some(bar) = &self.bar {
+            bar.set_message(message);
+        }
+    }
+
+    // Print a line on stderr without messing up the bar.
+    pub fn println(&self, line: impl AsRef<str>) {
+        match &self.bar {
+            Some(bar) => bar.println(line),
+            None => eprintln!("{}", line.as_ref()),
+        }
+    }
+
+    // Step on once for each item `iter` yields.
+    pub fn wrap<I: Iterator>(self, iter: I) -> impl Iterator<Item = I::Item> {
+        iter.inspect(move |_| self.inc(1))
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+        if let Some(tracker) = &self.tracker {
+            tracker.increment_counter(self.name, self.count.get());
+        }
+    }
+}
+
+// All the bars draw through this so that other output can get out of their
+// way with `suspend`.
+fn bars() -> &'static MultiProgress {
+    static BARS: OnceLock<MultiProgress> = OnceLock::new();
+    BARS.get_or_init(|| MultiProgress::with_draw_target(ProgressDrawTarget::stderr()))
+}
+
+// Run `write` with any progress bars cleared from the screen, then redraw
+// them underneath whatever it printed.
+pub fn suspend<R>(write: impl FnOnce() -> R) -> R {
+    bars().suspend(write)
+}
+
+// Start reporting progress through `total` steps, or an unknown number if
+// `None`, on the global tracker.  Without one it only counts.
+pub fn progress(name: &'static str, total: Option<u64>) -> Progress {
+    let tracker = global();
+    let bar = tracker
+        .as_ref()
+        .filter(|tracker| tracker.show_progress.get())
+        .map(|_| {
+            let (bar, template) = match total {
+                Some(total) => (
+                    ProgressBar::new(total),
+                    "{prefix} [{elapsed_precise}] {wide_bar} {pos}/{len} (eta {eta}) {msg}",
+                ),
+                None => (
+                    ProgressBar::new_spinner(),
+                    "{prefix} [{elapsed_precise}] {spinner} {pos} {msg}",
+                ),
+            };
+            let bar = bars().add(bar);
+            if let Ok(style) = ProgressStyle::with_template(template) {
+                bar.set_style(style);
+            }
+            bar.set_prefix(name);
+            bar
+        });
+    Progress {
+        name,
+        count: Cell::new(0),
+        tracker,
+        bar,
+    }
+}
+
+// Step through `iter` with a progress bar sized from its length:
+//
+//     for scanner in tracker::progress_iter("scanners", scanners.iter()) {
+pub fn progress_iter<I: ExactSizeIterator>(
+    name: &'static str,
+    iter: I,
+) -> impl Iterator<Item = I::Item> {
+    progress(name, Some(iter.len() as u64)).wrap(iter)
+}
+
+// Time `$body` on the global tracker, if one is installed:
+//
+//     let input = track!("parse", parse(reader));
+#[macro_export]
+macro_rules! track {
+    ($operation:expr, $body:expr) => {{
+        let _span = $crate::core::tracker::span($operation);
+        $body
+    }};
+}
+
+pub trait DurationTrack: Sized {
+    fn child(&self, operation: &'static str) -> Self;
+}
+
+pub trait OperationTrack {
+    type DurationTracker: DurationTrack;
+    fn track_duration(&self, operation: &'static str) -> Self::DurationTracker;
+}
+
+pub trait Track {
+    type OperationTracker: OperationTrack;
+    fn track_operation(&self) -> Self::OperationTracker;
+}
+
+impl Track for Rc<Tracker> {
+    type OperationTracker = OperationTracker;
+
+    fn track_operation(&self) -> Self::OperationTracker {
+        OperationTracker::new(self.clone())
+    }
+}
+
+impl OperationTrack for OperationTracker {
+    type DurationTracker = DurationTracker;
+
+    fn track_duration(&self, operation: &'static str) -> Self::DurationTracker {
+        DurationTracker(
+            self.sampled
+                .then(|| Span::new(vec![operation], self.tracker.clone())),
+        )
+    }
+}
+
+impl DurationTrack for DurationTracker {
+    fn child(&self, operation: &'static str) -> Self {
+        DurationTracker(self.0.as_ref().map(|span| {
+            let mut path = span.path.clone();
+            path.push(operation);
+            Span::new(path, span.tracker.clone())
+        }))
+    }
+}
+
+impl Track for () {
+    type OperationTracker = ();
+
+    fn track_operation(&self) -> Self::OperationTracker {}
+}
+
+impl OperationTrack for () {
+    type DurationTracker = ();
+
+    fn track_duration(&self, _operation: &'static str) -> Self::DurationTracker {}
+}
+
+impl DurationTrack for () {
+    fn child(&self, _operation: &'static str) -> Self {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets() {
+        for nanos in [0, 1, 15, 16, 17, 31, 32, 33, 1000, 123_456_789] {
+            let index = Histogram::index(nanos);
+            let lowest = Histogram::lowest_value(index);
+            assert!(lowest <= nanos, "{} in bucket from {}", nanos, lowest);
+            assert!(
+                nanos - lowest <= nanos / SUB_BUCKETS,
+                "{} in bucket from {}",
+                nanos,
+                lowest
+            );
+            assert_eq!(Histogram::index(lowest), index);
+        }
+    }
+
+    #[test]
+    fn test_export_csv() {
+        let tracker = Tracker::new(1000, io::sink());
+        for _ in 0..3 {
+            let operation = tracker.track_operation();
+            drop(operation.track_duration("step"));
+        }
+
+        let mut out = vec![];
+        tracker.export(MetricsFormat::Csv, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with("step,3,"));
+    }
+
+    #[test]
+    fn test_nested_spans() {
+        let tracker = Tracker::new(1000, io::sink());
+        {
+            let operation = tracker.track_operation();
+            let run = operation.track_duration("run");
+            for _ in 0..2 {
+                run.child("step");
+            }
+        }
+
+        let metrics = tracker.metrics();
+        let operations = metrics
+            .iter()
+            .map(|metric| (metric.operation.as_str(), metric.count))
+            .collect::<Vec<_>>();
+        assert_eq!(operations, vec![("run", 1), ("run/step", 2)]);
+    }
+
+    #[test]
+    fn test_export_trace() {
+        let tracker = Tracker::new(1000, io::sink());
+        drop(tracker.track_operation().track_duration("untraced"));
+
+        tracker.record_trace();
+        {
+            let operation = tracker.track_operation();
+            let run = operation.track_duration("run");
+            drop(run.child("step"));
+        }
+
+        let mut out = vec![];
+        tracker.export_trace(&mut out).unwrap();
+        let trace: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let events = trace["traceEvents"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| {
+                (
+                    event["name"].as_str().unwrap(),
+                    event["ph"].as_str().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![("run", "B"), ("step", "B"), ("step", "E"), ("run", "E")]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "count-allocations")]
+    fn test_track_allocations() {
+        let tracker = Tracker::new(1000, io::sink());
+        tracker.track_allocations();
+        {
+            let operation = tracker.track_operation();
+            let run = operation.track_duration("run");
+            let step = run.child("step");
+            let big = vec![0u8; 1 << 20];
+            drop(big);
+            drop(step);
+            let small = vec![0u8; 1 << 10];
+            drop(small);
+        }
+
+        let metrics = tracker.metrics();
+        let (run, step) = (&metrics[0], &metrics[1]);
+        assert!(step.allocations >= 1);
+        assert!(run.allocations > step.allocations);
+        assert!(step.peak_bytes >= 1 << 20);
+        assert!(run.peak_bytes >= step.peak_bytes);
+    }
+
+    #[derive(Clone, Default)]
+    struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_report_output() {
+        let buffer = Buffer::default();
+        let tracker = Tracker::new(2, buffer.clone());
+        for _ in 0..3 {
+            let operation = tracker.track_operation();
+            drop(operation.track_duration("step"));
+        }
+
+        let report = String::from_utf8(buffer.0.borrow().clone()).unwrap();
+        let lines = report.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "2:");
+        assert!(lines[1].starts_with("  step 2 "));
+    }
+
+    #[test]
+    fn test_report_every_period() {
+        let buffer = Buffer::default();
+        let tracker = Tracker::new(0, buffer.clone());
+        tracker.report_every(Duration::ZERO);
+        drop(tracker.track_operation().track_duration("step"));
+
+        let report = String::from_utf8(buffer.0.borrow().clone()).unwrap();
+        assert!(report.starts_with("1:\n  step 1 "));
+    }
+
+    #[test]
+    fn test_sampling() {
+        let tracker = Tracker::new(0, io::sink());
+        tracker.sample(3);
+        for _ in 0..10 {
+            let operation = tracker.track_operation();
+            drop(operation.track_duration("run").child("step"));
+        }
+
+        let metrics = tracker.metrics();
+        let operations = metrics
+            .iter()
+            .map(|metric| (metric.operation.as_str(), metric.count))
+            .collect::<Vec<_>>();
+        assert_eq!(operations, vec![("run", 4), ("run/step", 4)]);
+    }
+
+    #[test]
+    fn test_summary_on_drop() {
+        let buffer = Buffer::default();
+        let tracker = Tracker::new(0, buffer.clone());
+        for _ in 0..3 {
+            drop(tracker.track_operation().track_duration("step"));
+        }
+        assert!(buffer.0.borrow().is_empty());
+
+        drop(tracker);
+        let report = String::from_utf8(buffer.0.borrow().clone()).unwrap();
+        assert!(report.starts_with("3 total:\n  step 3 "));
+    }
+
+    #[test]
+    fn test_global_tracker() {
+        assert_eq!(crate::track!("untracked", 1 + 1), 2);
+
+        install(Tracker::new(0, io::sink()));
+        for _ in 0..2 {
+            crate::track!("run", {
+                crate::track!("parse", ());
+                crate::track!("solve", crate::track!("step", ()));
+            });
+        }
+        let tracker = uninstall().unwrap();
+
+        let metrics = tracker.metrics();
+        let operations = metrics
+            .iter()
+            .map(|metric| (metric.operation.as_str(), metric.count))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            operations,
+            vec![
+                ("run", 2),
+                ("run/parse", 2),
+                ("run/solve", 2),
+                ("run/solve/step", 2)
+            ]
+        );
+        assert_eq!(tracker.count.get(), 2);
+    }
+
+    #[test]
+    fn test_progress() {
+        let items = progress_iter("untracked", 0..3).collect::<Vec<_>>();
+        assert_eq!(items, vec![0, 1, 2]);
+
+        install(Tracker::new(0, io::sink()));
+        assert_eq!(progress_iter("items", 0..3).count(), 3);
+        {
+            let progress = progress("steps", None);
+            progress.inc(2);
+            progress.set_position(5);
+        }
+        let tracker = uninstall().unwrap();
+        assert_eq!(
+            tracker.counters(),
+            vec![("items".to_string(), 3), ("steps".to_string(), 5)]
+        );
+    }
+
+    #[test]
+    fn test_histogram_percentiles() {
+        let mut histogram = Histogram::default();
+        for nanos in 1..=100 {
+            histogram.record(Duration::from_nanos(nanos));
+        }
+        assert_eq!(histogram.percentile(0.0), Some(Duration::from_nanos(1)));
+        assert_eq!(histogram.percentile(50.0), Some(Duration::from_nanos(50)));
+        assert_eq!(histogram.percentile(100.0), Some(Duration::from_nanos(100)));
+        assert_eq!(Histogram::default().percentile(50.0), None);
+    }
+}
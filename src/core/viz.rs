@@ -0,0 +1,198 @@
+use crate::core::bounds::Rect;
+use crate::core::error::Result;
+use crate::core::grid::Grid;
+use crate::core::position::Position;
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::time::Duration;
+
+pub type Colour = [u8; 3];
+
+pub const BLACK: Colour = [0, 0, 0];
+pub const WHITE: Colour = [255, 255, 255];
+
+// An RGB picture, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Colour>,
+}
+
+impl Image {
+    pub fn from_grid<T>(grid: &Grid<T>, colour: impl Fn(&T) -> Colour) -> Self {
+        Image {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.values().map(colour).collect(),
+        }
+    }
+
+    // The smallest picture covering `points`, drawn as `on` over `off`.
+    pub fn from_points(
+        points: impl IntoIterator<Item = Position>,
+        on: Colour,
+        off: Colour,
+    ) -> Self {
+        let points = points.into_iter().collect::<HashSet<_>>();
+        let Some(bounds) = Rect::bounding(points.iter().copied()) else {
+            return Image {
+                width: 0,
+                height: 0,
+                pixels: vec![],
+            };
+        };
+        let pixels = bounds
+            .iter()
+            .map(|position| if points.contains(&position) { on } else { off })
+            .collect();
+        Image {
+            width: bounds.width() as usize,
+            height: bounds.height() as usize,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // Each pixel blown up into a `factor` by `factor` square.
+    pub fn scale(&self, factor: usize) -> Self {
+        let width = self.width * factor;
+        let height = self.height * factor;
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x / factor, y / factor)))
+            .map(|(x, y)| self.pixels[y * self.width + x])
+            .collect();
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flatten().copied().collect()
+    }
+
+    pub fn write_ppm(&self, mut out: impl Write) -> Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.bytes())?;
+        Ok(())
+    }
+
+    pub fn write_png(&self, out: impl Write) -> Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.bytes())
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)?;
+        Ok(())
+    }
+}
+
+// Frames of the same size, shown one after another.
+pub struct Animation {
+    frames: Vec<Image>,
+    delay: Duration,
+}
+
+impl Animation {
+    pub fn new(delay: Duration) -> Self {
+        Animation {
+            frames: vec![],
+            delay,
+        }
+    }
+
+    pub fn push(&mut self, frame: Image) {
+        if let Some(first) = self.frames.first() {
+            assert_eq!(
+                (first.width, first.height),
+                (frame.width, frame.height),
+                "frames must all be the same size"
+            );
+        }
+        self.frames.push(frame);
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    // Writes a GIF that loops forever.
+    pub fn write_gif(&self, out: impl Write) -> Result<()> {
+        let (width, height) = self
+            .frames
+            .first()
+            .map_or((0, 0), |frame| (frame.width as u16, frame.height as u16));
+        let mut encoder = gif::Encoder::new(out, width, height, &[]).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+
+        // GIF delays are in hundredths of a second.
+        let delay = (self.delay.as_millis() / 10) as u16;
+        for image in &self.frames {
+            let mut frame = gif::Frame::from_rgb_speed(width, height, &image.bytes(), 10);
+            frame.delay = delay;
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn checkerboard() -> Image {
+        let grid = Grid::new(2, 2, vec![true, false, false, true]);
+        Image::from_grid(&grid, |&on| if on { WHITE } else { BLACK })
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut out = vec![];
+        checkerboard().write_ppm(&mut out).unwrap();
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend([255, 255, 255, 0, 0, 0, 0, 0, 0, 255, 255, 255]);
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_from_points() {
+        let image = Image::from_points([Position::new(3, 1), Position::new(4, 3)], WHITE, BLACK);
+        assert_eq!((image.width(), image.height()), (2, 3));
+        assert_eq!(image.pixels[0], WHITE);
+        assert_eq!(image.pixels[5], WHITE);
+        assert_eq!(image.pixels[1], BLACK);
+        assert_eq!(image.scale(3).width(), 6);
+    }
+
+    #[test]
+    fn test_png_and_gif() {
+        let mut png = vec![];
+        checkerboard().scale(4).write_png(&mut png).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+
+        let mut animation = Animation::new(Duration::from_millis(100));
+        animation.push(checkerboard());
+        animation.push(checkerboard().scale(1));
+        let mut gif = vec![];
+        animation.write_gif(&mut gif).unwrap();
+        assert_eq!(&gif[..6], b"GIF89a");
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+type Snapshot = HashMap<PathBuf, Option<SystemTime>>;
+
+pub struct Watcher {
+    files: Vec<PathBuf>,
+    dirs: Vec<PathBuf>,
+    exclude: Vec<PathBuf>,
+    snapshot: Snapshot,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+fn walk(dir: &Path, exclude: &[PathBuf], files: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        if exclude.contains(&path) {
+            continue;
+        }
+
+        if path.is_dir() {
+            walk(&path, exclude, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+impl Watcher {
+    pub fn new() -> Self {
+        Watcher {
+            files: vec![],
+            dirs: vec![],
+            exclude: vec![],
+            snapshot: HashMap::new(),
+        }
+    }
+
+    pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.files.push(path.into());
+        self
+    }
+
+    pub fn source_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dirs.push(path.into());
+        self
+    }
+
+    pub fn exclude(mut self, path: impl Into<PathBuf>) -> Self {
+        self.exclude.push(path.into());
+        self
+    }
+
+    fn take_snapshot(&self) -> Snapshot {
+        let mut paths = self.files.clone();
+        for dir in &self.dirs {
+            walk(dir, &self.exclude, &mut paths);
+        }
+
+        paths
+            .into_iter()
+            .map(|path| {
+                let time = modified(&path);
+                (path, time)
+            })
+            .collect()
+    }
+
+    pub fn start(mut self) -> Self {
+        self.snapshot = self.take_snapshot();
+        self
+    }
+
+    pub fn wait_for_change(&mut self, interval: Duration) -> Vec<PathBuf> {
+        loop {
+            thread::sleep(interval);
+
+            let snapshot = self.take_snapshot();
+            let mut changed = snapshot
+                .iter()
+                .filter(|(path, time)| self.snapshot.get(*path) != Some(time))
+                .map(|(path, _)| path.clone())
+                .chain(
+                    self.snapshot
+                        .keys()
+                        .filter(|path| !snapshot.contains_key(*path))
+                        .cloned(),
+                )
+                .collect::<Vec<_>>();
+            self.snapshot = snapshot;
+
+            if !changed.is_empty() {
+                changed.sort();
+                return changed;
+            }
+        }
+    }
+}
+
+impl Default for Watcher {
+    fn default() -> Self {
+        Watcher::new()
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::VecDeque;
+use std::iter::Fuse;
+use std::ops::{Add, Sub};
+
+// Sliding windows over any iterator, not only slices, keeping just the items
+// in the current window.
+pub trait WindowsExt: Iterator + Sized {
+    // Each run of `size` consecutive items, overlapping by all but one.
+    fn windows(self, size: usize) -> Windows<Self>
+    where
+        Self::Item: Clone,
+    {
+        assert!(size > 0, "windows must hold at least one item");
+        Windows {
+            iter: self.fuse(),
+            recent: VecDeque::with_capacity(size),
+            size,
+        }
+    }
+
+    // The sum of each run of `size` consecutive items, keeping a running
+    // total rather than adding up every window.
+    fn window_sums(self, size: usize) -> WindowSums<Self>
+    where
+        Self::Item: Copy + Default + Add<Output = Self::Item> + Sub<Output = Self::Item>,
+    {
+        assert!(size > 0, "windows must hold at least one item");
+        WindowSums {
+            iter: self.fuse(),
+            recent: VecDeque::with_capacity(size),
+            size,
+            sum: Self::Item::default(),
+        }
+    }
+}
+
+impl<I: Iterator> WindowsExt for I {}
+
+pub struct Windows<I: Iterator> {
+    iter: Fuse<I>,
+    recent: VecDeque<I::Item>,
+    size: usize,
+}
+
+impl<I> Iterator for Windows<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.recent.len() == self.size {
+            self.recent.pop_front();
+        }
+        while self.recent.len() < self.size {
+            self.recent.push_back(self.iter.next()?);
+        }
+        Some(self.recent.iter().cloned().collect())
+    }
+}
+
+pub struct WindowSums<I: Iterator> {
+    iter: Fuse<I>,
+    recent: VecDeque<I::Item>,
+    size: usize,
+    sum: I::Item,
+}
+
+impl<I, T> Iterator for WindowSums<I>
+where
+    I: Iterator<Item = T>,
+    T: Copy + Add<Output = T> + Sub<Output = T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.recent.len() < self.size {
+            let item = self.iter.next()?;
+            self.sum = self.sum + item;
+            self.recent.push_back(item);
+        }
+
+        // Take the oldest off straight away so unsigned sums never go below
+        // zero.
+        let sum = self.sum;
+        self.sum = self.sum - self.recent.pop_front().unwrap();
+        Some(sum)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_windows() {
+        let items = [1, 2, 3, 4, 5];
+        for size in 1..=6 {
+            assert_eq!(
+                items.iter().copied().windows(size).collect::<Vec<_>>(),
+                items.windows(size).map(<[_]>::to_vec).collect::<Vec<_>>(),
+            );
+        }
+
+        let words = "a b c".split(' ').windows(2).collect::<Vec<_>>();
+        assert_eq!(words, [["a", "b"], ["b", "c"]]);
+    }
+
+    #[test]
+    fn test_window_sums() {
+        let items = [3_u64, 0, 7, 1, 9, 2];
+        for size in 1..=7 {
+            assert_eq!(
+                items.iter().copied().window_sums(size).collect::<Vec<_>>(),
+                items
+                    .windows(size)
+                    .map(|window| window.iter().sum::<u64>())
+                    .collect::<Vec<_>>(),
+            );
+        }
+    }
+}
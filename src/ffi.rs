@@ -0,0 +1,172 @@
+// A C interface to the solvers, declared in include/aoc2021.h.  Answers come
+// back as the text the binaries print: digits for numbers, the text itself
+// otherwise, and nothing at all for a part with no answer.
+use crate::core::error::exit_code;
+use crate::core::registry::{self, DEFAULT_YEAR};
+use crate::core::runner::Part;
+use std::panic::{self, AssertUnwindSafe};
+use std::{slice, str};
+
+pub const AOC2021_OK: i32 = 0;
+pub const AOC2021_NO_SUCH_DAY: i32 = 1;
+pub const AOC2021_NO_SUCH_PART: i32 = 2;
+pub const AOC2021_INVALID_UTF8: i32 = 3;
+// The error message is written to the output buffer, cut short if need be.
+pub const AOC2021_INVALID_INPUT: i32 = 4;
+// `*out_len` is set to the length needed and nothing is written.
+pub const AOC2021_BUFFER_TOO_SMALL: i32 = 5;
+pub const AOC2021_NULL_POINTER: i32 = 6;
+pub const AOC2021_PANIC: i32 = 7;
+// The input parsed but has no answer, or the search for one gave up.  The
+// message is written as for `AOC2021_INVALID_INPUT`, as it is for the next.
+pub const AOC2021_NO_SOLUTION: i32 = 8;
+pub const AOC2021_FAILURE: i32 = 9;
+
+fn solve(day: u32, part: u32, input: &[u8]) -> Result<String, (i32, String)> {
+    let day = registry::find(DEFAULT_YEAR, day)
+        .ok_or_else(|| (AOC2021_NO_SUCH_DAY, format!("no such day {}", day)))?;
+    let part = match part {
+        1 => Part::One,
+        2 => Part::Two,
+        _ => return Err((AOC2021_NO_SUCH_PART, format!("no such part {}", part))),
+    };
+    let input = str::from_utf8(input).map_err(|err| (AOC2021_INVALID_UTF8, err.to_string()))?;
+
+    let answers = day.solve(input, part).map_err(|err| {
+        let code = match err.exit_code() {
+            exit_code::BAD_INPUT => AOC2021_INVALID_INPUT,
+            exit_code::NO_SOLUTION => AOC2021_NO_SOLUTION,
+            _ => AOC2021_FAILURE,
+        };
+        (code, err.to_string())
+    })?;
+    let answer = answers.part1.or(answers.part2);
+    Ok(answer.map(|answer| answer.to_string()).unwrap_or_default())
+}
+
+/// Solves `part` (1 or 2) of `day` for the `len` bytes of UTF-8 at
+/// `input_ptr`, writing the answer to `out_buf`, which has room for
+/// `*out_len` bytes, and setting `*out_len` to its length.  The answer isn't
+/// NUL-terminated.  Returns one of the `AOC2021_` codes.
+///
+/// # Safety
+///
+/// `input_ptr` must point to `len` readable bytes, `out_len` to a writable
+/// `usize`, and `out_buf` to `*out_len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc2021_solve(
+    day: u32,
+    part: u32,
+    input_ptr: *const u8,
+    len: usize,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    if (input_ptr.is_null() && len > 0) || out_len.is_null() || (out_buf.is_null() && *out_len > 0)
+    {
+        return AOC2021_NULL_POINTER;
+    }
+    let input = if len == 0 {
+        &[][..]
+    } else {
+        slice::from_raw_parts(input_ptr, len)
+    };
+
+    // Unwinding across the FFI boundary is undefined behaviour.
+    let result = panic::catch_unwind(AssertUnwindSafe(|| solve(day, part, input)))
+        .unwrap_or_else(|_| Err((AOC2021_PANIC, "solver panicked".to_string())));
+
+    let capacity = *out_len;
+    let (code, text) = match result {
+        Ok(answer) if answer.len() > capacity => {
+            *out_len = answer.len();
+            return AOC2021_BUFFER_TOO_SMALL;
+        }
+        Ok(answer) => (AOC2021_OK, answer),
+        Err((code, mut message)) => {
+            // Only cut on a character boundary.
+            let mut end = message.len().min(capacity);
+            while !message.is_char_boundary(end) {
+                end -= 1;
+            }
+            message.truncate(end);
+            (code, message)
+        }
+    };
+
+    if !text.is_empty() {
+        out_buf.copy_from_nonoverlapping(text.as_ptr(), text.len());
+    }
+    *out_len = text.len();
+    code
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn call(day: u32, part: u32, input: &str, capacity: usize) -> (i32, usize, String) {
+        let mut out = vec![0; capacity];
+        let mut out_len = capacity;
+        let code = unsafe {
+            aoc2021_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr(),
+                &mut out_len,
+            )
+        };
+        let written = String::from_utf8_lossy(&out[..out_len.min(capacity)]).into_owned();
+        (code, out_len, written)
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+        assert_eq!(call(1, 1, input, 16), (AOC2021_OK, 1, "7".to_string()));
+        assert_eq!(call(1, 2, input, 16), (AOC2021_OK, 1, "5".to_string()));
+        assert_eq!(call(25, 2, ">.\n", 16), (AOC2021_OK, 0, "".to_string()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(call(26, 1, "", 16).0, AOC2021_NO_SUCH_DAY);
+        assert_eq!(call(1, 3, "", 16).0, AOC2021_NO_SUCH_PART);
+
+        let (code, len, message) = call(1, 1, "one\n", 64);
+        assert_eq!(code, AOC2021_INVALID_INPUT);
+        assert_eq!(len, message.len());
+        assert!(message.starts_with("invalid input"), "{}", message);
+        assert_eq!(call(1, 1, "one\n", 4).1, 4);
+
+        let (code, len, message) = call(3, 2, "101\n110\n", 64);
+        assert_eq!(code, AOC2021_NO_SOLUTION);
+        assert_eq!(len, message.len());
+        assert!(message.starts_with("no solution"), "{}", message);
+
+        let (code, len, _) = call(6, 2, "3,4,3,1,2\n", 4);
+        assert_eq!((code, len), (AOC2021_BUFFER_TOO_SMALL, 11));
+
+        let mut out_len = 0;
+        let code = unsafe {
+            aoc2021_solve(
+                1,
+                1,
+                std::ptr::null(),
+                1,
+                std::ptr::null_mut(),
+                &mut out_len,
+            )
+        };
+        assert_eq!(code, AOC2021_NULL_POINTER);
+
+        let bad_utf8 = [0xff, 0xfe];
+        let mut out = [0; 64];
+        let mut out_len = out.len();
+        let code =
+            unsafe { aoc2021_solve(1, 1, bad_utf8.as_ptr(), 2, out.as_mut_ptr(), &mut out_len) };
+        assert_eq!(code, AOC2021_INVALID_UTF8);
+    }
+}
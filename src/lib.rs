@@ -1,3 +1,9 @@
-pub mod a_star;
-pub mod position;
-pub mod tracker;
+// Everything that doesn't depend on the year: the runner and registry, and
+// the helpers the solutions share.
+pub mod core;
+// The C interface, for the cdylib.
+pub mod ffi;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+// A year's solutions, along with the `DAYS` that registers them.
+pub mod y2021;
@@ -0,0 +1,33 @@
+// JavaScript bindings, for solving in the browser.  Build with
+//
+//     cargo build --lib --release --target wasm32-unknown-unknown --features wasm
+//     wasm-bindgen --target web --out-dir web/pkg \
+//         target/wasm32-unknown-unknown/release/aoc2021.wasm
+//
+// and serve web/ to try it out.
+use crate::core::registry;
+use crate::core::runner::Part;
+use std::panic::{self, AssertUnwindSafe};
+use wasm_bindgen::prelude::*;
+
+// The days `year` has solutions for.
+#[wasm_bindgen]
+pub fn days(year: u32) -> Vec<u32> {
+    registry::year(year)
+        .map(|year| year.days.iter().map(|day| day.number).collect())
+        .unwrap_or_default()
+}
+
+// Solves `input` for the given day and returns the answers as JSON, in the
+// same shape as the binaries' --json output.  A solver that panics throws
+// rather than taking the instance down with it, where panics unwind; built
+// with panic=abort the instance still traps.
+#[wasm_bindgen]
+pub fn solve(year: u32, day: u32, input: &str) -> Result<String, JsError> {
+    let day = registry::find(year, day)
+        .ok_or_else(|| JsError::new(&format!("no solution for {} day {}", year, day)))?;
+    let answers = panic::catch_unwind(AssertUnwindSafe(|| day.solve(input, Part::All)))
+        .map_err(|_| JsError::new("solver panicked"))?
+        .map_err(|err| JsError::new(&err.to_string()))?;
+    serde_json::to_string(&answers).map_err(|err| JsError::new(&err.to_string()))
+}
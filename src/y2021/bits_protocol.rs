@@ -0,0 +1,555 @@
+// The Buoyancy Interchange Transmission System: packets that are either a
+// literal value or an operator applied to the sub-packets inside them.
+//
+//     let packet = Packet::parse_hex("9C0141080250320F1802104A08")?;
+//     assert_eq!(packet.evaluate()?, 1);
+//     assert_eq!(packet.to_string().parse::<Packet>()?, packet);
+use crate::core::arena::{Arena, NodeId};
+use crate::core::bits::BitReader;
+use crate::core::error::{AocError, Result};
+use itertools::{EitherOrBoth, Itertools};
+use std::fmt::Display;
+use std::iter;
+use std::str::FromStr;
+
+// Operators hold their first sub-packet, with the rest following on from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Payload {
+    Literal(u64),
+    Sum(Option<NodeId>),
+    Product(Option<NodeId>),
+    Minimum(Option<NodeId>),
+    Maximum(Option<NodeId>),
+    GreaterThan(Option<NodeId>),
+    LessThan(Option<NodeId>),
+    EqualTo(Option<NodeId>),
+}
+
+impl Payload {
+    fn type_id(&self) -> u8 {
+        use Payload::*;
+        match self {
+            Sum(_) => 0,
+            Product(_) => 1,
+            Minimum(_) => 2,
+            Maximum(_) => 3,
+            Literal(_) => 4,
+            GreaterThan(_) => 5,
+            LessThan(_) => 6,
+            EqualTo(_) => 7,
+        }
+    }
+
+    fn first_sub_packet(&self) -> Option<NodeId> {
+        use Payload::*;
+        match self {
+            Literal(_) => None,
+            Sum(first) | Product(first) | Minimum(first) | Maximum(first) | GreaterThan(first)
+            | LessThan(first) | EqualTo(first) => *first,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Node {
+    version: u8,
+    payload: Payload,
+    // The next sub-packet of the same operator.
+    next: Option<NodeId>,
+}
+
+// The outermost packet, along with all the packets inside it.
+#[derive(Debug)]
+pub struct Packet {
+    nodes: Arena<Node>,
+    root: NodeId,
+}
+
+impl Packet {
+    // Reads a transmission given as hex, as the puzzle gives it, ignoring
+    // whitespace around it.
+    pub fn parse_hex(hex: &str) -> Result<Packet> {
+        let mut hex = hex.trim().to_string();
+        if hex.len() % 2 == 1 {
+            hex.push('0');
+        }
+
+        Packet::parse_bits(&hex::decode(&hex).map_err(AocError::parse)?)
+    }
+
+    // Reads the packet from the start of `data`, most significant bit first.
+    // Anything after it is ignored.
+    pub fn parse_bits(data: &[u8]) -> Result<Packet> {
+        let mut reader = BitReader::new(data);
+        let mut nodes = Arena::new();
+        let root = read_packet(&mut reader, &mut nodes)?;
+        Ok(Packet { nodes, root })
+    }
+
+    fn sub_packets(&self, first: Option<NodeId>) -> impl Iterator<Item = NodeId> + '_ {
+        iter::successors(first, move |&id| self.nodes[id].next)
+    }
+
+    fn values(&self, first: Option<NodeId>) -> Result<Vec<u64>> {
+        self.sub_packets(first)
+            .map(|id| self.evaluate_at(id))
+            .collect()
+    }
+
+    fn operands(&self, first: Option<NodeId>) -> Result<(u64, u64)> {
+        match self.values(first)?[..] {
+            [left, right] => Ok((left, right)),
+            ref values => Err(AocError::Evaluate(format!(
+                "comparing {} values",
+                values.len()
+            ))),
+        }
+    }
+
+    fn evaluate_at(&self, id: NodeId) -> Result<u64> {
+        let overflow = || AocError::Evaluate("value doesn't fit in 64 bits".to_string());
+        let empty = || AocError::Evaluate("no values to compare".to_string());
+
+        use Payload::*;
+        Ok(match self.nodes[id].payload {
+            Literal(val) => val,
+            Sum(first) => self
+                .values(first)?
+                .into_iter()
+                .try_fold(0_u64, u64::checked_add)
+                .ok_or_else(overflow)?,
+            Product(first) => self
+                .values(first)?
+                .into_iter()
+                .try_fold(1_u64, u64::checked_mul)
+                .ok_or_else(overflow)?,
+            Minimum(first) => self.values(first)?.into_iter().min().ok_or_else(empty)?,
+            Maximum(first) => self.values(first)?.into_iter().max().ok_or_else(empty)?,
+            GreaterThan(first) => {
+                let (left, right) = self.operands(first)?;
+                if left > right {
+                    1
+                } else {
+                    0
+                }
+            }
+            LessThan(first) => {
+                let (left, right) = self.operands(first)?;
+                if left < right {
+                    1
+                } else {
+                    0
+                }
+            }
+            EqualTo(first) => {
+                let (left, right) = self.operands(first)?;
+                if left == right {
+                    1
+                } else {
+                    0
+                }
+            }
+        })
+    }
+
+    // The value of the expression the packet encodes.  Fails if any of it
+    // overflows a `u64`.
+    pub fn evaluate(&self) -> Result<u64> {
+        self.evaluate_at(self.root)
+    }
+
+    fn total_version_at(&self, id: NodeId) -> usize {
+        let node = &self.nodes[id];
+        node.version as usize
+            + self
+                .sub_packets(node.payload.first_sub_packet())
+                .map(|id| self.total_version_at(id))
+                .sum::<usize>()
+    }
+
+    // The sum of the version numbers of this packet and every one inside it.
+    pub fn total_version(&self) -> usize {
+        self.total_version_at(self.root)
+    }
+
+    fn same_as(&self, id: NodeId, other: &Packet, other_id: NodeId) -> bool {
+        let (node, other_node) = (&self.nodes[id], &other.nodes[other_id]);
+        if node.version != other_node.version
+            || node.payload.type_id() != other_node.payload.type_id()
+        {
+            return false;
+        }
+
+        match (node.payload, other_node.payload) {
+            (Payload::Literal(a), Payload::Literal(b)) => a == b,
+            _ => self
+                .sub_packets(node.payload.first_sub_packet())
+                .zip_longest(other.sub_packets(other_node.payload.first_sub_packet()))
+                .all(|pair| match pair {
+                    EitherOrBoth::Both(a, b) => self.same_as(a, other, b),
+                    _ => false,
+                }),
+        }
+    }
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.same_as(self.root, other, other.root)
+    }
+}
+
+impl Eq for Packet {}
+
+// Builds up an operator's sub-packets, linking each to the one before.
+#[derive(Default)]
+struct SubPackets {
+    first: Option<NodeId>,
+    last: Option<NodeId>,
+}
+
+impl SubPackets {
+    fn push(&mut self, nodes: &mut Arena<Node>, id: NodeId) {
+        match self.last {
+            Some(last) => nodes[last].next = Some(id),
+            None => self.first = Some(id),
+        }
+        self.last = Some(id);
+    }
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bits: Vec<bool>,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u64, width: usize) {
+        self.bits
+            .extend((0..width).rev().map(|bit| (value >> bit) & 1 == 1));
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        self.bits
+            .chunks(8)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0, |byte, (index, &bit)| byte | (bit as u8) << (7 - index))
+            })
+            .collect()
+    }
+}
+
+fn write_literal_payload(writer: &mut BitWriter, value: u64) {
+    let groups = ((64 - value.leading_zeros() as usize).div_ceil(4)).max(1);
+    for group in (0..groups).rev() {
+        let more = if group > 0 { 0x10 } else { 0 };
+        writer.write(more | (value >> (group * 4)) & 0xF, 5);
+    }
+}
+
+// Sub-packets are given by length in bits where that fits, else by count.
+fn write_sub_packets(writer: &mut BitWriter, packet: &Packet, first: Option<NodeId>) {
+    let mut sub_writer = BitWriter::default();
+    for id in packet.sub_packets(first) {
+        write_packet(&mut sub_writer, packet, id);
+    }
+
+    if sub_writer.bits.len() < 1 << 15 {
+        writer.write(0, 1);
+        writer.write(sub_writer.bits.len() as u64, 15);
+    } else {
+        writer.write(1, 1);
+        writer.write(packet.sub_packets(first).count() as u64, 11);
+    }
+    writer.bits.extend(sub_writer.bits);
+}
+
+fn write_packet(writer: &mut BitWriter, packet: &Packet, id: NodeId) {
+    let node = &packet.nodes[id];
+    writer.write(node.version as u64, 3);
+    writer.write(node.payload.type_id() as u64, 3);
+    match node.payload {
+        Payload::Literal(value) => write_literal_payload(writer, value),
+        payload => write_sub_packets(writer, packet, payload.first_sub_packet()),
+    }
+}
+
+// The transmission as hex, the same way the puzzle gives it.
+impl Display for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut writer = BitWriter::default();
+        write_packet(&mut writer, self, self.root);
+        write!(f, "{}", hex::encode_upper(writer.into_bytes()))
+    }
+}
+
+impl FromStr for Packet {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Packet::parse_hex(s)
+    }
+}
+
+fn read_literal_payload(reader: &mut BitReader) -> Result<Payload> {
+    let mut value = 0_u64;
+
+    loop {
+        let next = reader.read_bits(5)?;
+        if value >> 60 != 0 {
+            return Err(AocError::parse("literal doesn't fit in 64 bits"));
+        }
+        value <<= 4;
+        value |= next & 0xF;
+
+        if next & 0x10 == 0 {
+            break;
+        }
+    }
+
+    Ok(Payload::Literal(value))
+}
+
+fn read_defined_length_packets(
+    reader: &mut BitReader,
+    nodes: &mut Arena<Node>,
+) -> Result<Option<NodeId>> {
+    let length = reader.read_bits(15)?;
+    let mut sub_reader = reader.sub_reader(length)?;
+
+    let mut packets = SubPackets::default();
+    while !sub_reader.is_empty() {
+        let packet = read_packet(&mut sub_reader, nodes)?;
+        packets.push(nodes, packet);
+    }
+
+    Ok(packets.first)
+}
+
+fn read_defined_num_packets(
+    reader: &mut BitReader,
+    nodes: &mut Arena<Node>,
+) -> Result<Option<NodeId>> {
+    let num_packets = reader.read_bits(11)?;
+
+    let mut packets = SubPackets::default();
+    for _ in 0..num_packets {
+        let packet = read_packet(reader, nodes)?;
+        packets.push(nodes, packet);
+    }
+
+    Ok(packets.first)
+}
+
+fn read_operator_payload<F>(
+    reader: &mut BitReader,
+    nodes: &mut Arena<Node>,
+    cons: F,
+) -> Result<Payload>
+where
+    F: Fn(Option<NodeId>) -> Payload,
+{
+    let first = read_sub_packets(reader, nodes)?;
+    Ok(cons(first))
+}
+
+fn read_sub_packets(reader: &mut BitReader, nodes: &mut Arena<Node>) -> Result<Option<NodeId>> {
+    if reader.read_bit()? {
+        read_defined_num_packets(reader, nodes)
+    } else {
+        read_defined_length_packets(reader, nodes)
+    }
+}
+
+// Comparisons need exactly two values and the minimum and maximum at least
+// one, so check for them up front rather than failing to evaluate.
+fn check_operands(payload: &Payload, nodes: &Arena<Node>) -> Result<()> {
+    let count = iter::successors(payload.first_sub_packet(), |&id| nodes[id].next).count();
+
+    use Payload::*;
+    let valid = match payload {
+        Literal(_) | Sum(_) | Product(_) => true,
+        Minimum(_) | Maximum(_) => count > 0,
+        GreaterThan(_) | LessThan(_) | EqualTo(_) => count == 2,
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(AocError::parse(format!(
+            "type {} packet with {} sub-packets",
+            payload.type_id(),
+            count
+        )))
+    }
+}
+
+fn read_packet(reader: &mut BitReader, nodes: &mut Arena<Node>) -> Result<NodeId> {
+    let version = reader.read_bits(3)? as u8;
+    let type_id = reader.read_bits(3)?;
+
+    use Payload::*;
+    let payload = match type_id {
+        0 => read_operator_payload(reader, nodes, Sum),
+        1 => read_operator_payload(reader, nodes, Product),
+        2 => read_operator_payload(reader, nodes, Minimum),
+        3 => read_operator_payload(reader, nodes, Maximum),
+        4 => read_literal_payload(reader),
+        5 => read_operator_payload(reader, nodes, GreaterThan),
+        6 => read_operator_payload(reader, nodes, LessThan),
+        7 => read_operator_payload(reader, nodes, EqualTo),
+        _ => Err(AocError::parse(format!("Unknown type ID {}", type_id))),
+    }?;
+    check_operands(&payload, nodes)?;
+
+    Ok(nodes.alloc(Node {
+        version,
+        payload,
+        next: None,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[derive(Debug, Clone)]
+    enum Tree {
+        Literal(u8, u64),
+        Operator(u8, u8, Vec<Tree>),
+    }
+
+    fn build(nodes: &mut Arena<Node>, tree: &Tree) -> NodeId {
+        use Payload::*;
+        let (version, payload) = match tree {
+            Tree::Literal(version, value) => (*version, Literal(*value)),
+            Tree::Operator(version, operator, trees) => {
+                let mut packets = SubPackets::default();
+                for tree in trees {
+                    let packet = build(nodes, tree);
+                    packets.push(nodes, packet);
+                }
+                let first = packets.first;
+                let payload = match operator {
+                    0 => Sum(first),
+                    1 => Product(first),
+                    2 => Minimum(first),
+                    3 => Maximum(first),
+                    4 => GreaterThan(first),
+                    5 => LessThan(first),
+                    _ => EqualTo(first),
+                };
+                (*version, payload)
+            }
+        };
+        nodes.alloc(Node {
+            version,
+            payload,
+            next: None,
+        })
+    }
+
+    fn packet() -> impl Strategy<Value = Packet> {
+        let literal =
+            (0..8_u8, any::<u64>()).prop_map(|(version, value)| Tree::Literal(version, value));
+        let tree = literal.prop_recursive(4, 32, 4, |inner| {
+            (0..8_u8, 0..7_u8, prop::collection::vec(inner, 1..4)).prop_map(
+                |(version, operator, mut trees)| {
+                    // Comparisons take exactly two.
+                    if operator >= 4 {
+                        trees.push(trees[0].clone());
+                        trees.truncate(2);
+                    }
+                    Tree::Operator(version, operator, trees)
+                },
+            )
+        });
+        tree.prop_map(|tree| {
+            let mut nodes = Arena::new();
+            let root = build(&mut nodes, &tree);
+            Packet { nodes, root }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(packet in packet()) {
+            let encoded = packet.to_string();
+            prop_assert_eq!(encoded.parse::<Packet>().unwrap(), packet);
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        // A literal is written the same way the puzzle writes it.
+        assert_eq!("D2FE28".parse::<Packet>().unwrap().to_string(), "D2FE28");
+
+        for data in ["8A004A801A8002F478", "C0015000016115A2E0802F182340"] {
+            let packet = data.parse::<Packet>().unwrap();
+            assert_eq!(packet.to_string().parse::<Packet>().unwrap(), packet);
+        }
+    }
+
+    #[test]
+    fn test_total_version() {
+        for (data, total_version) in [
+            ("8A004A801A8002F478", 16),
+            ("620080001611562C8802118E34", 12),
+            ("C0015000016115A2E0802F182340", 23),
+            ("A0016C880162017C3686B18A3D4780", 31),
+        ] {
+            let packet = Packet::parse_hex(data).unwrap();
+            assert_eq!(packet.total_version(), total_version);
+        }
+    }
+
+    #[test]
+    fn test_evaluate() {
+        for (data, value) in [
+            ("C200B40A82", 3),
+            ("04005AC33890", 54),
+            ("880086C3E88112", 7),
+            ("CE00C43D881120", 9),
+            ("D8005AC2A8F0", 1),
+            ("F600BC2D8F", 0),
+            ("9C005AC2F8F0", 0),
+            ("9C0141080250320F1802104A08", 1),
+        ] {
+            let packet = Packet::parse_hex(data).unwrap();
+            assert_eq!(packet.evaluate().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_parse_bits() {
+        let data = hex::decode("D2FE28").unwrap();
+        let packet = Packet::parse_bits(&data).unwrap();
+        assert_eq!(packet.evaluate().unwrap(), 2021);
+        assert!(Packet::parse_bits(&data[..2]).is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(Packet::parse_hex("not hex").is_err());
+
+        // A sum of two literals that doesn't fit.
+        let mut nodes = Arena::new();
+        let max = Tree::Literal(0, u64::MAX);
+        let root = build(&mut nodes, &Tree::Operator(0, 0, vec![max.clone(), max]));
+        let packet = Packet { nodes, root };
+        assert!(matches!(packet.evaluate(), Err(AocError::Evaluate(_))));
+        let encoded = packet.to_string();
+        assert!(Packet::parse_hex(&encoded).unwrap().evaluate().is_err());
+
+        // A less than packet with one literal inside.
+        let one = Tree::Literal(0, 1);
+        let mut nodes = Arena::new();
+        let root = build(&mut nodes, &Tree::Operator(0, 5, vec![one]));
+        let encoded = Packet { nodes, root }.to_string();
+        assert!(Packet::parse_hex(&encoded).is_err());
+    }
+}
@@ -0,0 +1,318 @@
+use crate::core::answer::Answer;
+use crate::core::error::{parse_value, Result};
+use crate::core::runner::{self, Solution};
+use crate::core::windows::WindowsExt;
+use itertools::Itertools;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fmt::{self, Display};
+use std::io::BufRead;
+use std::str::FromStr;
+
+fn read_depths(reader: impl BufRead) -> Result<Box<[u64]>> {
+    reader.lines().map(|line| parse_value(&line?)).collect()
+}
+
+// How many times the sum of `window` consecutive depths is bigger than the
+// sum of the ones a step before.
+pub fn count_increases(depths: &[u64], window: usize) -> usize {
+    depths
+        .iter()
+        .copied()
+        .window_sums(window)
+        .tuple_windows()
+        .filter(|(before, after)| after > before)
+        .count()
+}
+
+// A window whose sum is bigger than the one before's, and by how much.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Increase {
+    pub index: usize,
+    pub delta: u64,
+}
+
+// Every increase for one window width, with some statistics about them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub window: usize,
+    pub count: usize,
+    // The most increases in a row.
+    pub longest_run: usize,
+    pub biggest_jump: Option<Increase>,
+    pub increases: Vec<Increase>,
+}
+
+// The increases that `count_increases` counts, indexed by where the window
+// they're into starts.
+pub fn report(depths: &[u64], window: usize) -> Report {
+    let increases = depths
+        .iter()
+        .copied()
+        .window_sums(window)
+        .tuple_windows()
+        .enumerate()
+        .filter(|(_, (before, after))| after > before)
+        .map(|(index, (before, after))| Increase {
+            index: index + 1,
+            delta: after - before,
+        })
+        .collect::<Vec<_>>();
+
+    let longest_run = increases
+        .iter()
+        .map(|increase| increase.index)
+        .enumerate()
+        .group_by(|(position, index)| index - position)
+        .into_iter()
+        .map(|(_, run)| run.count())
+        .max()
+        .unwrap_or(0);
+
+    Report {
+        window,
+        count: increases.len(),
+        longest_run,
+        // The first of the biggest, if there are several.
+        biggest_jump: increases
+            .iter()
+            .copied()
+            .rev()
+            .max_by_key(|increase| increase.delta),
+        increases,
+    }
+}
+
+// A summary line followed by each increase, for reading rather than for
+// other programs.
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "window {}: {} increases, longest run {}",
+            self.window, self.count, self.longest_run
+        )?;
+        if let Some(jump) = self.biggest_jump {
+            write!(f, ", biggest jump +{} at {}", jump.delta, jump.index)?;
+        }
+        for increase in &self.increases {
+            write!(f, "\n  {} +{}", increase.index, increase.delta)?;
+        }
+        Ok(())
+    }
+}
+
+// Like `count_increases` for each of the `windows`, but reading the depths a
+// line at a time and only keeping as many as the widest window, so the input
+// can be bigger than memory.
+pub fn stream_increases(reader: impl BufRead, windows: &[usize]) -> Result<Vec<usize>> {
+    let widest = windows.iter().copied().max().unwrap_or(0);
+    let mut recent = VecDeque::with_capacity(widest + 1);
+    let mut counts = vec![0; windows.len()];
+
+    for line in reader.lines() {
+        let depth: u64 = parse_value(&line?)?;
+        for (count, &window) in counts.iter_mut().zip(windows) {
+            if recent.len() >= window && depth > recent[recent.len() - window] {
+                *count += 1;
+            }
+        }
+
+        recent.push_back(depth);
+        if recent.len() > widest {
+            recent.pop_front();
+        }
+    }
+
+    Ok(counts)
+}
+
+// How to replace each depth with a typical value of those around it, to
+// smooth out noise before counting increases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Smoothing {
+    Median,
+    Mean,
+}
+
+impl FromStr for Smoothing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "median" => Ok(Smoothing::Median),
+            "mean" => Ok(Smoothing::Mean),
+            _ => Err(format!(
+                "Invalid smoothing {:?}, expected median or mean",
+                s
+            )),
+        }
+    }
+}
+
+// Replaces each depth with the median or mean of those up to `radius` either
+// side of it, including itself.  There are fewer to go on at the ends.  Means
+// are rounded to the nearest whole depth, and medians of an even number of
+// depths take the lower of the middle two.
+pub fn smooth(depths: &[u64], smoothing: Smoothing, radius: usize) -> Vec<u64> {
+    (0..depths.len())
+        .map(|index| {
+            let around =
+                &depths[index.saturating_sub(radius)..(index + radius + 1).min(depths.len())];
+            match smoothing {
+                Smoothing::Median => {
+                    let mut around = around.to_vec();
+                    let middle = (around.len() - 1) / 2;
+                    *around.select_nth_unstable(middle).1
+                }
+                Smoothing::Mean => {
+                    let count = around.len() as u64;
+                    (around.iter().sum::<u64>() + count / 2) / count
+                }
+            }
+        })
+        .collect()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 1;
+    type Input = Box<[u64]>;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        read_depths(reader)
+    }
+
+    fn part1(depths: &Self::Input) -> Result<Answer> {
+        Ok(count_increases(depths, 1).into())
+    }
+
+    fn part2(depths: &Self::Input) -> Result<Answer> {
+        Ok(count_increases(depths, 3).into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day01>(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+        assert_eq!(solve(input).unwrap(), (7_usize.into(), 5_usize.into()));
+    }
+
+    #[test]
+    fn test_window_sizes() {
+        let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        let sums = |width: usize| {
+            depths
+                .windows(width)
+                .map(|w| w.iter().sum())
+                .collect::<Vec<u64>>()
+        };
+        for window in 1..=depths.len() {
+            assert_eq!(
+                count_increases(&depths, window),
+                count_increases(&sums(window), 1),
+                "window {}",
+                window
+            );
+        }
+        assert_eq!(count_increases(&depths, 2), 5);
+    }
+
+    #[test]
+    fn test_report() {
+        let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        let report1 = report(&depths, 1);
+        assert_eq!(report1.count, count_increases(&depths, 1));
+        assert_eq!(
+            report1
+                .increases
+                .iter()
+                .map(|i| i.index)
+                .collect::<Vec<_>>(),
+            [1, 2, 3, 5, 6, 7, 9]
+        );
+        assert_eq!(report1.longest_run, 3);
+        assert_eq!(
+            report1.biggest_jump,
+            Some(Increase {
+                index: 6,
+                delta: 33
+            })
+        );
+
+        let report3 = report(&depths, 3);
+        assert_eq!(report3.count, 5);
+        assert_eq!(
+            report3.increases[0],
+            Increase {
+                index: 1,
+                delta: 11
+            }
+        );
+        assert_eq!(report3.longest_run, 4);
+
+        let flat = report(&[5, 5, 4], 1);
+        assert_eq!(
+            (flat.count, flat.longest_run, flat.biggest_jump),
+            (0, 0, None)
+        );
+        assert_eq!(flat.to_string(), "window 1: 0 increases, longest run 0");
+
+        assert_eq!(
+            report(&[1, 3, 2, 7], 1).to_string(),
+            "window 1: 2 increases, longest run 1, biggest jump +5 at 3\n  1 +2\n  3 +5"
+        );
+    }
+
+    #[test]
+    fn test_smooth() {
+        let depths = [100, 101, 180, 102, 103, 90, 104];
+        assert_eq!(
+            smooth(&depths, Smoothing::Median, 1),
+            [100, 101, 102, 103, 102, 103, 90]
+        );
+        assert_eq!(
+            smooth(&depths, Smoothing::Mean, 1),
+            [101, 127, 128, 128, 98, 99, 97]
+        );
+        assert_eq!(smooth(&depths, Smoothing::Median, 0), depths);
+        assert!(smooth(&[], Smoothing::Mean, 3).is_empty());
+
+        // Noise either side of a steady descent.
+        let depths = [200, 210, 195, 205, 190, 200, 185];
+        assert_eq!(count_increases(&depths, 1), 3);
+        assert_eq!(
+            count_increases(&smooth(&depths, Smoothing::Median, 2), 1),
+            0
+        );
+        assert_eq!("mean".parse(), Ok(Smoothing::Mean));
+        assert!("mode".parse::<Smoothing>().is_err());
+    }
+
+    #[test]
+    fn test_stream_increases() {
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+        let depths = read_depths(input.as_bytes()).unwrap();
+        let windows = [3, 1, 2, 10, 11];
+        let expected = windows
+            .iter()
+            .map(|&window| count_increases(&depths, window))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stream_increases(input.as_bytes(), &windows).unwrap(),
+            expected
+        );
+        assert_eq!(stream_increases("".as_bytes(), &[1]).unwrap(), [0]);
+        assert!(stream_increases("1\nx\n".as_bytes(), &[1]).is_err());
+    }
+}
@@ -0,0 +1,458 @@
+use crate::core::answer::Answer;
+use crate::core::error::{AocError, Result};
+use crate::core::runner::{self, Solution};
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+// Where the submarine is: how far along, how deep, and its aim.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Position {
+    pub x: isize,
+    pub y: isize,
+    pub aim: isize,
+    // Turned round, so going forward goes back.
+    reversed: bool,
+}
+
+impl Position {
+    pub fn new(x: isize, y: isize, aim: isize) -> Self {
+        Position {
+            x,
+            y,
+            aim,
+            reversed: false,
+        }
+    }
+}
+
+// Written as `x,y,aim`, for starting part way through a course.
+impl FromStr for Position {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split(',')
+            .map(|value| value.trim().parse())
+            .collect::<Result<Vec<isize>, _>>()
+            .map_err(|err| format!("Invalid position {:?}: {}", s, err))?;
+        match values[..] {
+            [x, y, aim] => Ok(Position::new(x, y, aim)),
+            _ => Err(format!("Invalid position {:?}, expected x,y,aim", s)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Command {
+    Forward(isize),
+    Down(isize),
+    Up(isize),
+    // Turn round.
+    Reverse,
+    // Come straight up to the surface, levelling off.
+    Surface,
+    Repeat(usize, Box<[Command]>),
+}
+
+// What follows a verb on its line.
+enum Verb {
+    Amount(fn(isize) -> Command),
+    Bare(fn() -> Command),
+}
+
+// Every verb a course can use.  Adding one means adding it here and saying
+// what it does in `execute_command`.
+const VERBS: &[(&str, Verb)] = &[
+    ("forward", Verb::Amount(Command::Forward)),
+    ("down", Verb::Amount(Command::Down)),
+    ("up", Verb::Amount(Command::Up)),
+    ("reverse", Verb::Bare(|| Command::Reverse)),
+    ("surface", Verb::Bare(|| Command::Surface)),
+];
+
+fn parse_amount<T: FromStr>(amount: &str) -> Result<T, String> {
+    amount
+        .parse()
+        .map_err(|_| format!("invalid amount {:?}", amount))
+}
+
+fn parse_command(line: &str) -> Result<Command, String> {
+    let parts: Vec<_> = line.split(' ').collect();
+    let verb = VERBS
+        .iter()
+        .find(|(name, _)| *name == parts[0])
+        .map(|(_, verb)| verb)
+        .ok_or_else(|| format!("unknown command {:?}", parts[0]))?;
+
+    match (verb, &parts[1..]) {
+        (Verb::Amount(cons), [amount]) => Ok(cons(parse_amount(amount)?)),
+        (Verb::Amount(_), _) => Err(format!("{} takes one amount", parts[0])),
+        (Verb::Bare(cons), []) => Ok(cons()),
+        (Verb::Bare(_), _) => Err(format!("{} doesn't take an amount", parts[0])),
+    }
+}
+
+// Where a line is in the input, for pointing at it in errors.
+#[derive(Debug, Clone, Copy)]
+struct Line<'a> {
+    number: usize,
+    column: usize,
+    offset: usize,
+    text: &'a str,
+}
+
+impl Line<'_> {
+    fn error(&self, input: &str, message: String) -> AocError {
+        AocError::ParseAt {
+            line: self.number,
+            column: self.column,
+            message: format!("{} in {:?}", message, self.text),
+            input: input.to_string(),
+            span: (self.offset, self.text.len()).into(),
+        }
+    }
+}
+
+// The commands, where `repeat N {` starts a block that runs N times up to the
+// matching `}`.  Blank lines and comments are ignored.  Each line that's
+// wrong is passed to `skip`, which either gives the error back to stop there
+// or lets it carry on without the line.
+fn read_course<F>(reader: impl BufRead, mut skip: F) -> Result<Box<[Command]>>
+where
+    F: FnMut(AocError) -> Result<()>,
+{
+    let mut input = String::new();
+    let mut reader = reader;
+    reader.read_to_string(&mut input)?;
+
+    // The commands in each block that's still open, with how many times to
+    // repeat it and the line it started on, outermost first.
+    let mut blocks = vec![(1, None, vec![])];
+
+    let mut offset = 0;
+    for (index, text) in input.split_inclusive('\n').enumerate() {
+        // Anything after a # is a comment.
+        let command = text.split('#').next().unwrap();
+        let indent = &command[..command.len() - command.trim_start().len()];
+        let line = Line {
+            number: index + 1,
+            column: indent.chars().count() + 1,
+            offset: offset + indent.len(),
+            text: command.trim(),
+        };
+        offset += text.len();
+        if line.text.is_empty() {
+            continue;
+        }
+
+        let parts: Vec<_> = line.text.split(' ').collect();
+        let result = match parts[..] {
+            ["repeat", times, "{"] => parse_amount(times).map(|times| {
+                blocks.push((times, Some(line), vec![]));
+            }),
+            ["}"] if blocks.len() > 1 => {
+                let (times, _, commands) = blocks.pop().unwrap();
+                let repeat = Command::Repeat(times, commands.into_boxed_slice());
+                blocks.last_mut().unwrap().2.push(repeat);
+                Ok(())
+            }
+            ["}"] => Err("} without a repeat".to_string()),
+            _ => parse_command(line.text).map(|command| blocks.last_mut().unwrap().2.push(command)),
+        };
+
+        if let Err(message) = result {
+            skip(line.error(&input, message))?;
+        }
+    }
+
+    // Blocks left open run to the end.
+    while blocks.len() > 1 {
+        let (times, start, commands) = blocks.pop().unwrap();
+        let start = start.unwrap();
+        skip(start.error(&input, "repeat without a }".to_string()))?;
+        let repeat = Command::Repeat(times, commands.into_boxed_slice());
+        blocks.last_mut().unwrap().2.push(repeat);
+    }
+
+    Ok(blocks.pop().unwrap().2.into_boxed_slice())
+}
+
+pub fn read_commands(reader: impl BufRead) -> Result<Box<[Command]>> {
+    read_course(reader, Err)
+}
+
+// Like `read_commands`, but leaving out any lines that are wrong, and giving
+// back what was wrong with each.
+pub fn read_commands_lenient(reader: impl BufRead) -> Result<(Box<[Command]>, Vec<AocError>)> {
+    let mut skipped = vec![];
+    let commands = read_course(reader, |err| {
+        skipped.push(err);
+        Ok(())
+    })?;
+    Ok((commands, skipped))
+}
+
+// How the submarine reads the commands: part 1 thinks down and up change
+// the depth, part 2 that they change the aim.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Simple,
+    Aim,
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "simple" => Ok(Mode::Simple),
+            "aim" => Ok(Mode::Aim),
+            _ => Err(format!("Invalid mode {:?}, expected simple or aim", s)),
+        }
+    }
+}
+
+// Moves the submarine as `command` says, calling `visit` with where it is
+// after each command that isn't a repeat.
+fn execute_command<F>(command: &Command, position: &mut Position, mode: Mode, visit: &mut F)
+where
+    F: FnMut(&Position),
+{
+    use Command::*;
+    match (command, mode) {
+        (Repeat(times, commands), _) => {
+            for _ in 0..*times {
+                for command in commands.iter() {
+                    execute_command(command, position, mode, visit);
+                }
+            }
+            return;
+        }
+        (Reverse, _) => position.reversed = !position.reversed,
+        (Surface, _) => {
+            position.y = 0;
+            position.aim = 0;
+        }
+        (Forward(x), _) => {
+            let x = if position.reversed { -x } else { *x };
+            position.x += x;
+            if mode == Mode::Aim {
+                position.y += x * position.aim;
+            }
+        }
+        (Down(x), Mode::Simple) => position.y += x,
+        (Up(x), Mode::Simple) => position.y -= x,
+        (Down(x), Mode::Aim) => position.aim += x,
+        (Up(x), Mode::Aim) => position.aim -= x,
+    }
+    visit(position);
+}
+
+fn execute_commands_visiting<F>(
+    commands: &[Command],
+    mode: Mode,
+    start: Position,
+    mut visit: F,
+) -> Position
+where
+    F: FnMut(&Position),
+{
+    let mut position = start;
+    visit(&position);
+
+    for command in commands {
+        execute_command(command, &mut position, mode, &mut visit);
+    }
+
+    position
+}
+
+fn execute_commands(commands: &[Command], mode: Mode) -> Position {
+    execute_from(commands, mode, Position::default())
+}
+
+// Where the submarine ends up if it follows the commands from `start`.
+pub fn execute_from(commands: &[Command], mode: Mode, start: Position) -> Position {
+    execute_commands_visiting(commands, mode, start, |_| {})
+}
+
+// Writes where the submarine is at the start and after every command as CSV,
+// for plotting the dive.
+pub fn write_trace(
+    commands: &[Command],
+    mode: Mode,
+    start: Position,
+    mut writer: impl Write,
+) -> Result<()> {
+    writeln!(writer, "step,position,depth,aim")?;
+    let mut step = 0;
+    let mut result = Ok(());
+    execute_commands_visiting(commands, mode, start, |position| {
+        if result.is_ok() {
+            result = writeln!(
+                writer,
+                "{},{},{},{}",
+                step, position.x, position.y, position.aim
+            );
+        }
+        step += 1;
+    });
+    Ok(result?)
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 2;
+    type Input = Box<[Command]>;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        read_commands(reader)
+    }
+
+    fn part1(commands: &Self::Input) -> Result<Answer> {
+        let end_pos = execute_commands(commands, Mode::Simple);
+        Ok((end_pos.x * end_pos.y).into())
+    }
+
+    fn part2(commands: &Self::Input) -> Result<Answer> {
+        let end_pos = execute_commands(commands, Mode::Aim);
+        Ok((end_pos.x * end_pos.y).into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day02>(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn run(course: &str, mode: Mode) -> (isize, isize) {
+        let commands = read_commands(course.as_bytes()).unwrap();
+        let position = execute_commands(&commands, mode);
+        (position.x, position.y)
+    }
+
+    #[test]
+    fn test_repeat() {
+        let course = "repeat 3 {\nforward 2\nrepeat 2 {\ndown 1\n}\n}\nup 1\n";
+        assert_eq!(run(course, Mode::Simple), (6, 5));
+        assert_eq!(run(course, Mode::Aim), (6, 2 * 2 + 2 * 4));
+        assert_eq!(run("repeat 0 {\nforward 5\n}\n", Mode::Aim), (0, 0));
+    }
+
+    #[test]
+    fn test_reverse_and_surface() {
+        let course = "down 2\nforward 5\nreverse\nforward 2\nsurface\nreverse\nforward 1\n";
+        assert_eq!(run(course, Mode::Simple), (4, 0));
+        assert_eq!(run(course, Mode::Aim), (4, 0));
+        assert_eq!(run("down 2\nreverse\nforward 3\n", Mode::Aim), (-3, -6));
+    }
+
+    #[test]
+    fn test_write_trace() {
+        let commands =
+            read_commands("forward 5\nrepeat 2 {\ndown 5\n}\nforward 8\n".as_bytes()).unwrap();
+        let mut trace = vec![];
+        write_trace(&commands, Mode::Aim, Position::default(), &mut trace).unwrap();
+        assert_eq!(
+            String::from_utf8(trace).unwrap(),
+            "step,position,depth,aim\n0,0,0,0\n1,5,0,0\n2,5,0,5\n3,5,0,10\n4,13,80,10\n"
+        );
+    }
+
+    #[test]
+    fn test_comments() {
+        let course = "# Out to the reef.\n\nforward 5  # full speed\n   \n  down 2\n#forward 100\n";
+        assert_eq!(run(course, Mode::Simple), (5, 2));
+
+        let error = read_commands("forward 5\n\n  up z # why\n".as_bytes()).unwrap_err();
+        match error {
+            AocError::ParseAt {
+                line,
+                column,
+                input,
+                span,
+                ..
+            } => {
+                assert_eq!((line, column), (3, 3));
+                assert_eq!(&input[span.offset()..span.offset() + span.len()], "up z");
+            }
+            error => panic!("unexpected {:?}", error),
+        }
+    }
+
+    #[test]
+    fn test_start() {
+        let start = "10, 4,2".parse::<Position>().unwrap();
+        assert_eq!(start, Position::new(10, 4, 2));
+        assert!("1,2".parse::<Position>().is_err());
+        assert!("1,2,x".parse::<Position>().is_err());
+
+        // Splitting a course in two and carrying on from where the first half
+        // left off gets to the same place.
+        let first = read_commands("forward 5\ndown 5\nforward 8\n".as_bytes()).unwrap();
+        let second = read_commands("up 3\ndown 8\nforward 2\n".as_bytes()).unwrap();
+        let middle = execute_from(&first, Mode::Aim, Position::default());
+        assert_eq!(
+            execute_from(&second, Mode::Aim, middle),
+            Position::new(15, 60, 10)
+        );
+    }
+
+    #[test]
+    fn test_invalid_courses() {
+        for course in [
+            "forward\n",
+            "reverse 2\n",
+            "sideways 3\n",
+            "repeat 2 {\nforward 1\n",
+            "}\n",
+            "repeat x {\n}\n",
+        ] {
+            assert!(read_commands(course.as_bytes()).is_err(), "{:?}", course);
+        }
+    }
+
+    #[test]
+    fn test_error_line() {
+        let error = read_commands("forward 5\ndown 5\nforwrad 8\nup 3\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid input: line 3, column 1: unknown command \"forwrad\" in \"forwrad 8\""
+        );
+
+        let error = read_commands("repeat 2 {\r\nup x\r\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid input: line 2, column 1: invalid amount \"x\" in \"up x\""
+        );
+        match error {
+            AocError::ParseAt { input, span, .. } => {
+                assert_eq!(&input[span.offset()..span.offset() + span.len()], "up x");
+            }
+            error => panic!("unexpected {:?}", error),
+        }
+    }
+
+    #[test]
+    fn test_lenient() {
+        let course = "forward 5\nrepeat 2 {\nforward\ndown 1\n}\n}\nrepeat 3 {\nforward 1\n";
+        let (commands, skipped) = read_commands_lenient(course.as_bytes()).unwrap();
+        assert_eq!(
+            skipped
+                .iter()
+                .map(|err| match err {
+                    AocError::ParseAt { line, .. } => *line,
+                    _ => 0,
+                })
+                .collect::<Vec<_>>(),
+            [3, 6, 7]
+        );
+        let position = execute_commands(&commands, Mode::Simple);
+        assert_eq!((position.x, position.y), (8, 2));
+    }
+}
@@ -0,0 +1,297 @@
+use crate::core::answer::Answer;
+use crate::core::error::{AocError, Result};
+use crate::core::runner::{self, Solution};
+use std::io::BufRead;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+// The diagnostic report as a matrix of bits, one row per value.  Each row is
+// its value as little-endian words, so a row can be used as a number as it
+// stands and values can be as wide as they like.
+pub struct Report {
+    width: usize,
+    words: usize,
+    bits: Box<[u64]>,
+}
+
+impl Report {
+    fn len(&self) -> usize {
+        self.bits.len() / self.words
+    }
+
+    fn row(&self, index: usize) -> &[u64] {
+        &self.bits[index * self.words..(index + 1) * self.words]
+    }
+
+    // Columns count from the left, as the values are written, so column 0 is
+    // the most significant bit.
+    fn bit(&self, index: usize, column: usize) -> bool {
+        let position = self.width - 1 - column;
+        self.row(index)[position / WORD_BITS] >> (position % WORD_BITS) & 1 == 1
+    }
+
+    // A value with each column set where `set` says.
+    fn value_where(&self, set: impl Fn(usize) -> bool) -> Vec<u64> {
+        let mut value = vec![0; self.words];
+        for column in (0..self.width).filter(|&column| set(column)) {
+            let position = self.width - 1 - column;
+            value[position / WORD_BITS] |= 1 << (position % WORD_BITS);
+        }
+        value
+    }
+}
+
+fn read_report(reader: impl BufRead) -> Result<Report> {
+    let mut width = 0;
+    let mut words = 0;
+    let mut bits = vec![];
+
+    for line in reader.lines() {
+        let line = line?;
+        if width == 0 {
+            width = line.len();
+            words = width.div_ceil(WORD_BITS);
+        }
+        if width == 0 || line.len() != width || !line.bytes().all(|c| c == b'0' || c == b'1') {
+            return Err(AocError::parse(format!(
+                "\"{}\" is not a {}-bit binary number",
+                line, width
+            )));
+        }
+
+        let start = bits.len();
+        bits.resize(start + words, 0);
+        for (position, c) in line.bytes().rev().enumerate() {
+            if c == b'1' {
+                bits[start + position / WORD_BITS] |= 1 << (position % WORD_BITS);
+            }
+        }
+    }
+
+    if width == 0 {
+        return Err(AocError::parse("no diagnostic values"));
+    }
+
+    Ok(Report {
+        width,
+        words,
+        bits: bits.into_boxed_slice(),
+    })
+}
+
+// How many values have each column set.
+fn get_bit_counts(report: &Report) -> Box<[usize]> {
+    let mut counts = vec![0_usize; report.width];
+    for index in 0..report.len() {
+        for (word_index, &word) in report.row(index).iter().enumerate() {
+            let mut word = word;
+            while word != 0 {
+                let position = word_index * WORD_BITS + word.trailing_zeros() as usize;
+                counts[report.width - 1 - position] += 1;
+                word &= word - 1;
+            }
+        }
+    }
+    counts.into_boxed_slice()
+}
+
+// Multiplies two numbers held as little-endian words.
+fn multiply(x: &[u64], y: &[u64]) -> Vec<u64> {
+    let mut product = vec![0_u64; x.len() + y.len()];
+    for (i, &a) in x.iter().enumerate() {
+        let mut carry = 0_u128;
+        for (j, &b) in y.iter().enumerate() {
+            let total = a as u128 * b as u128 + product[i + j] as u128 + carry;
+            product[i + j] = total as u64;
+            carry = total >> WORD_BITS;
+        }
+        product[i + y.len()] = carry as u64;
+    }
+    product
+}
+
+// A number held as little-endian words as an answer, written out in decimal
+// if it's too big to be an `Answer::Number`.
+fn to_answer(value: &[u64]) -> Answer {
+    let mut value = value.to_vec();
+    while value.last() == Some(&0) {
+        value.pop();
+    }
+
+    match value[..] {
+        [] => 0_u64.into(),
+        [low] => low.into(),
+        [low, high] if high >> (WORD_BITS - 1) == 0 => {
+            Answer::Number(((high as i128) << WORD_BITS) | low as i128)
+        }
+        _ => {
+            // Nineteen digits at a time, least significant first.
+            const CHUNK: u64 = 10_000_000_000_000_000_000;
+            let mut chunks = vec![];
+            while !value.is_empty() {
+                let mut remainder = 0_u128;
+                for word in value.iter_mut().rev() {
+                    let current = (remainder << WORD_BITS) | *word as u128;
+                    *word = (current / CHUNK as u128) as u64;
+                    remainder = current % CHUNK as u128;
+                }
+                chunks.push(remainder as u64);
+                while value.last() == Some(&0) {
+                    value.pop();
+                }
+            }
+
+            let mut text = chunks.pop().unwrap().to_string();
+            for chunk in chunks.iter().rev() {
+                text.push_str(&format!("{:019}", chunk));
+            }
+            Answer::Text(text)
+        }
+    }
+}
+
+fn get_power_consumption(report: &Report) -> Answer {
+    let bit_counts = get_bit_counts(report);
+    let total = report.len();
+
+    let gamma = report.value_where(|column| bit_counts[column] * 2 >= total);
+    let epsilon = report.value_where(|column| bit_counts[column] * 2 < total);
+
+    to_answer(&multiply(&gamma, &epsilon))
+}
+
+// None if the criteria throw out every value, which happens when all the
+// values left share a bit and the criteria want the other one.
+fn get_rating<F>(report: &Report, take_set: F) -> Option<&[u64]>
+where
+    F: Fn(usize, usize) -> bool,
+{
+    let mut remaining: Vec<usize> = (0..report.len()).collect();
+
+    // Any left after the last column are all the same.
+    for column in 0..report.width {
+        if remaining.len() <= 1 {
+            break;
+        }
+
+        let set = remaining
+            .iter()
+            .filter(|&&index| report.bit(index, column))
+            .count();
+        let keep = take_set(set, remaining.len() - set);
+        remaining.retain(|&index| report.bit(index, column) == keep);
+    }
+
+    remaining.first().map(|&index| report.row(index))
+}
+
+fn get_oxygen_rating(report: &Report) -> Option<&[u64]> {
+    get_rating(report, |set, unset| set >= unset)
+}
+
+fn get_co2_rating(report: &Report) -> Option<&[u64]> {
+    get_rating(report, |set, unset| set < unset)
+}
+
+fn get_life_support_rating(report: &Report) -> Result<Answer> {
+    let no_match =
+        |rating| AocError::no_solution(2, format!("no value matches the {} criteria", rating));
+    let oxygen_generator_rating = get_oxygen_rating(report).ok_or_else(|| no_match("oxygen"))?;
+    let co2_scrubber_rating = get_co2_rating(report).ok_or_else(|| no_match("CO2"))?;
+
+    Ok(to_answer(&multiply(
+        oxygen_generator_rating,
+        co2_scrubber_rating,
+    )))
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 3;
+    type Input = Report;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        read_report(reader)
+    }
+
+    fn part1(report: &Self::Input) -> Result<Answer> {
+        Ok(get_power_consumption(report))
+    }
+
+    fn part2(report: &Self::Input) -> Result<Answer> {
+        get_life_support_rating(report)
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day03>(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: [&str; 12] = [
+        "00100", "11110", "10110", "10111", "10101", "01111", "00111", "11100", "10000", "11001",
+        "00010", "01010",
+    ];
+
+    #[test]
+    fn test_wide_values() {
+        // Each of the example's values written out 26 times over, 130 bits.
+        let input = EXAMPLE
+            .iter()
+            .map(|value| value.repeat(26) + "\n")
+            .collect::<String>();
+        let (part1, part2) = solve(&input).unwrap();
+        assert_eq!(
+            part1.to_string(),
+            "381716273365054846099763663379316641080770347712327305255853944598227756350022"
+        );
+        assert_eq!(
+            part2.to_string(),
+            "443407792292740477792654760491125391154430201888056970751749531604001939194470"
+        );
+    }
+
+    #[test]
+    fn test_to_answer() {
+        assert_eq!(to_answer(&[0, 0]), Answer::from(0));
+        assert_eq!(to_answer(&[5, 1]), Answer::Number((1 << 64) + 5));
+        assert_eq!(
+            to_answer(&multiply(&[1, 1], &[1, 1])),
+            Answer::from("340282366920938463500268095579187314689")
+        );
+        assert_eq!(
+            to_answer(&[0, 0, 1]).to_string(),
+            "340282366920938463463374607431768211456"
+        );
+    }
+
+    #[test]
+    fn test_invalid_reports() {
+        for input in ["", "\n", "101\n10\n", "101\n1x1\n"] {
+            assert!(read_report(input.as_bytes()).is_err(), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let (part1, part2) = solve("101\n101\n010\n").unwrap();
+        assert_eq!((part1, part2), (Answer::from(5 * 2), Answer::from(5 * 2)));
+    }
+
+    #[test]
+    fn test_shared_bit() {
+        // Both values start with a 1, so the CO2 criteria keep neither.
+        let error = solve(
+            "101
+110
+",
+        )
+        .unwrap_err();
+        assert!(matches!(error, AocError::NoSolution { part: 2, .. }));
+    }
+}
@@ -0,0 +1,160 @@
+use crate::core::answer::Answer;
+use crate::core::error::{AocError, Result};
+use crate::core::runner::{self, Solution};
+use std::collections::HashSet;
+use std::io::{self, BufRead};
+
+#[derive(Clone, Debug)]
+pub struct Card {
+    match_sets: Box<[HashSet<usize>]>,
+}
+
+impl Card {
+    fn new(grid: &[Box<[usize]>]) -> Self {
+        let rows = grid
+            .iter()
+            .map(|row| row.iter().cloned().collect::<HashSet<_>>());
+        let cols =
+            (0..grid[0].len()).map(|col| grid.iter().map(|row| row[col]).collect::<HashSet<_>>());
+        let match_sets = rows.chain(cols).collect::<Vec<_>>().into_boxed_slice();
+        Card { match_sets }
+    }
+
+    fn mark(&mut self, num: usize) {
+        for set in self.match_sets.iter_mut() {
+            set.remove(&num);
+        }
+    }
+
+    fn unmarked(&self) -> HashSet<usize> {
+        self.match_sets
+            .iter()
+            .fold(HashSet::new(), |current, next| {
+                current.union(next).cloned().collect()
+            })
+    }
+
+    fn has_won(&self) -> bool {
+        self.match_sets.iter().any(|set| set.is_empty())
+    }
+}
+
+type Numbers = Box<[usize]>;
+type Cards = Box<[Card]>;
+
+fn read_data(reader: impl BufRead) -> Result<(Numbers, Cards)> {
+    parsing::game(&io::read_to_string(reader)?)
+}
+
+fn find_winner<'a>(inputs: &'a [usize], cards: &mut [Card]) -> Option<(Card, usize, &'a [usize])> {
+    for (index, num) in inputs.iter().enumerate() {
+        for card in cards.iter_mut() {
+            card.mark(*num);
+        }
+
+        if let Some(card) = cards.iter().find(|card| card.has_won()) {
+            return Some((card.clone(), *num, &inputs[index + 1..]));
+        }
+    }
+    None
+}
+
+fn find_last_winner(inputs: &[usize], cards: &mut [Card]) -> Option<(Card, usize)> {
+    let mut still_to_win: Vec<&mut Card> =
+        cards.iter_mut().filter(|card| !card.has_won()).collect();
+
+    for num in inputs {
+        for card in still_to_win.iter_mut() {
+            card.mark(*num);
+        }
+
+        if still_to_win.iter().all(|card| card.has_won()) {
+            return Some((still_to_win[0].clone(), *num));
+        }
+
+        still_to_win.retain(|card| !card.has_won());
+    }
+    None
+}
+
+fn score(card: &Card, last_number: usize) -> usize {
+    let total: usize = card.unmarked().iter().sum();
+    total * last_number
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 4;
+    type Input = (Numbers, Cards);
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        read_data(reader)
+    }
+
+    fn part1((inputs, cards): &Self::Input) -> Result<Answer> {
+        let (winning_card, last_number, _) = find_winner(inputs, &mut cards.clone())
+            .ok_or_else(|| AocError::no_solution(1, "no card wins"))?;
+        Ok(score(&winning_card, last_number).into())
+    }
+
+    fn part2((inputs, cards): &Self::Input) -> Result<Answer> {
+        let (last_winning_card, last_number) = find_last_winner(inputs, &mut cards.clone())
+            .ok_or_else(|| AocError::no_solution(2, "not every card wins"))?;
+        Ok(score(&last_winning_card, last_number).into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day04>(input)
+}
+
+mod parsing {
+    use super::{Card, Cards, Numbers};
+    use crate::core::error::{AocError, Result};
+    use crate::core::parsing::{
+        blank_line, blank_line_separated_blocks, integer, lines_of, parse_all,
+    };
+    use nom::character::complete::{char, space0};
+    use nom::combinator::{map, map_res};
+    use nom::multi::{many1, separated_list1};
+    use nom::sequence::{preceded, separated_pair};
+    use nom::IResult;
+
+    fn numbers(input: &str) -> IResult<&str, Numbers> {
+        map(separated_list1(char(','), integer), Vec::into_boxed_slice)(input)
+    }
+
+    fn row(input: &str) -> IResult<&str, Box<[usize]>> {
+        map(many1(preceded(space0, integer)), Vec::into_boxed_slice)(input)
+    }
+
+    fn card(input: &str) -> IResult<&str, Card> {
+        map_res(lines_of(row), |grid: Vec<Box<[usize]>>| {
+            if grid.iter().any(|row| row.len() != grid[0].len()) {
+                return Err(AocError::parse("rows have different lengths"));
+            }
+            Ok(Card::new(&grid))
+        })(input)
+    }
+
+    fn cards(input: &str) -> IResult<&str, Cards> {
+        map(blank_line_separated_blocks(card), Vec::into_boxed_slice)(input)
+    }
+
+    pub(super) fn game(input: &str) -> Result<(Numbers, Cards)> {
+        parse_all(separated_pair(numbers, blank_line, cards), input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ragged_card() {
+        assert!(read_data("1,2\n\n1 2\n3 4\n".as_bytes()).is_ok());
+        assert!(read_data("1,2\n\n1 2\n3\n".as_bytes()).is_err());
+    }
+}
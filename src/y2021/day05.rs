@@ -0,0 +1,83 @@
+use crate::core::answer::Answer;
+use crate::core::error::Result;
+use crate::core::geometry::Line;
+use crate::core::position::Position;
+use crate::core::runner::{self, Solution};
+use std::collections::HashSet;
+use std::io::{self, BufRead};
+
+fn read_lines(reader: impl BufRead) -> Result<Box<[Line]>> {
+    parsing::lines(&io::read_to_string(reader)?)
+}
+
+fn count_overlaps(lines: &[Line]) -> usize {
+    let mut overlaps: HashSet<Position> = HashSet::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        for other in &lines[index + 1..] {
+            if let Some(overlap) = line.intersection(other) {
+                overlaps.extend(overlap.points());
+            }
+        }
+    }
+
+    overlaps.len()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 5;
+    type Input = Box<[Line]>;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        read_lines(reader)
+    }
+
+    fn part1(all_lines: &Self::Input) -> Result<Answer> {
+        let flat_lines = all_lines
+            .iter()
+            .filter(|line| line.is_horizontal() || line.is_vertical())
+            .cloned()
+            .collect::<Vec<_>>();
+        Ok(count_overlaps(&flat_lines).into())
+    }
+
+    fn part2(all_lines: &Self::Input) -> Result<Answer> {
+        Ok(count_overlaps(all_lines).into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day05>(input)
+}
+
+mod parsing {
+    use crate::core::error::Result;
+    use crate::core::geometry::Line;
+    use crate::core::parsing::{integer, lines_of, parse_all};
+    use crate::core::position::Position;
+
+    use nom::bytes::complete::tag;
+    use nom::combinator::map;
+    use nom::sequence::separated_pair;
+    use nom::IResult;
+
+    fn position(input: &str) -> IResult<&str, Position> {
+        map(separated_pair(integer, tag(","), integer), |(x, y)| {
+            Position::new(x, y)
+        })(input)
+    }
+
+    fn ends(input: &str) -> IResult<&str, (Position, Position)> {
+        separated_pair(position, tag(" -> "), position)(input)
+    }
+
+    pub(super) fn lines(input: &str) -> Result<Box<[Line]>> {
+        parse_all(lines_of(ends), input)?
+            .into_iter()
+            .map(|(start, end)| Line::try_new(start, end))
+            .collect()
+    }
+}
@@ -0,0 +1,65 @@
+use crate::core::answer::Answer;
+use crate::core::error::{parse_value, AocError, Result};
+use crate::core::linalg::Matrix;
+use crate::core::runner::{self, Solution};
+use std::io::{self, BufRead};
+
+type Fishes = [u128; 9];
+
+fn read_fish(reader: impl BufRead) -> Result<Fishes> {
+    let mut fishes = [0; 9];
+
+    let data = io::read_to_string(reader)?;
+    for num in data.trim_end().split(',') {
+        let timer = parse_value::<usize>(num)?;
+        *fishes
+            .get_mut(timer)
+            .ok_or_else(|| AocError::parse(format!("fish timer {} out of range", timer)))? += 1;
+    }
+
+    Ok(fishes)
+}
+
+// The change in each timer's count over one day: every fish's timer goes
+// down by one, and fish at zero go back to six and spawn a new fish at eight.
+fn step_matrix() -> Matrix<9> {
+    let mut step = Matrix::zero();
+    for timer in 0..8 {
+        step.0[timer][timer + 1] = 1;
+    }
+    step.0[6][0] = 1;
+    step.0[8][0] = 1;
+    step
+}
+
+fn count_fish(fishes: &Fishes) -> u128 {
+    fishes.iter().sum()
+}
+
+fn count_fish_after(fishes: &Fishes, days: u64) -> u128 {
+    count_fish(&step_matrix().pow(days).apply(fishes))
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 6;
+    type Input = Fishes;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        read_fish(reader)
+    }
+
+    fn part1(fishes: &Self::Input) -> Result<Answer> {
+        Ok(count_fish_after(fishes, 80).into())
+    }
+
+    fn part2(fishes: &Self::Input) -> Result<Answer> {
+        Ok(count_fish_after(fishes, 256).into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day06>(input)
+}
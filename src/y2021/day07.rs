@@ -0,0 +1,85 @@
+use crate::core::answer::Answer;
+use crate::core::counter::Counter;
+use crate::core::error::{parse_value, Result};
+use crate::core::parallel;
+use crate::core::runner::{self, Solution};
+use std::io::{self, BufRead};
+
+type Crabs = Counter<isize>;
+
+fn read_crabs(reader: impl BufRead) -> Result<Crabs> {
+    let data = io::read_to_string(reader)?;
+    data.trim_end()
+        .split(',')
+        .map(parse_value::<isize>)
+        .collect()
+}
+
+fn find_min_linear_fuel_to_align(crabs: &Crabs) -> isize {
+    let mut current_fuel: isize = crabs
+        .iter()
+        .map(|(position, count)| position * count as isize)
+        .sum();
+    let mut left_crabs = crabs.get(&0) as isize;
+    let mut right_crabs = crabs.total() as isize - left_crabs;
+    let mut position = 0;
+
+    while right_crabs > left_crabs {
+        current_fuel -= right_crabs - left_crabs;
+
+        position += 1;
+        let new_crabs = crabs.get(&position) as isize;
+        left_crabs += new_crabs;
+        right_crabs -= new_crabs;
+    }
+
+    current_fuel
+}
+
+fn find_min_quadratic_fuel_to_align(crabs: &Crabs) -> isize {
+    let min_pos = crabs.keys().min().cloned().unwrap();
+    let max_pos = crabs.keys().max().cloned().unwrap();
+
+    let fuel_to_move_all_crabs = |pos: isize| {
+        crabs
+            .iter()
+            .map(|(crab_pos, num_crabs)| num_crabs as isize * fuel_to_move_one_crab(pos, *crab_pos))
+            .sum::<isize>()
+    };
+
+    fn fuel_to_move_one_crab(pos: isize, crab_pos: isize) -> isize {
+        let distance = (crab_pos - pos).abs();
+        (distance * (distance + 1)) / 2
+    }
+
+    parallel::par_map_reduce(
+        min_pos..=max_pos,
+        fuel_to_move_all_crabs,
+        || isize::MAX,
+        isize::min,
+    )
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 7;
+    type Input = Crabs;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        read_crabs(reader)
+    }
+
+    fn part1(crabs: &Self::Input) -> Result<Answer> {
+        Ok(find_min_linear_fuel_to_align(crabs).into())
+    }
+
+    fn part2(crabs: &Self::Input) -> Result<Answer> {
+        Ok(find_min_quadratic_fuel_to_align(crabs).into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day07>(input)
+}
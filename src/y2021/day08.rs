@@ -0,0 +1,149 @@
+use crate::core::answer::Answer;
+use crate::core::error::{AocError, Result};
+use crate::core::runner::{self, Solution};
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::str::FromStr;
+
+type Signals = HashSet<char>;
+
+pub struct Problem {
+    distinct_digits: [Signals; 10],
+    output_digits: [Signals; 4],
+}
+
+fn parse_signals(sequence: &str) -> Vec<Signals> {
+    sequence
+        .split(' ')
+        .map(|digits| digits.chars().collect())
+        .collect()
+}
+
+impl FromStr for Problem {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = value.trim_end().split(" | ").collect();
+        if parts.len() != 2 {
+            return Err(format!("Invalid problem {}", value));
+        }
+
+        let distinct_digits = parse_signals(parts[0])
+            .try_into()
+            .map_err(|ds: Vec<Signals>| {
+                format!("Incorrect number of distinct digits: {} != 10", ds.len())
+            })?;
+        let output_digits = parse_signals(parts[1])
+            .try_into()
+            .map_err(|ds: Vec<Signals>| {
+                format!("Incorrect number of output digits: {} != 4", ds.len())
+            })?;
+
+        Ok(Problem {
+            distinct_digits,
+            output_digits,
+        })
+    }
+}
+
+fn read_problems(reader: impl BufRead) -> Result<Box<[Problem]>> {
+    reader
+        .lines()
+        .map(|line| line?.parse().map_err(AocError::Parse))
+        .collect()
+}
+
+fn find_digit<F>(digits: &mut Vec<Signals>, pred: F) -> Option<Signals>
+where
+    F: Fn(&Signals) -> bool,
+{
+    digits
+        .iter()
+        .position(pred)
+        .map(|index| digits.remove(index))
+}
+
+// Works out which signals make up which digit, or `None` if they don't
+// fit any wiring.
+fn find_digits(distinct_digits: &[Signals; 10]) -> Option<[Signals; 10]> {
+    let mut output: [Signals; 10] = Default::default();
+    let mut digits = distinct_digits.to_vec();
+
+    output[1] = find_digit(&mut digits, |sigs| sigs.len() == 2)?;
+    output[4] = find_digit(&mut digits, |sigs| sigs.len() == 4)?;
+    output[7] = find_digit(&mut digits, |sigs| sigs.len() == 3)?;
+    output[8] = find_digit(&mut digits, |sigs| sigs.len() == 7)?;
+
+    output[6] = find_digit(&mut digits, |sigs| {
+        sigs.len() == 6 && !sigs.is_superset(&output[1])
+    })?;
+    output[9] = find_digit(&mut digits, |sigs| {
+        sigs.len() == 6 && sigs.is_superset(&output[4])
+    })?;
+    output[0] = find_digit(&mut digits, |sigs| sigs.len() == 6)?;
+
+    // All remaining digits have 5 signals
+    output[3] = find_digit(&mut digits, |sigs| sigs.is_superset(&output[1]))?;
+    output[5] = find_digit(&mut digits, |sigs| {
+        sigs.intersection(&output[6]).count() == 5
+    })?;
+    output[2] = digits.pop()?;
+
+    Some(output)
+}
+
+fn decode_output(digits: &[Signals; 10], output: &[Signals; 4]) -> Option<[usize; 4]> {
+    let mut decoded = [0; 4];
+    for (digit, signals) in decoded.iter_mut().zip(output) {
+        *digit = digits.iter().position(|sigs| sigs == signals)?;
+    }
+    Some(decoded)
+}
+
+fn decode_outputs(problems: &[Problem], part: u32) -> Result<Vec<[usize; 4]>> {
+    problems
+        .iter()
+        .map(|problem| {
+            let digits = find_digits(&problem.distinct_digits)?;
+            decode_output(&digits, &problem.output_digits)
+        })
+        .collect::<Option<_>>()
+        .ok_or_else(|| AocError::no_solution(part, "signals don't match any wiring"))
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 8;
+    type Input = Box<[Problem]>;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        read_problems(reader)
+    }
+
+    fn part1(problems: &Self::Input) -> Result<Answer> {
+        Ok(decode_outputs(problems, 1)?
+            .into_iter()
+            .map(|output| {
+                output
+                    .iter()
+                    .filter(|&&d| d == 1 || d == 4 || d == 7 || d == 8)
+                    .count()
+            })
+            .sum::<usize>()
+            .into())
+    }
+
+    fn part2(problems: &Self::Input) -> Result<Answer> {
+        Ok(decode_outputs(problems, 2)?
+            .into_iter()
+            .map(|output| output[0] * 1000 + output[1] * 100 + output[2] * 10 + output[3])
+            .sum::<usize>()
+            .into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day08>(input)
+}
@@ -0,0 +1,71 @@
+use crate::core::answer::Answer;
+use crate::core::error::{AocError, Result};
+use crate::core::grid::Grid;
+use crate::core::position::Position;
+use crate::core::runner::{self, Solution};
+use crate::core::search;
+use std::collections::HashSet;
+use std::io::BufRead;
+
+type HeightMap = Grid<usize>;
+
+fn read_map(reader: impl BufRead) -> Result<HeightMap> {
+    Grid::from_lines_with(reader, |c| {
+        c.to_digit(10)
+            .map(|height| height as usize)
+            .ok_or_else(|| AocError::parse(format!("invalid height {:?}", c)))
+    })
+}
+
+fn find_low_points(map: &HeightMap) -> Box<[Position]> {
+    map.windows(3, 3)
+        .filter(|window| window.adjacent().all(|height| height > window.value()))
+        .map(|window| window.centre())
+        .collect::<Vec<_>>()
+        .into_boxed_slice()
+}
+
+fn get_risk_level(map: &HeightMap, position: Position) -> usize {
+    map[position] + 1
+}
+
+fn find_basin(map: &HeightMap, position: Position) -> HashSet<Position> {
+    search::flood_fill(position, |&pos| map.neighbours(pos), |&pos| map[pos] < 9)
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 9;
+    type Input = HeightMap;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        read_map(reader)
+    }
+
+    fn part1(map: &Self::Input) -> Result<Answer> {
+        Ok(find_low_points(map)
+            .iter()
+            .map(|&position| get_risk_level(map, position))
+            .sum::<usize>()
+            .into())
+    }
+
+    fn part2(map: &Self::Input) -> Result<Answer> {
+        let mut basin_sizes = find_low_points(map)
+            .iter()
+            .map(|&pos| find_basin(map, pos).len())
+            .collect::<Vec<_>>();
+        basin_sizes.sort_by(|a, b| a.cmp(b).reverse());
+
+        match basin_sizes[..] {
+            [a, b, c, ..] => Ok((a * b * c).into()),
+            _ => Err(AocError::no_solution(2, "fewer than three basins")),
+        }
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day09>(input)
+}
@@ -0,0 +1,163 @@
+use crate::core::answer::Answer;
+use crate::core::error::{AocError, Result};
+use crate::core::runner::{self, Solution};
+use std::io::BufRead;
+
+fn is_bracket(c: char) -> bool {
+    matches!(c, '(' | '[' | '{' | '<' | ')' | ']' | '}' | '>')
+}
+
+fn read_program(reader: impl BufRead) -> Result<Box<[String]>> {
+    reader
+        .lines()
+        .map(|line| {
+            let line = line?;
+            match line.chars().find(|&c| !is_bracket(c)) {
+                Some(c) => Err(AocError::parse(format!(
+                    "unexpected {:?} in \"{}\", expected only brackets",
+                    c, line
+                ))),
+                None => Ok(line),
+            }
+        })
+        .collect()
+}
+
+enum ValidateResult {
+    Invalid(char),
+    Incomplete(String),
+}
+
+impl ValidateResult {
+    fn invalid_char(&self) -> Option<char> {
+        match *self {
+            ValidateResult::Invalid(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    fn remaining_string(&self) -> Option<&str> {
+        match self {
+            ValidateResult::Incomplete(remaining) => Some(remaining),
+            _ => None,
+        }
+    }
+}
+
+fn closer(open: char) -> char {
+    match open {
+        '(' => ')',
+        '[' => ']',
+        '{' => '}',
+        '<' => '>',
+        _ => panic!("Not a open bracket: {}", open),
+    }
+}
+
+fn validate_line(line: &str) -> ValidateResult {
+    let mut stack = vec![];
+
+    for c in line.chars() {
+        match c {
+            '(' | '[' | '{' | '<' => stack.push(closer(c)),
+            ')' | ']' | '}' | '>' => {
+                let expected = stack.pop();
+                if expected != Some(c) {
+                    return ValidateResult::Invalid(c);
+                }
+            }
+            _ => unreachable!("unexpected character {} got past parsing", c),
+        }
+    }
+
+    let remaining = stack.into_iter().rev().collect();
+    ValidateResult::Incomplete(remaining)
+}
+
+fn validate_program(program: &[String]) -> Box<[ValidateResult]> {
+    program
+        .iter()
+        .map(String::as_str)
+        .map(validate_line)
+        .collect::<Vec<_>>()
+        .into_boxed_slice()
+}
+
+fn invalid_char_score(c: char) -> usize {
+    match c {
+        ')' => 3,
+        ']' => 57,
+        '}' => 1197,
+        '>' => 25137,
+        _ => panic!("Unexpected invalid char: {}", c),
+    }
+}
+
+fn remaining_char_score(c: char) -> usize {
+    match c {
+        ')' => 1,
+        ']' => 2,
+        '}' => 3,
+        '>' => 4,
+        _ => panic!("Unexpected remaining char: {}", c),
+    }
+}
+
+fn remaining_score(remaining: &str) -> usize {
+    remaining
+        .chars()
+        .rev()
+        .enumerate()
+        .map(|(index, c)| 5_usize.pow(index as u32) * remaining_char_score(c))
+        .sum()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 10;
+    type Input = Box<[String]>;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        read_program(reader)
+    }
+
+    fn part1(program: &Self::Input) -> Result<Answer> {
+        Ok(validate_program(program)
+            .iter()
+            .filter_map(ValidateResult::invalid_char)
+            .map(invalid_char_score)
+            .sum::<usize>()
+            .into())
+    }
+
+    fn part2(program: &Self::Input) -> Result<Answer> {
+        let mut remaining_scores: Vec<usize> = validate_program(program)
+            .iter()
+            .filter_map(ValidateResult::remaining_string)
+            .map(remaining_score)
+            .collect();
+        remaining_scores.sort_unstable();
+        match remaining_scores.get(remaining_scores.len() / 2) {
+            Some(&score) => Ok(score.into()),
+            None => Err(AocError::no_solution(2, "no incomplete lines")),
+        }
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day10>(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_invalid_characters() {
+        assert!(read_program("([])\n".as_bytes()).is_ok());
+        assert!(read_program("([])\n(x)\n".as_bytes()).is_err());
+        assert!(read_program("( )\n".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use crate::core::animate;
+use crate::core::answer::Answer;
+use crate::core::error::{AocError, Result};
+use crate::core::grid::Grid;
+use crate::core::runner::{self, Solution};
+use std::collections::HashSet;
+use std::io::BufRead;
+
+type Octopuses = Grid<usize>;
+
+fn read_octopuses(reader: impl BufRead) -> Result<Octopuses> {
+    Grid::from_lines_with(reader, |c| {
+        c.to_digit(10)
+            .map(|energy| energy as usize)
+            .ok_or_else(|| AocError::parse(format!("invalid energy {:?}", c)))
+    })
+}
+
+// Octopuses that just flashed show as `*`.
+fn render(octopuses: &Octopuses) -> String {
+    octopuses.render(|&energy| match energy {
+        0 => '*',
+        _ => char::from_digit(energy as u32, 10).unwrap_or('?'),
+    })
+}
+
+fn step(octopuses: &mut Octopuses) -> usize {
+    for energy in octopuses.values_mut() {
+        *energy += 1;
+    }
+
+    let positions = octopuses.positions().collect::<Vec<_>>();
+    let mut flashed = HashSet::new();
+
+    loop {
+        let mut have_flashed = false;
+
+        for &position in positions.iter() {
+            if octopuses[position] > 9 && !flashed.contains(&position) {
+                let neighbours = octopuses.neighbours8(position).collect::<Vec<_>>();
+                for neighbour in neighbours {
+                    octopuses[neighbour] += 1;
+                }
+
+                have_flashed = true;
+                flashed.insert(position);
+            }
+        }
+
+        if !have_flashed {
+            break;
+        }
+    }
+
+    for &position in flashed.iter() {
+        octopuses[position] = 0;
+    }
+
+    animate::frame(|| render(octopuses));
+
+    flashed.len()
+}
+
+fn count_flashes(mut octopuses: Octopuses, steps: usize) -> usize {
+    let mut total = 0;
+
+    for _ in 0..steps {
+        total += step(&mut octopuses);
+    }
+
+    total
+}
+
+fn find_when_all_flash(mut octopuses: Octopuses) -> usize {
+    let count = octopuses.width() * octopuses.height();
+    for index in 1.. {
+        if step(&mut octopuses) == count {
+            return index;
+        }
+    }
+    panic!("Unreachable");
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 11;
+    type Input = Octopuses;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        read_octopuses(reader)
+    }
+
+    fn part1(octopuses: &Self::Input) -> Result<Answer> {
+        Ok(count_flashes(octopuses.clone(), 100).into())
+    }
+
+    fn part2(octopuses: &Self::Input) -> Result<Answer> {
+        Ok(find_when_all_flash(octopuses.clone()).into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day11>(input)
+}
@@ -0,0 +1,126 @@
+use crate::core::answer::Answer;
+use crate::core::error::{AocError, Result};
+use crate::core::graph::{Graph, NodeId};
+use crate::core::runner::{self, Solution};
+use crate::core::search;
+use std::io::BufRead;
+use std::str::FromStr;
+
+struct Tunnel {
+    start: String,
+    end: String,
+}
+
+impl FromStr for Tunnel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('-');
+
+        let start = parts.next().unwrap().to_string();
+        let end = parts
+            .next()
+            .ok_or(format!("Invalid tunnel {:?}", s))?
+            .to_string();
+
+        if parts.next().is_some() {
+            return Err(format!("Invalid tunnel {:?}", s));
+        }
+
+        Ok(Tunnel { start, end })
+    }
+}
+
+fn parse_tunnels(reader: impl BufRead) -> Result<Graph> {
+    let mut tunnels = Graph::new();
+
+    for line in reader.lines() {
+        let Tunnel { start, end } = line?.parse::<Tunnel>().map_err(AocError::Parse)?;
+
+        let start = tunnels.node(&start);
+        let end = tunnels.node(&end);
+        tunnels.add_undirected_edge(start, end, 1);
+    }
+
+    for cave in ["start", "end"] {
+        if tunnels.id(cave).is_none() {
+            return Err(AocError::parse(format!("no {} cave", cave)));
+        }
+    }
+
+    Ok(tunnels)
+}
+
+fn is_large_cave(tunnels: &Graph, cave: NodeId) -> bool {
+    tunnels.name(cave).chars().all(|c| c.is_uppercase())
+}
+
+fn find_num_routes<F, S>(tunnels: &Graph, initial_state: S, can_visit: F) -> usize
+where
+    F: Fn(&[NodeId], NodeId, &S) -> Option<S>,
+    S: Clone,
+{
+    let start = tunnels.id("start").unwrap();
+    let end = tunnels.id("end").unwrap();
+
+    search::count_paths(
+        (vec![start], initial_state),
+        |(route, state)| {
+            let last = *route.last().unwrap();
+            tunnels
+                .neighbours(last)
+                .filter_map(|next| {
+                    can_visit(route, next, state).map(|new_state| {
+                        let mut new_route = route.clone();
+                        new_route.push(next);
+                        (new_route, new_state)
+                    })
+                })
+                .collect::<Vec<_>>()
+        },
+        |(route, _)| *route.last().unwrap() == end,
+    )
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 12;
+    type Input = Graph;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        parse_tunnels(reader)
+    }
+
+    fn part1(tunnels: &Self::Input) -> Result<Answer> {
+        Ok(find_num_routes(tunnels, (), |route, next, _| {
+            if is_large_cave(tunnels, next) || !route.contains(&next) {
+                Some(())
+            } else {
+                None
+            }
+        })
+        .into())
+    }
+
+    fn part2(tunnels: &Self::Input) -> Result<Answer> {
+        let start = tunnels.id("start").unwrap();
+        Ok(
+            find_num_routes(tunnels, true, |route, next, &can_visit_small_cave_twice| {
+                if is_large_cave(tunnels, next) || !route.contains(&next) {
+                    Some(can_visit_small_cave_twice)
+                } else if can_visit_small_cave_twice && next != start {
+                    Some(false)
+                } else {
+                    None
+                }
+            })
+            .into(),
+        )
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day12>(input)
+}
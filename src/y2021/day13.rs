@@ -0,0 +1,223 @@
+use crate::core::answer::Answer;
+use crate::core::error::Result;
+use crate::core::ocr;
+use crate::core::position::Position;
+use crate::core::runner::{self, Solution};
+use std::collections::HashSet;
+use std::io::{self, BufRead};
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+enum Axis {
+    X,
+    Y,
+}
+
+impl Axis {
+    fn reflect(self, position: Position, line: i64) -> Position {
+        match self {
+            Axis::X => Position::new(line - (position.x - line), position.y),
+            Axis::Y => Position::new(position.x, line - (position.y - line)),
+        }
+    }
+
+    fn coord(self, position: Position) -> i64 {
+        match self {
+            Axis::X => position.x,
+            Axis::Y => position.y,
+        }
+    }
+}
+
+type Paper = HashSet<Position>;
+
+pub struct Fold {
+    axis: Axis,
+    line: i64,
+}
+
+impl Fold {
+    fn apply(&self, paper: &Paper) -> Paper {
+        paper
+            .iter()
+            .map(|&position| {
+                if self.axis.coord(position) > self.line {
+                    self.axis.reflect(position, self.line)
+                } else {
+                    position
+                }
+            })
+            .collect()
+    }
+}
+
+type Inputs = (Paper, Box<[Fold]>);
+
+fn parse_files(reader: impl BufRead) -> Result<Inputs> {
+    parsing::parse_input(&io::read_to_string(reader)?)
+}
+
+fn render_paper(paper: &Paper) -> String {
+    let max_x = paper.iter().map(|pos| pos.x).max().unwrap();
+    let max_y = paper.iter().map(|pos| pos.y).max().unwrap();
+
+    let mut output = String::new();
+    for y in 0..=max_y {
+        for x in 0..=max_x {
+            if paper.contains(&Position::new(x, y)) {
+                output.push('#');
+            } else {
+                output.push('.');
+            }
+        }
+        output.push('\n');
+    }
+    output
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 13;
+    type Input = Inputs;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        parse_files(reader)
+    }
+
+    fn part1((paper, folds): &Self::Input) -> Result<Answer> {
+        Ok(folds[0].apply(paper).len().into())
+    }
+
+    fn part2((paper, folds): &Self::Input) -> Result<Answer> {
+        let final_paper = folds
+            .iter()
+            .fold(paper.clone(), |paper, fold| fold.apply(&paper));
+        Ok(match ocr::recognize(final_paper.iter().copied()) {
+            Some(code) => code.into(),
+            None => render_paper(&final_paper).into(),
+        })
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day13>(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5
+";
+
+    #[test]
+    fn test_render_paper() {
+        let (paper, folds) = parse_files(EXAMPLE.as_bytes()).unwrap();
+        insta::assert_snapshot!("unfolded", render_paper(&paper));
+
+        let paper = folds.iter().fold(paper, |paper, fold| fold.apply(&paper));
+        insta::assert_snapshot!("folded", render_paper(&paper));
+    }
+}
+
+mod parsing {
+    use super::{Axis, Fold, Inputs};
+    use crate::core::error::Result;
+    use crate::core::parsing::{blank_line, integer, lines_of, parse_all};
+    use crate::core::position::Position;
+
+    use nom::bytes::complete::tag;
+    use nom::character::complete::one_of;
+    use nom::combinator::{map, map_res};
+    use nom::sequence::{preceded, separated_pair};
+    use nom::IResult;
+
+    fn position(input: &str) -> IResult<&str, Position> {
+        map(separated_pair(integer, tag(","), integer), |(x, y)| {
+            Position::new(x, y)
+        })(input)
+    }
+
+    fn axis(input: &str) -> IResult<&str, Axis> {
+        map_res(one_of("xy"), |axis| match axis {
+            'x' => Ok(Axis::X),
+            'y' => Ok(Axis::Y),
+            _ => Err(format!("Unknown axis: {}", axis)),
+        })(input)
+    }
+
+    fn fold(input: &str) -> IResult<&str, Fold> {
+        map(
+            preceded(tag("fold along "), separated_pair(axis, tag("="), integer)),
+            |(axis, line)| Fold { axis, line },
+        )(input)
+    }
+
+    pub(super) fn parse_input(input: &str) -> Result<Inputs> {
+        let (positions, folds) = parse_all(
+            separated_pair(lines_of(position), blank_line, lines_of(fold)),
+            input,
+        )?;
+        Ok((positions.into_iter().collect(), folds.into_boxed_slice()))
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        #[test]
+        fn test_parse_position() {
+            let (rest, pos) = position("9,10").unwrap();
+            assert_eq!(rest, "");
+            assert_eq!(pos.x, 9);
+            assert_eq!(pos.y, 10);
+        }
+
+        #[test]
+        fn test_parse_fold() {
+            let (rest, f) = fold("fold along y=7").unwrap();
+            assert_eq!(rest, "");
+            assert_eq!(f.axis, Axis::Y);
+            assert_eq!(f.line, 7);
+        }
+
+        #[test]
+        fn test_parse_folds() {
+            let (rest, fs) = lines_of(fold)("fold along y=7\nfold along x=5").unwrap();
+            assert_eq!(rest, "");
+            assert_eq!(fs.len(), 2);
+            assert_eq!(fs[0].axis, Axis::Y);
+            assert_eq!(fs[0].line, 7);
+            assert_eq!(fs[1].axis, Axis::X);
+            assert_eq!(fs[1].line, 5);
+        }
+
+        #[test]
+        fn test_full_parse() {
+            let input = "0,14\n9,10\n0,3\n10,4\n4,11\n6,0\n6,12\n4,1\n0,13\n10,12\n3,4\n3,0\n8,4\n1,10\n2,14\n8,10\n9,0\n\nfold along y=7\nfold along x=5\n";
+            parse_input(input).unwrap();
+        }
+    }
+}
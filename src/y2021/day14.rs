@@ -0,0 +1,76 @@
+use crate::core::answer::Answer;
+use crate::core::error::Result;
+use crate::core::runner::{self, Solution};
+use crate::core::substitution::{PairCounts, Rules};
+use std::io::{self, BufRead};
+
+type Inputs = (Box<[char]>, Rules<char>);
+
+fn parse_input(reader: impl BufRead) -> Result<Inputs> {
+    parsing::parse_input(&io::read_to_string(reader)?)
+}
+
+fn offset_after(steps: usize, template: &[char], rules: &Rules<char>) -> u64 {
+    let mut counts = PairCounts::new(template);
+    counts.steps(rules, steps);
+
+    let elements = counts.elements();
+    let (_, max) = elements.max().unwrap();
+    let (_, min) = elements.min().unwrap();
+    max - min
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 14;
+    type Input = Inputs;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        parse_input(reader)
+    }
+
+    fn part1((template, rules): &Self::Input) -> Result<Answer> {
+        Ok(offset_after(10, template, rules).into())
+    }
+
+    fn part2((template, rules): &Self::Input) -> Result<Answer> {
+        Ok(offset_after(40, template, rules).into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day14>(input)
+}
+
+mod parsing {
+    use super::Inputs;
+    use crate::core::error::Result;
+    use crate::core::parsing::{blank_line, lines_of, parse_all};
+
+    use nom::bytes::complete::tag;
+    use nom::character::complete::one_of;
+    use nom::combinator::map;
+    use nom::multi::many1;
+    use nom::sequence::{pair, separated_pair};
+    use nom::IResult;
+
+    fn template(input: &str) -> IResult<&str, Box<[char]>> {
+        map(many1(upper), Vec::into_boxed_slice)(input)
+    }
+
+    fn upper(input: &str) -> IResult<&str, char> {
+        one_of("ABCDEFGHIJKLMNOPQRSTUVWXYZ")(input)
+    }
+
+    fn rule(input: &str) -> IResult<&str, ((char, char), char)> {
+        separated_pair(pair(upper, upper), tag(" -> "), upper)(input)
+    }
+
+    pub(super) fn parse_input(input: &str) -> Result<Inputs> {
+        let (template, rules) =
+            parse_all(separated_pair(template, blank_line, lines_of(rule)), input)?;
+        Ok((template, rules.into_iter().collect()))
+    }
+}
@@ -0,0 +1,66 @@
+use crate::core::a_star;
+use crate::core::answer::Answer;
+use crate::core::bucket_queue::BucketQueue;
+use crate::core::error::{AocError, Result};
+use crate::core::grid::{Connectivity, Grid, GridPath};
+use crate::core::position::Position;
+use crate::core::runner::{self, Solution};
+use std::io::BufRead;
+
+type RiskMap = Grid<usize>;
+
+fn parse_risk_map(reader: impl BufRead) -> Result<RiskMap> {
+    Grid::from_lines_with(reader, |c| {
+        c.to_digit(10)
+            .map(|risk| risk as usize)
+            .ok_or_else(|| AocError::parse(format!("invalid risk {:?}", c)))
+    })
+}
+
+// The map tiled `mult` times in each direction, with risks going up by one
+// for each tile right or down and wrapping from 9 back to 1.
+fn expand(risks: &RiskMap, mult: usize) -> RiskMap {
+    let (width, height) = (risks.width(), risks.height());
+    let cells = (0..height * mult)
+        .flat_map(|y| (0..width * mult).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let risk = risks[Position::new((x % width) as i64, (y % height) as i64)];
+            (risk + x / width + y / height - 1) % 9 + 1
+        })
+        .collect();
+    Grid::new(width * mult, height * mult, cells)
+}
+
+fn find_total_risk(risks: &RiskMap, part: u32) -> Result<usize> {
+    let bottom_right = Position::new(risks.width() as i64 - 1, risks.height() as i64 - 1);
+    let start = GridPath::new(risks, Position::new(0, 0), bottom_right, Connectivity::Four);
+    let (solution, _) =
+        a_star::solve_with_frontier::<_, BucketQueue<_>, _>(start, a_star::SolveOptions::new());
+    let (_, total_risk) =
+        solution.ok_or_else(|| AocError::no_solution(part, "no path to the bottom right"))?;
+    Ok(total_risk)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 15;
+    type Input = RiskMap;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        parse_risk_map(reader)
+    }
+
+    fn part1(risks: &Self::Input) -> Result<Answer> {
+        Ok(find_total_risk(risks, 1)?.into())
+    }
+
+    fn part2(risks: &Self::Input) -> Result<Answer> {
+        Ok(find_total_risk(&expand(risks, 5), 2)?.into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day15>(input)
+}
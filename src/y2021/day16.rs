@@ -0,0 +1,45 @@
+use crate::core::answer::Answer;
+use crate::core::error::Result;
+use crate::core::runner::{self, Solution};
+use crate::y2021::bits_protocol::Packet;
+use std::io::{self, BufRead};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 16;
+    // The packet along with its value, worked out up front so that one that
+    // overflows is reported as bad input.
+    type Input = (Packet, u64);
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        let packet = Packet::parse_hex(&io::read_to_string(reader)?)?;
+        let value = packet.evaluate()?;
+        Ok((packet, value))
+    }
+
+    fn part1((packet, _): &Self::Input) -> Result<Answer> {
+        Ok(packet.total_version().into())
+    }
+
+    fn part2(&(_, value): &Self::Input) -> Result<Answer> {
+        Ok(value.into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day16>(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_overflow() {
+        // A sum of two literals of 2^64 - 1.
+        let input = "020084FFFFFFFFFFFFFFFFFFEF13FFFFFFFFFFFFFFFFFFBC";
+        assert!(solve(input).is_err());
+    }
+}
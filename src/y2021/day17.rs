@@ -0,0 +1,195 @@
+use crate::core::answer::Answer;
+use crate::core::error::{parse_value, AocError, Result};
+use crate::core::parallel;
+use crate::core::runner::{self, Solution};
+use itertools::Itertools;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Range {
+    min: i64,
+    max: i64,
+}
+
+impl Range {
+    fn contains(&self, val: i64) -> bool {
+        self.min <= val && val <= self.max
+    }
+}
+
+impl FromStr for Range {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (min, max) = s
+            .get(2..)
+            .and_then(|bounds| bounds.split_once(".."))
+            .ok_or_else(|| AocError::parse(format!("invalid range {:?}", s)))?;
+        Ok(Range {
+            min: parse_value(min)?,
+            max: parse_value(max)?,
+        })
+    }
+}
+
+fn parse_ranges(reader: impl BufRead) -> Result<(Range, Range)> {
+    let text = io::read_to_string(reader)?;
+    let invalid = || AocError::parse(format!("invalid target area {:?}", text.trim_end()));
+
+    text.trim_end()
+        .strip_prefix("target area: ")
+        .ok_or_else(invalid)?
+        .split(", ")
+        .map(Range::from_str)
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .collect_tuple()
+        .ok_or_else(invalid)
+}
+
+fn max_x_distance(velocity: i64) -> i64 {
+    velocity * (velocity + 1) / 2
+}
+
+fn find_min_x_velocity(x_range: Range) -> i64 {
+    for v in 1.. {
+        if max_x_distance(v) >= x_range.min {
+            return v;
+        }
+    }
+    panic!("Unhittable")
+}
+fn find_max_x_velocity(x_range: Range) -> i64 {
+    x_range.max
+}
+
+fn find_min_y_velocity(y_range: Range) -> i64 {
+    y_range.min
+}
+fn find_max_y_velocity(y_range: Range) -> i64 {
+    -y_range.min - 1
+}
+
+fn max_height_for_velocity(velocity: i64) -> i64 {
+    velocity * (velocity + 1) / 2
+}
+
+fn find_max_height(y_range: Range) -> i64 {
+    let max_velocity = find_max_y_velocity(y_range);
+    max_height_for_velocity(max_velocity)
+}
+
+fn y_intercepts(y_range: Range, dy: i64) -> impl Iterator<Item = i64> {
+    let (base_t, v) = if dy > 0 {
+        (dy * 2 + 1, dy + 1)
+    } else {
+        (0, -dy)
+    };
+
+    (0..)
+        .map(move |dt| (base_t + dt, -(dt * v + (dt - 1) * dt / 2)))
+        .skip_while(move |&(_, y)| y > y_range.max)
+        .take_while(move |&(_, y)| y >= y_range.min)
+        .map(|(t, _)| t)
+}
+
+fn x_after(dx: i64, t: i64) -> i64 {
+    if t >= dx {
+        max_x_distance(dx)
+    } else {
+        max_x_distance(dx) - max_x_distance(dx - t)
+    }
+}
+
+fn hits(dx: i64, dy: i64, x_range: Range, y_range: Range) -> bool {
+    y_intercepts(y_range, dy).any(|t| x_range.contains(x_after(dx, t)))
+}
+
+fn find_intercept(init_dx: i64, init_dy: i64, x_range: Range, y_range: Range) -> Option<i64> {
+    let mut x = 0;
+    let mut y = 0;
+    let mut dx = init_dx;
+    let mut dy = init_dy;
+
+    for t in 0.. {
+        if x > x_range.max || y < y_range.min {
+            return None;
+        }
+
+        if x_range.contains(x) && y_range.contains(y) {
+            return Some(t);
+        }
+
+        x += dx;
+        y += dy;
+
+        if dx > 0 {
+            dx -= 1;
+        }
+        dy -= 1;
+    }
+    panic!("Unhittable");
+}
+
+#[allow(clippy::suspicious_map)]
+fn num_valid_velocities(x_range: Range, y_range: Range) -> usize {
+    let min_x_velocity = find_min_x_velocity(x_range);
+    let max_x_velocity = find_max_x_velocity(x_range);
+    let min_y_velocity = find_min_y_velocity(y_range);
+    let max_y_velocity = find_max_y_velocity(y_range);
+
+    let hits_for_dx = |dx| {
+        (min_y_velocity..=max_y_velocity)
+            .filter(|&dy| hits(dx, dy, x_range, y_range))
+            .map(|dy| {
+                find_intercept(dx, dy, x_range, y_range)
+                    .unwrap_or_else(|| panic!("{}, {} missed target", dx, dy))
+            })
+            .count()
+    };
+    parallel::par_map_reduce(
+        min_x_velocity..=max_x_velocity,
+        hits_for_dx,
+        || 0,
+        |a, b| a + b,
+    )
+}
+
+// Everything below assumes the probe is falling when it reaches the target.
+fn check_below(y_range: Range, part: u32) -> Result<()> {
+    if y_range.max < 0 {
+        Ok(())
+    } else {
+        Err(AocError::no_solution(
+            part,
+            "target area isn't below the launcher",
+        ))
+    }
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 17;
+    type Input = (Range, Range);
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        parse_ranges(reader)
+    }
+
+    fn part1(&(_, y_range): &Self::Input) -> Result<Answer> {
+        check_below(y_range, 1)?;
+        Ok(find_max_height(y_range).into())
+    }
+
+    fn part2(&(x_range, y_range): &Self::Input) -> Result<Answer> {
+        check_below(y_range, 2)?;
+        Ok(num_valid_velocities(x_range, y_range).into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day17>(input)
+}
@@ -0,0 +1,46 @@
+use crate::core::answer::Answer;
+use crate::core::error::{AocError, Result};
+use crate::core::runner::{self, Solution};
+use crate::y2021::snailfish::Number;
+use itertools::Itertools;
+use std::io::BufRead;
+
+fn parse_numbers(reader: impl BufRead) -> Result<Box<[Number]>> {
+    reader.lines().map(|value| value?.parse()).collect()
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 18;
+    type Input = Box<[Number]>;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        parse_numbers(reader)
+    }
+
+    fn part1(numbers: &Self::Input) -> Result<Answer> {
+        let total = numbers
+            .iter()
+            .cloned()
+            .reduce(|a, b| a + b)
+            .ok_or_else(|| AocError::no_solution(1, "no numbers to add"))?;
+        Ok(total.magnitude().into())
+    }
+
+    fn part2(numbers: &Self::Input) -> Result<Answer> {
+        Ok(numbers
+            .iter()
+            .cartesian_product(numbers.iter())
+            .filter(|(x, y)| x != y)
+            .map(|(x, y)| (x.clone() + y.clone()).magnitude())
+            .max()
+            .ok_or_else(|| AocError::no_solution(2, "fewer than two numbers"))?
+            .into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day18>(input)
+}
@@ -0,0 +1,238 @@
+use crate::core::answer::Answer;
+use crate::core::bounds::Cuboid;
+use crate::core::error::{AocError, Result};
+use crate::core::position::Position3 as Position;
+use crate::core::rotations::Rotation;
+use crate::core::runner::{self, Solution};
+use crate::core::tracker;
+use itertools::Itertools;
+use std::cell::OnceCell;
+use std::collections::HashSet;
+use std::io::{self, BufRead};
+
+// How far a scanner can see along each axis.
+const RANGE: i64 = 1000;
+
+#[derive(Clone)]
+struct Scanner {
+    index: i64,
+    position: Position,
+    beacons: HashSet<Position>,
+}
+
+impl Scanner {
+    fn rotate(&self, rotation: &Rotation) -> Self {
+        let beacons = self
+            .beacons
+            .iter()
+            .map(|&pos| rotation.apply(pos))
+            .collect();
+        Scanner {
+            index: self.index,
+            position: self.position,
+            beacons,
+        }
+    }
+
+    fn all_translations<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = Position> + 'a {
+        other.beacons.iter().flat_map(|&to_beacon| {
+            self.beacons
+                .iter()
+                .map(move |&from_beacon| to_beacon - from_beacon)
+        })
+    }
+
+    fn translate(&self, translation: Position) -> Scanner {
+        let position = self.position + translation;
+        let beacons = self.beacons.iter().map(|&pos| pos + translation).collect();
+        Scanner {
+            index: self.index,
+            position,
+            beacons,
+        }
+    }
+
+    #[allow(dead_code)]
+    fn overlapping_beacons<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> impl Iterator<Item = &'a Position> + 'a {
+        self.beacons.intersection(&other.beacons)
+    }
+
+    fn distance_to(&self, other: &Self) -> i64 {
+        self.position.manhattan_distance(other.position)
+    }
+
+    fn range(&self) -> Cuboid {
+        Cuboid::around(self.position, RANGE)
+    }
+
+    fn beacons_in<'a>(&'a self, region: &'a Cuboid) -> impl Iterator<Item = &'a Position> + 'a {
+        self.beacons
+            .iter()
+            .filter(|&&position| region.contains(position))
+    }
+}
+
+fn parse_scanners(reader: impl BufRead) -> Result<Box<[Scanner]>> {
+    let text = io::read_to_string(reader)?;
+    parsing::scanners(&text)
+}
+
+fn find_scanner_to_place(
+    placed_scanners: &[Scanner],
+    remaining_scanners: &[Scanner],
+) -> Option<Scanner> {
+    for scanner in remaining_scanners.iter() {
+        for placed_scanner in placed_scanners {
+            for translation in scanner.all_translations(placed_scanner) {
+                let translated_overlap = match scanner
+                    .range()
+                    .translate(translation)
+                    .intersection(&placed_scanner.range())
+                {
+                    Some(overlap) => overlap,
+                    None => continue,
+                };
+                let placed_overlapped_beacons = placed_scanner
+                    .beacons_in(&translated_overlap)
+                    .cloned()
+                    .collect::<HashSet<_>>();
+                if placed_overlapped_beacons.len() < 12 {
+                    continue;
+                }
+                let orig_overlap = translated_overlap.translate(-translation);
+                let mut translated_overlapped_beacons = scanner
+                    .beacons_in(&orig_overlap)
+                    .map(|&pos| pos + translation);
+                //     .collect::<HashSet<_>>();
+
+                //if placed_overlapped_beacons == translated_overlapped_beacons {
+                if translated_overlapped_beacons.all(|pos| placed_overlapped_beacons.contains(&pos))
+                    && scanner.beacons_in(&orig_overlap).count() == placed_overlapped_beacons.len()
+                {
+                    return Some(scanner.translate(translation));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// None if some scanner doesn't overlap enough with any of the others to be
+// placed.
+fn place_scanners(scanners: &[Scanner]) -> Option<Box<[Scanner]>> {
+    let rotations = Rotation::all();
+    let mut placed_scanners = vec![scanners[0].clone()];
+    let mut possible_scanners = scanners[1..]
+        .iter()
+        .flat_map(|scanner| rotations.iter().map(|rotation| scanner.rotate(rotation)))
+        .collect::<Vec<_>>();
+
+    let progress = tracker::progress("scanners placed", Some(scanners.len() as u64 - 1));
+    while !possible_scanners.is_empty() {
+        let scanner = find_scanner_to_place(&placed_scanners, &possible_scanners)?;
+        tracing::info!(scanner = scanner.index, position = ?scanner.position, "placed scanner");
+        progress.inc(1);
+        possible_scanners.retain(|s| s.index != scanner.index);
+        placed_scanners.push(scanner);
+    }
+
+    Some(placed_scanners.into_boxed_slice())
+}
+
+fn find_all_positions(scanners: &[Scanner]) -> HashSet<Position> {
+    scanners.iter().fold(HashSet::new(), |x, y| {
+        x.union(&y.beacons).cloned().collect()
+    })
+}
+
+fn find_max_distance(scanners: &[Scanner]) -> i64 {
+    scanners
+        .iter()
+        .cartesian_product(scanners)
+        .map(|(x, y)| x.distance_to(y))
+        .max()
+        .unwrap()
+}
+
+pub struct Scan {
+    scanners: Box<[Scanner]>,
+    placed_scanners: OnceCell<Option<Box<[Scanner]>>>,
+}
+
+impl Scan {
+    fn new(scanners: Box<[Scanner]>) -> Self {
+        Scan {
+            scanners,
+            placed_scanners: OnceCell::new(),
+        }
+    }
+
+    fn placed_scanners(&self, part: u32) -> Result<&[Scanner]> {
+        self.placed_scanners
+            .get_or_init(|| place_scanners(&self.scanners))
+            .as_deref()
+            .ok_or_else(|| AocError::no_solution(part, "couldn't place every scanner"))
+    }
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 19;
+    type Input = Scan;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        Ok(Scan::new(parse_scanners(reader)?))
+    }
+
+    fn part1(scan: &Self::Input) -> Result<Answer> {
+        Ok(find_all_positions(scan.placed_scanners(1)?).len().into())
+    }
+
+    fn part2(scan: &Self::Input) -> Result<Answer> {
+        Ok(find_max_distance(scan.placed_scanners(2)?).into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day19>(input)
+}
+
+mod parsing {
+    use super::*;
+
+    use crate::core::parsing::{blank_line_separated_blocks, integer, lines_of, parse_all, signed};
+    use nom::bytes::complete::tag;
+    use nom::character::complete::line_ending;
+    use nom::combinator::map;
+    use nom::sequence::{delimited, separated_pair, tuple};
+    use nom::IResult;
+
+    pub fn position(input: &str) -> IResult<&str, Position> {
+        map(
+            tuple((signed, tag(","), signed, tag(","), signed)),
+            |(x, _, y, _, z)| Position::new(x, y, z),
+        )(input)
+    }
+
+    fn scanner(input: &str) -> IResult<&str, Scanner> {
+        let header = delimited(tag("--- scanner "), integer, tag(" ---"));
+        map(
+            separated_pair(header, line_ending, lines_of(position)),
+            |(index, positions)| Scanner {
+                index,
+                position: Position::default(),
+                beacons: positions.into_iter().collect(),
+            },
+        )(input)
+    }
+
+    pub(super) fn scanners(input: &str) -> Result<Box<[Scanner]>> {
+        parse_all(blank_line_separated_blocks(scanner), input).map(Vec::into_boxed_slice)
+    }
+}
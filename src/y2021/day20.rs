@@ -0,0 +1,194 @@
+use crate::core::answer::Answer;
+use crate::core::error::{AocError, Result};
+use crate::core::grid::{Grid, Window};
+use crate::core::position::Position;
+use crate::core::runner::{self, Solution};
+use std::fmt::Display;
+use std::io::BufRead;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pixel {
+    Light,
+    Dark,
+}
+
+impl Display for Pixel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pixel::Dark => write!(f, "."),
+            Pixel::Light => write!(f, "#"),
+        }
+    }
+}
+
+impl TryFrom<char> for Pixel {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Pixel::Dark),
+            '#' => Ok(Pixel::Light),
+            _ => Err(format!("Invalid Pixel {}", value)),
+        }
+    }
+}
+
+pub struct Algorithm(Box<[Pixel]>);
+
+impl Algorithm {
+    fn all_dark_region() -> usize {
+        0
+    }
+
+    fn all_light_region() -> usize {
+        0x1FF
+    }
+
+    fn get(&self, key: usize) -> Pixel {
+        self.0[key]
+    }
+
+    // The 3x3 window read row by row as a binary number, with light pixels
+    // as 1s and `default` beyond the edge of the grid.
+    fn key_for_window(window: &Window<Pixel>, default: Pixel) -> usize {
+        window
+            .cells()
+            .map(|pixel| {
+                if pixel.copied().unwrap_or(default) == Pixel::Light {
+                    1
+                } else {
+                    0
+                }
+            })
+            .fold(0, |acc, bit| (acc << 1) | bit)
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let pixels = s
+            .chars()
+            .map(Pixel::try_from)
+            .collect::<Result<Box<[_]>, _>>()?;
+        if pixels.len() != 512 {
+            return Err(format!(
+                "Algorithm has {} pixels, expected 512",
+                pixels.len()
+            ));
+        }
+        Ok(Algorithm(pixels))
+    }
+}
+
+// The pixels that might differ from `default` are all within `pixels`.
+pub struct Image {
+    default: Pixel,
+    pixels: Grid<Pixel>,
+}
+
+impl Image {
+    fn apply_algorithm(&self, algorithm: &Algorithm) -> Image {
+        let default = algorithm.get(match self.default {
+            Pixel::Dark => Algorithm::all_dark_region(),
+            Pixel::Light => Algorithm::all_light_region(),
+        });
+
+        // Pixels next to the grid can change, so grow it by one each time.
+        let padded = self.pixels.pad(1, self.default);
+        let pixels = padded
+            .windows(3, 3)
+            .map(|window| algorithm.get(Algorithm::key_for_window(&window, self.default)))
+            .collect();
+
+        Image {
+            default,
+            pixels: Grid::new(padded.width(), padded.height(), pixels),
+        }
+    }
+
+    fn num_light_pixels(&self) -> Option<usize> {
+        if self.default == Pixel::Dark {
+            Some(
+                self.pixels
+                    .values()
+                    .filter(|&&pixel| pixel == Pixel::Light)
+                    .count(),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+fn read_image_enhancement_algorithm(reader: &mut impl BufRead) -> Result<Algorithm> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    line.trim_end().parse().map_err(AocError::Parse)
+}
+
+fn read_image(reader: impl BufRead) -> Result<Image> {
+    Ok(Image {
+        default: Pixel::Dark,
+        pixels: Grid::from_lines(reader)?,
+    })
+}
+
+fn parse_input(mut reader: impl BufRead) -> Result<(Algorithm, Image)> {
+    let algo = read_image_enhancement_algorithm(&mut reader)?;
+    reader.read_line(&mut String::new())?;
+    let image = read_image(&mut reader)?;
+    Ok((algo, image))
+}
+
+#[allow(dead_code)]
+fn display_image(image: &Image) {
+    for y in 0..image.pixels.height() as i64 {
+        for x in 0..image.pixels.width() as i64 {
+            print!("{}", image.pixels[Position::new(x, y)])
+        }
+        println!();
+    }
+    println!();
+}
+
+fn num_light_pixels_after(
+    algo: &Algorithm,
+    image: &Image,
+    steps: usize,
+    part: u32,
+) -> Result<usize> {
+    let mut image = image.apply_algorithm(algo);
+    for _ in 1..steps {
+        image = image.apply_algorithm(algo);
+    }
+    image
+        .num_light_pixels()
+        .ok_or_else(|| AocError::no_solution(part, "infinitely many pixels are lit"))
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 20;
+    type Input = (Algorithm, Image);
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        parse_input(reader)
+    }
+
+    fn part1((algo, image): &Self::Input) -> Result<Answer> {
+        Ok(num_light_pixels_after(algo, image, 2, 1)?.into())
+    }
+
+    fn part2((algo, image): &Self::Input) -> Result<Answer> {
+        Ok(num_light_pixels_after(algo, image, 50, 2)?.into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day20>(input)
+}
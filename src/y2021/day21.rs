@@ -0,0 +1,241 @@
+use crate::core::answer::Answer;
+use crate::core::error::{parse_value, AocError, Result};
+use crate::core::expansion::weighted_state_expansion;
+use crate::core::runner::{self, Solution};
+use std::cmp::Ord;
+use std::collections::HashMap;
+use std::io::BufRead;
+
+#[derive(Debug)]
+#[allow(dead_code)]
+struct DeterministicOutcome {
+    winner: usize,
+    loser: usize,
+    scores: [usize; 2],
+    num_die_rolls: usize,
+}
+
+struct DeterministicDie {
+    last_roll: usize,
+    num_rolls: usize,
+}
+
+impl DeterministicDie {
+    fn new() -> Self {
+        DeterministicDie {
+            last_roll: 100,
+            num_rolls: 0,
+        }
+    }
+}
+
+impl Iterator for DeterministicDie {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.last_roll = if self.last_roll < 100 {
+            self.last_roll + 1
+        } else {
+            1
+        };
+        self.num_rolls += 1;
+        Some(self.last_roll)
+    }
+}
+
+fn parse_player_starts(reader: impl BufRead) -> Result<[usize; 2]> {
+    reader
+        .lines()
+        .map(|line| {
+            let line = line?;
+            let (_, start) = line
+                .split_once(": ")
+                .ok_or_else(|| AocError::parse(format!("invalid player {:?}", line)))?;
+            parse_value(start)
+        })
+        .collect::<Result<Vec<_>>>()?
+        .try_into()
+        .map_err(|starts: Vec<_>| {
+            AocError::parse(format!("expected 2 players, found {}", starts.len()))
+        })
+}
+
+fn other_player(player: usize) -> usize {
+    1 - player
+}
+
+fn play_deterministic_game(start_pos: [usize; 2]) -> DeterministicOutcome {
+    let mut positions = start_pos;
+    let mut scores = [0; 2];
+    let mut die = DeterministicDie::new();
+    let mut player = 0;
+
+    loop {
+        let rolls = [
+            die.next().unwrap(),
+            die.next().unwrap(),
+            die.next().unwrap(),
+        ];
+        let next_move = rolls.iter().sum::<usize>();
+        positions[player] += next_move;
+        while positions[player] > 10 {
+            positions[player] -= 10;
+        }
+        scores[player] += positions[player];
+
+        if scores[player] >= 1000 {
+            break DeterministicOutcome {
+                winner: player,
+                loser: other_player(player),
+                scores,
+                num_die_rolls: die.num_rolls,
+            };
+        }
+
+        player = other_player(player);
+    }
+}
+
+struct QuantumOutcome {
+    winning_universes: [u64; 2],
+}
+
+#[derive(Hash, PartialEq, Eq, Debug, Clone, Copy)]
+struct PlayerState {
+    position: usize,
+    score: usize,
+}
+
+impl PlayerState {
+    fn new(position: usize) -> Self {
+        PlayerState { position, score: 0 }
+    }
+}
+
+#[derive(Hash, PartialEq, Eq, Debug, Clone, Copy)]
+struct UniverseState {
+    next_player: usize,
+    players: [PlayerState; 2],
+}
+
+impl UniverseState {
+    fn new(start_pos: [usize; 2]) -> Self {
+        UniverseState {
+            next_player: 0,
+            players: [
+                PlayerState::new(start_pos[0]),
+                PlayerState::new(start_pos[1]),
+            ],
+        }
+    }
+
+    fn winning_player(&self) -> Option<usize> {
+        self.players.iter().enumerate().find_map(|(index, player)| {
+            if player.score >= 21 {
+                Some(index)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn with_roll(&self, roll: usize) -> Self {
+        let mut new_state = *self;
+        {
+            let player = &mut new_state.players[self.next_player];
+            player.position += roll;
+            while player.position > 10 {
+                player.position -= 10;
+            }
+            player.score += player.position;
+        }
+        new_state.next_player = other_player(self.next_player);
+        new_state
+    }
+}
+
+impl PartialOrd for UniverseState {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for UniverseState {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        Ord::cmp(&self.players[0].score, &other.players[0].score)
+            .then_with(|| Ord::cmp(&self.players[1].score, &other.players[1].score))
+            .then_with(|| Ord::cmp(&self.players[0].position, &other.players[0].position))
+            .then_with(|| Ord::cmp(&self.players[1].position, &other.players[1].position))
+            .then_with(|| Ord::cmp(&self.next_player, &other.next_player))
+    }
+}
+
+fn get_splits() -> [u64; 10] {
+    let mut splits = [0; 10];
+
+    for x in 1..=3 {
+        for y in 1..=3 {
+            for z in 1..=3 {
+                splits[x + y + z] += 1;
+            }
+        }
+    }
+
+    splits
+}
+
+fn count_winning_universes(universes: &HashMap<UniverseState, u64>, player: usize) -> u64 {
+    universes
+        .iter()
+        .filter(|(state, _)| state.winning_player() == Some(player))
+        .map(|(_, count)| count)
+        .sum()
+}
+
+fn play_quantum_game(start_pos: [usize; 2]) -> QuantumOutcome {
+    let splits = get_splits();
+
+    let universes = weighted_state_expansion(
+        [(UniverseState::new(start_pos), 1)],
+        |&state| {
+            splits
+                .iter()
+                .enumerate()
+                .map(move |(roll, &num_new_universes)| (state.with_roll(roll), num_new_universes))
+        },
+        |state| state.winning_player().is_some(),
+    );
+
+    QuantumOutcome {
+        winning_universes: [
+            count_winning_universes(&universes, 0),
+            count_winning_universes(&universes, 1),
+        ],
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 21;
+    type Input = [usize; 2];
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        parse_player_starts(reader)
+    }
+
+    fn part1(&start_pos: &Self::Input) -> Result<Answer> {
+        let outcome = play_deterministic_game(start_pos);
+        Ok((outcome.scores[outcome.loser] * outcome.num_die_rolls).into())
+    }
+
+    fn part2(&start_pos: &Self::Input) -> Result<Answer> {
+        let outcome = play_quantum_game(start_pos);
+        Ok((*outcome.winning_universes.iter().max().unwrap()).into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day21>(input)
+}
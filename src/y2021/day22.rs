@@ -0,0 +1,135 @@
+use crate::core::answer::Answer;
+use crate::core::bounds::Cuboid;
+use crate::core::error::Result;
+use crate::core::interval_map::IntervalMap;
+use crate::core::position::Position3;
+use crate::core::runner::{self, Solution};
+use std::io::{self, BufRead};
+
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    on: bool,
+    region: Cuboid,
+}
+
+impl Instruction {
+    fn restrict(&self, region: &Cuboid) -> Option<Self> {
+        Some(Instruction {
+            on: self.on,
+            region: self.region.intersection(region)?,
+        })
+    }
+
+    fn apply(&self, cube_map: &mut IntervalMap<bool, 3>) {
+        cube_map.update(self.region.min.coords(), self.region.max.coords(), self.on);
+    }
+}
+
+fn parse_instructions(reader: impl BufRead) -> Result<Box<[Instruction]>> {
+    let data = io::read_to_string(reader)?;
+    parsing::instructions(&data)
+}
+
+fn run(instructions: &[Instruction], region: Option<Cuboid>) -> i64 {
+    let mut cube_map = IntervalMap::new(false);
+    for instruction in instructions.iter() {
+        if let Some(region) = &region {
+            if let Some(instruction) = instruction.restrict(region) {
+                instruction.apply(&mut cube_map);
+            }
+        } else {
+            instruction.apply(&mut cube_map);
+        }
+    }
+
+    cube_map.measure(|&on| on)
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 22;
+    type Input = Box<[Instruction]>;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        parse_instructions(reader)
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<Answer> {
+        Ok(run(
+            instructions,
+            Some(Cuboid::around(Position3::new(0, 0, 0), 50)),
+        )
+        .into())
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<Answer> {
+        Ok(run(instructions, None).into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day22>(input)
+}
+
+mod parsing {
+    use super::*;
+
+    use crate::core::error::AocError;
+    use crate::core::parsing::{lines_of, parse_all, signed};
+    use nom::branch::alt;
+    use nom::bytes::complete::tag;
+    use nom::combinator::{map, map_res};
+    use nom::sequence::separated_pair;
+    use nom::IResult;
+
+    pub fn range(input: &str) -> IResult<&str, (i64, i64)> {
+        map_res(separated_pair(signed, tag(".."), signed), |(min, max)| {
+            if min <= max {
+                Ok((min, max))
+            } else {
+                Err(AocError::parse("range ends before it starts"))
+            }
+        })(input)
+    }
+
+    fn command(input: &str) -> IResult<&str, bool> {
+        alt((map(tag("on"), |_| true), map(tag("off"), |_| false)))(input)
+    }
+
+    fn instruction(input: &str) -> IResult<&str, Instruction> {
+        let (input, on) = command(input)?;
+        let (input, _) = tag(" x=")(input)?;
+        let (input, x_range) = range(input)?;
+        let (input, _) = tag(",y=")(input)?;
+        let (input, y_range) = range(input)?;
+        let (input, _) = tag(",z=")(input)?;
+        let (input, z_range) = range(input)?;
+        Ok((
+            input,
+            Instruction {
+                on,
+                region: Cuboid::new(
+                    Position3::new(x_range.0, y_range.0, z_range.0),
+                    Position3::new(x_range.1, y_range.1, z_range.1),
+                ),
+            },
+        ))
+    }
+
+    pub(super) fn instructions(input: &str) -> Result<Box<[Instruction]>> {
+        parse_all(lines_of(instruction), input).map(Vec::into_boxed_slice)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_backwards_range() {
+        assert!(parse_instructions("on x=1..2,y=3..4,z=5..6\n".as_bytes()).is_ok());
+        assert!(parse_instructions("on x=1..2,y=4..3,z=5..6\n".as_bytes()).is_err());
+    }
+}
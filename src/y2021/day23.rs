@@ -0,0 +1,680 @@
+use crate::core::a_star;
+use crate::core::answer::Answer;
+use crate::core::error::{AocError, Result};
+use crate::core::runner::{self, Solution};
+use crate::core::tracker;
+use std::fmt::{Display, Write};
+use std::hash::Hash;
+use std::io::BufRead;
+
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
+enum Amphipod {
+    Amber,
+    Bronze,
+    Copper,
+    Desert,
+}
+
+impl Amphipod {
+    fn room(&self) -> usize {
+        use Amphipod::*;
+        match self {
+            Amber => 0,
+            Bronze => 1,
+            Copper => 2,
+            Desert => 3,
+        }
+    }
+
+    fn energy_to_move(&self) -> usize {
+        use Amphipod::*;
+        match self {
+            Amber => 1,
+            Bronze => 10,
+            Copper => 100,
+            Desert => 1000,
+        }
+    }
+}
+
+impl Display for Amphipod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Amphipod::*;
+        match self {
+            Amber => write!(f, "A"),
+            Bronze => write!(f, "B"),
+            Copper => write!(f, "C"),
+            Desert => write!(f, "D"),
+        }
+    }
+}
+
+impl TryFrom<char> for Amphipod {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        use Amphipod::*;
+        match value {
+            'A' => Ok(Amber),
+            'B' => Ok(Bronze),
+            'C' => Ok(Copper),
+            'D' => Ok(Desert),
+            _ => Err(value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Location {
+    Room { room: usize, depth: usize },
+    Corridor { spot: usize },
+}
+
+impl From<Location> for Position {
+    fn from(location: Location) -> Self {
+        use Location::*;
+
+        match location {
+            Room { room, depth } => Position {
+                x: 2 + 2 * room,
+                y: depth + 1,
+            },
+            Corridor { spot } => {
+                if spot == 0 {
+                    Position { x: 0, y: 0 }
+                } else if spot < 6 {
+                    Position {
+                        x: 2 * spot - 1,
+                        y: 0,
+                    }
+                } else {
+                    Position { x: 10, y: 0 }
+                }
+            }
+        }
+    }
+}
+
+impl Location {
+    fn distance_to(&self, other: Location) -> usize {
+        Position::from(*self).distance_to(other.into())
+    }
+
+    fn room(&self) -> Option<usize> {
+        use Location::*;
+        match self {
+            Room { room, .. } => Some(*room),
+            Corridor { .. } => None,
+        }
+    }
+
+    fn same_room(&self, other: Location) -> bool {
+        self.room()
+            .zip(other.room())
+            .map(|(room, other_room)| room == other_room)
+            .unwrap_or(false)
+    }
+}
+
+impl TryFrom<Position> for Location {
+    type Error = ();
+
+    fn try_from(position: Position) -> Result<Self, Self::Error> {
+        use Location::*;
+        if position.y == 0 {
+            if position.x == 0 {
+                Ok(Corridor { spot: 0 })
+            } else if position.x < 10 {
+                if !position.x.is_multiple_of(2) {
+                    Ok(Corridor {
+                        spot: position.x.div_ceil(2),
+                    })
+                } else {
+                    Err(())
+                }
+            } else {
+                Ok(Corridor { spot: 6 })
+            }
+        } else {
+            Ok(Room {
+                room: position.x / 2 - 1,
+                depth: position.y - 1,
+            })
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    x: usize,
+    y: usize,
+}
+
+impl Position {
+    fn distance_to(&self, other: Position) -> usize {
+        self.x.abs_diff(other.x) + self.y + other.y
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Layout {
+    room_depth: usize,
+    corridor: [Option<Amphipod>; 7],
+    rooms: [Vec<Amphipod>; 4],
+}
+
+impl Layout {
+    fn read(reader: impl BufRead) -> Result<Layout> {
+        let lines = reader.lines();
+
+        let rows = lines
+            .skip(2)
+            .take(2)
+            .map(|line| Self::parse_row(&line?))
+            .collect::<Result<Vec<_>>>()?;
+
+        if rows.len() != 2 {
+            return Err(AocError::parse("expected two rows of amphipods"));
+        }
+
+        let mut rooms: [Vec<Amphipod>; 4] = Default::default();
+
+        for amphipods in rows.iter().rev() {
+            for (&amphipod, room) in amphipods.iter().zip(rooms.iter_mut()) {
+                room.push(amphipod)
+            }
+        }
+
+        Ok(Layout {
+            room_depth: 2,
+            corridor: Default::default(),
+            rooms,
+        })
+    }
+
+    fn parse_row(line: &str) -> Result<[Amphipod; 4]> {
+        line.chars()
+            .filter_map(|c| c.try_into().ok())
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|_| AocError::parse(format!("expected 4 amphipods in {:?}", line)))
+    }
+
+    fn insert_row(&mut self, index: usize, row: &[Amphipod; 4]) {
+        for (amphipod, room) in row.iter().zip(self.rooms.iter_mut()) {
+            room.insert(index, *amphipod);
+        }
+        self.room_depth += 1;
+    }
+
+    fn is_complete(&self) -> bool {
+        self.rooms.iter().enumerate().all(|(room, amphipods)| {
+            amphipods.len() == self.room_depth
+                && amphipods.iter().all(|amphipod| amphipod.room() == room)
+        })
+    }
+
+    fn get_spot(position: usize) -> Option<usize> {
+        if position == 0 {
+            Some(0)
+        } else if position < 10 {
+            if !position.is_multiple_of(2) {
+                Some(position.div_ceil(2))
+            } else {
+                None
+            }
+        } else {
+            Some(6)
+        }
+    }
+
+    fn get_room(position: usize) -> Option<usize> {
+        if position == 0 {
+            None
+        } else if position < 10 {
+            if position.is_multiple_of(2) {
+                Some((position / 2) - 1)
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    fn locations_between<A: Into<Position>, B: Into<Position>>(
+        from: A,
+        to: B,
+    ) -> impl Iterator<Item = Location> {
+        let from: Position = from.into();
+        let to: Position = to.into();
+
+        let xs = if from.x < to.x {
+            from.x + 1..=to.x
+        } else {
+            to.x..=from.x - 1
+        };
+
+        (0..from.y)
+            .rev()
+            .map(move |y| Position { x: from.x, y })
+            .chain(xs.map(|x| Position { x, y: 0 }))
+            .chain((1..=to.y).map(move |y| Position { x: to.x, y }))
+            .filter_map(|pos| pos.try_into().ok())
+    }
+
+    fn location_clear(&self, location: Location) -> bool {
+        use Location::*;
+        match location {
+            Room { room, depth } => self.rooms[room].len() < self.room_depth - depth,
+            Corridor { spot } => self.corridor[spot].is_none(),
+        }
+    }
+
+    fn is_clear(&self, from: Location, to: Location) -> bool {
+        Self::locations_between(from, to).all(|spot| self.location_clear(spot))
+    }
+
+    fn available_room_location(&self, room: usize) -> Option<Location> {
+        if self.rooms[room].len() < self.room_depth {
+            Some(Location::Room {
+                room,
+                depth: self.room_depth - self.rooms[room].len() - 1,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn can_move_to_room(&self, location: Location, room: usize) -> Option<Location> {
+        self.available_room_location(room).filter(|&room_location| {
+            //self.can_move(location) &&
+            !location.same_room(room_location)
+                && self.is_clear(location, room_location)
+                && self.rooms[room]
+                    .iter()
+                    .all(|amphipod| amphipod.room() == room)
+        })
+    }
+
+    fn can_move_to_corridor(&self, location: Location, spot: usize) -> Option<Location> {
+        let corridor_location = Location::Corridor { spot };
+        if location != corridor_location && self.is_clear(location, corridor_location) {
+            Some(corridor_location)
+        } else {
+            None
+        }
+    }
+
+    fn amphipods(&self) -> impl Iterator<Item = (Location, Amphipod)> + '_ {
+        self.amphipods_in_corridor()
+            .chain(self.amphipods_in_rooms())
+    }
+
+    fn amphipods_in_corridor(&self) -> impl Iterator<Item = (Location, Amphipod)> + '_ {
+        self.corridor
+            .iter()
+            .enumerate()
+            .filter_map(|(spot, contents)| {
+                contents.map(|amphipod| (Location::Corridor { spot }, amphipod))
+            })
+    }
+
+    fn amphipods_in_rooms(&self) -> impl Iterator<Item = (Location, Amphipod)> + '_ {
+        self.rooms
+            .iter()
+            .enumerate()
+            .flat_map(move |(room, contents)| {
+                contents.iter().enumerate().map(move |(height, amphipod)| {
+                    (
+                        Location::Room {
+                            room,
+                            depth: self.room_depth - height - 1,
+                        },
+                        *amphipod,
+                    )
+                })
+            })
+    }
+
+    fn min_energy_to_solve(&self) -> usize {
+        self.amphipods()
+            .filter(|&(location, amphipod)| !self.is_settled(location, amphipod))
+            .map(|(location, amphipod)| {
+                amphipod.energy_to_move()
+                    * location.distance_to(Location::Room {
+                        room: amphipod.room(),
+                        depth: 0,
+                    })
+            })
+            .sum()
+    }
+
+    // In its own room with nothing but its own kind below it, so it never
+    // needs to move again.
+    fn is_settled(&self, location: Location, amphipod: Amphipod) -> bool {
+        match location {
+            Location::Room { room, depth } => {
+                room == amphipod.room()
+                    && self.rooms[room][..self.room_depth - depth]
+                        .iter()
+                        .all(|other| other.room() == room)
+            }
+            Location::Corridor { .. } => false,
+        }
+    }
+
+    fn remove(&mut self, location: Location) -> Option<Amphipod> {
+        use Location::*;
+        match location {
+            Corridor { spot } => self.corridor[spot].take(),
+            Room { room, depth } => {
+                // Can only remove the top amphipod in a room.
+                if self.room_depth - depth == self.rooms[room].len() {
+                    self.rooms[room].pop()
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn add(&mut self, location: Location, amphipod: Amphipod) {
+        use Location::*;
+        match location {
+            Corridor { spot } => {
+                assert!(self.corridor[spot].is_none());
+                self.corridor[spot] = Some(amphipod)
+            }
+            Room { room, depth } => {
+                // Can only add to the top of the amphipods in a room.
+                assert_eq!(self.room_depth - depth, self.rooms[room].len() + 1);
+                self.rooms[room].push(amphipod);
+            }
+        }
+    }
+
+    fn moves_to_corridor(&self, location: Location) -> impl Iterator<Item = Location> + '_ {
+        (0..7).filter_map(move |spot| self.can_move_to_corridor(location, spot))
+    }
+
+    fn do_move(&self, from: Location, to: Location) -> Self {
+        let mut layout = self.clone();
+        let amphipod = layout.remove(from).unwrap();
+        layout.add(to, amphipod);
+        layout
+    }
+}
+
+impl Display for Layout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "#############")?;
+        write!(f, "#")?;
+        for position in 0..=10 {
+            if let Some(spot) = Self::get_spot(position) {
+                if let Some(amphipod) = self.corridor[spot] {
+                    write!(f, "{}", amphipod)?;
+                } else {
+                    write!(f, ".")?;
+                }
+            } else {
+                write!(f, ".")?
+            }
+        }
+        writeln!(f, "#")?;
+
+        for index in 0..self.room_depth {
+            if index == 0 {
+                write!(f, "###")?;
+            } else {
+                write!(f, "  #")?;
+            }
+            for position in 2..=8 {
+                if let Some(room) = Self::get_room(position) {
+                    if let Some(amphipod) = self.rooms[room].get(self.room_depth - index - 1) {
+                        write!(f, "{}", amphipod)?;
+                    } else {
+                        write!(f, ".")?;
+                    }
+                } else {
+                    write!(f, "#")?
+                }
+            }
+            if index == 0 {
+                writeln!(f, "###")?;
+            } else {
+                writeln!(f, "#")?;
+            }
+        }
+
+        writeln!(f, "  #########   ")
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct AmphipodState {
+    layout: Layout,
+}
+
+impl AmphipodState {
+    fn new(layout: Layout) -> Self {
+        AmphipodState { layout }
+    }
+
+    fn successor(&self, amphipod: Amphipod, from: Location, to: Location) -> (Self, usize) {
+        let layout = self.layout.do_move(from, to);
+        let new_energy = amphipod.energy_to_move() * from.distance_to(to);
+
+        (AmphipodState { layout }, new_energy)
+    }
+
+    fn moves_to_room(&self) -> impl Iterator<Item = (AmphipodState, usize)> + '_ {
+        self.layout.amphipods().filter_map(|(location, amphipod)| {
+            self.layout
+                .can_move_to_room(location, amphipod.room())
+                .map(|new_location| self.successor(amphipod, location, new_location))
+        })
+    }
+
+    fn moves_to_corridor(&self) -> impl Iterator<Item = (AmphipodState, usize)> + '_ {
+        self.layout
+            .amphipods_in_rooms()
+            .flat_map(move |(location, amphipod)| {
+                self.layout
+                    .moves_to_corridor(location)
+                    .map(move |new_location| self.successor(amphipod, location, new_location))
+            })
+    }
+}
+
+impl a_star::State for AmphipodState {
+    fn min_remaining_cost(&self) -> usize {
+        self.layout.min_energy_to_solve()
+    }
+
+    fn successors(&self) -> Box<dyn Iterator<Item = (Self, usize)> + '_> {
+        // If an amphipod can move into their final room always do that as
+        // they have to do that at some point, it's never going to get cheaper
+        // to do so, and once they're in the room they can't affect anything
+        // else.
+        if let Some(candidate) = self.moves_to_room().next() {
+            Box::new([candidate].into_iter()) as Box<dyn Iterator<Item = (AmphipodState, usize)>>
+        } else {
+            Box::new(self.moves_to_corridor()) as Box<dyn Iterator<Item = (AmphipodState, usize)>>
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.layout.is_complete()
+    }
+}
+
+fn render_history(path: &[(AmphipodState, usize)]) -> String {
+    let mut output = String::new();
+    for ((state, energy), (_, next_energy)) in path.iter().zip(&path[1..]) {
+        writeln!(output, "{}", state.layout).unwrap();
+        writeln!(output, "Energy: {}\n", next_energy - energy).unwrap();
+    }
+    if let Some((state, _)) = path.last() {
+        writeln!(output, "{}", state.layout).unwrap();
+    }
+    output
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 23;
+    type Input = Layout;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        Layout::read(reader)
+    }
+
+    fn part1(layout: &Self::Input) -> Result<Answer> {
+        let state = AmphipodState::new(layout.clone());
+        let (_, total_energy) = a_star::solve(state)
+            .ok_or_else(|| AocError::no_solution(1, "the amphipods can't be organized"))?;
+        Ok(total_energy.into())
+    }
+
+    fn part2(layout: &Self::Input) -> Result<Answer> {
+        let mut layout = layout.clone();
+
+        use Amphipod::*;
+        layout.insert_row(1, &[Desert, Copper, Bronze, Amber]);
+        layout.insert_row(1, &[Desert, Bronze, Amber, Copper]);
+
+        let state = AmphipodState::new(layout);
+        let progress = tracker::progress("states expanded", None);
+        let options = a_star::SolveOptions::new().progress(10_000, |stats| {
+            progress.set_position(stats.expanded as u64);
+            progress.set_message(format!(
+                "generated {} heap {} duplicates {}",
+                stats.generated, stats.max_heap_size, stats.duplicates
+            ));
+        });
+        let (path, _) = a_star::solve_with_path_and_options(state, options);
+        let path =
+            path.ok_or_else(|| AocError::no_solution(2, "the amphipods can't be organized"))?;
+
+        tracing::debug!("moves:\n{}", render_history(&path));
+        Ok(path.last().unwrap().1.into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day23>(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::a_star::State;
+    use crate::core::local_search::{self, Schedule};
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+
+    const EXAMPLE: &str = "\
+#############
+#...........#
+###B#C#B#D###
+  #A#D#C#A#
+  #########
+";
+
+    #[test]
+    fn test_successors_from_rooms() {
+        use Amphipod::*;
+
+        let layout = Layout {
+            corridor: Default::default(),
+            room_depth: 2,
+            rooms: [
+                vec![Amber, Bronze],
+                vec![Desert, Copper],
+                vec![Copper, Bronze],
+                vec![Amber, Desert],
+            ],
+        };
+        let state = AmphipodState::new(layout);
+        let successors = state.successors().collect::<Vec<_>>();
+        assert_eq!(successors.len(), 28);
+    }
+
+    #[test]
+    fn test_heuristic_is_consistent() {
+        use Amphipod::*;
+
+        let layout = Layout {
+            corridor: Default::default(),
+            room_depth: 2,
+            rooms: [
+                vec![Amber, Bronze],
+                vec![Desert, Copper],
+                vec![Copper, Bronze],
+                vec![Amber, Desert],
+            ],
+        };
+        let state = a_star::Checked::new(AmphipodState::new(layout));
+        let (_, total_energy) = a_star::solve(state.clone()).unwrap();
+        state.assert_valid();
+        assert_eq!(total_energy, 12521);
+    }
+
+    #[test]
+    fn test_render_history() {
+        let layout = Layout::read(EXAMPLE.as_bytes()).unwrap();
+        insta::assert_snapshot!("layout", layout.to_string());
+
+        let path = a_star::solve_with_path(AmphipodState::new(layout)).unwrap();
+        insta::assert_snapshot!("history", render_history(&path));
+    }
+
+    // Plays out the moves picked by `choices`, each choosing among the
+    // successors at its step, for the energy used if that solves it.
+    fn play_out(state: &AmphipodState, choices: &[usize]) -> Option<usize> {
+        let mut state = state.clone();
+        let mut energy = 0;
+        for choice in choices {
+            if state.is_complete() {
+                break;
+            }
+            let successors = state.successors().collect::<Vec<_>>();
+            if successors.is_empty() {
+                return None;
+            }
+            let (next, cost) = successors[choice % successors.len()].clone();
+            state = next;
+            energy += cost;
+        }
+        state.is_complete().then_some(energy)
+    }
+
+    #[test]
+    fn test_anneal_against_a_star() {
+        let state = AmphipodState::new(Layout::read(EXAMPLE.as_bytes()).unwrap());
+        let (_, exact) = a_star::solve(state.clone()).unwrap();
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let choices = (0..40)
+            .map(|_| rng.random_range(0..100))
+            .collect::<Vec<_>>();
+        let (_, energy) = local_search::anneal(
+            choices,
+            Schedule::new(2000).temperature(5000.0, 10.0),
+            &mut rng,
+            |choices, rng| {
+                let mut choices = choices.clone();
+                let index = rng.random_range(0..choices.len());
+                choices[index] = rng.random_range(0..100);
+                choices
+            },
+            |choices| play_out(&state, choices).map_or(1e9, |energy| energy as f64),
+        );
+
+        assert!(energy < 1e9, "never solved it");
+        assert!(energy >= exact as f64);
+    }
+}
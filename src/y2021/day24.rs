@@ -0,0 +1,593 @@
+use crate::core::answer::Answer;
+use crate::core::error::{AocError, Result};
+use crate::core::runner::{self, Solution};
+use crate::core::tracker;
+use crate::y2021::symbolic::Expression;
+use itertools::Itertools;
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::io::BufRead;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Variable {
+    W,
+    X,
+    Y,
+    Z,
+}
+
+impl Variable {
+    fn all() -> impl Iterator<Item = Variable> {
+        use Variable::*;
+        [W, X, Y, Z].into_iter()
+    }
+
+    // How the ALU program refers to the variable.
+    fn name(self) -> &'static str {
+        use Variable::*;
+        match self {
+            W => "w",
+            X => "x",
+            Y => "y",
+            Z => "z",
+        }
+    }
+}
+
+impl FromStr for Variable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Variable::all()
+            .find(|variable| variable.name() == s)
+            .ok_or_else(|| format!("Invalid variable {}", s))
+    }
+}
+
+impl Display for Variable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Variable::*;
+        match self {
+            W => write!(f, "W"),
+            X => write!(f, "X"),
+            Y => write!(f, "Y"),
+            Z => write!(f, "Z"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Value {
+    Variable(Variable),
+    Literal(i64),
+    Argument(usize),
+}
+
+impl Value {
+    fn resolve(self, state: &State, arguments: &[i64]) -> i64 {
+        use Value::*;
+        match self {
+            Variable(variable) => state.get(variable),
+            Literal(value) => value,
+            Argument(index) => arguments[index],
+        }
+    }
+
+    fn extract_argument(&mut self, index: usize) -> Option<i64> {
+        use Value::*;
+        let value = if let Literal(value) = self {
+            Some(*value)
+        } else {
+            None
+        };
+
+        if value.is_some() {
+            *self = Argument(index);
+        }
+
+        value
+    }
+
+    fn remove_argument(&mut self, index: usize, value: i64) {
+        use Value::*;
+        if *self == Argument(index) {
+            *self = Literal(value);
+        } else if let Argument(ix) = self {
+            if *ix > index {
+                *ix -= 1
+            }
+        }
+    }
+}
+
+impl FromStr for Value {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<i64>()
+            .map(Value::Literal)
+            .or_else(|_| s.parse::<Variable>().map(Value::Variable))
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Variable(variable) => write!(f, "{}", variable.name()),
+            Value::Literal(value) => write!(f, "{}", value),
+            Value::Argument(index) => write!(f, "args[{}]", index),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Instruction {
+    Input(Variable),
+    Add(Variable, Value),
+    Mul(Variable, Value),
+    Div(Variable, Value),
+    Mod(Variable, Value),
+    Eql(Variable, Value),
+}
+
+// What an expression for the ALU's state can refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Symbol {
+    Register(Variable),
+    Argument(usize),
+    Input(usize),
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Symbol::Register(variable) => write!(f, "{}", variable),
+            Symbol::Argument(index) => write!(f, "args[{}]", index),
+            Symbol::Input(index) => write!(f, "input[{}]", index),
+        }
+    }
+}
+
+impl From<Variable> for Expression<Symbol> {
+    fn from(v: Variable) -> Self {
+        Expression::variable(Symbol::Register(v))
+    }
+}
+
+impl From<Value> for Expression<Symbol> {
+    fn from(v: Value) -> Self {
+        match v {
+            Value::Literal(value) => Expression::constant(value),
+            Value::Variable(var) => var.into(),
+            Value::Argument(index) => Expression::variable(Symbol::Argument(index)),
+        }
+    }
+}
+
+impl Instruction {
+    fn execute<I: Iterator<Item = i64>>(
+        &self,
+        state: &mut State,
+        mut inputs: I,
+        arguments: &[i64],
+    ) {
+        use Instruction::*;
+        match *self {
+            Input(out) => state.set(out, inputs.next().unwrap()),
+            Add(x, y) => state.set(x, state.get(x) + y.resolve(state, arguments)),
+            Mul(x, y) => state.set(x, state.get(x) * y.resolve(state, arguments)),
+            Div(x, y) => state.set(x, state.get(x) / y.resolve(state, arguments)),
+            Mod(x, y) => state.set(x, state.get(x) % y.resolve(state, arguments)),
+            Eql(x, y) => state.set(
+                x,
+                if state.get(x) == y.resolve(state, arguments) {
+                    1
+                } else {
+                    0
+                },
+            ),
+        }
+    }
+
+    // The variable the instruction sets and what it's set to.
+    fn assignment<I: Iterator<Item = usize>>(&self, mut inputs: I) -> (Symbol, Expression<Symbol>) {
+        let (var, expression) = match *self {
+            Instruction::Input(out) => (
+                out,
+                Expression::variable(Symbol::Input(inputs.next().unwrap())),
+            ),
+            Instruction::Add(x, y) => (x, Expression::sum(x.into(), y.into())),
+            Instruction::Mul(x, y) => (x, Expression::product(x.into(), y.into())),
+            Instruction::Div(x, y) => (x, Expression::quotient(x.into(), y.into())),
+            Instruction::Mod(x, y) => (x, Expression::remainder(x.into(), y.into())),
+            Instruction::Eql(x, y) => (x, Expression::equals(x.into(), y.into())),
+        };
+        (Symbol::Register(var), expression)
+    }
+
+    fn extract_argument(&mut self, index: usize) -> Option<i64> {
+        use Instruction::*;
+        match self {
+            Input(_) => None,
+            Add(_, y) | Mul(_, y) | Div(_, y) | Mod(_, y) | Eql(_, y) => y.extract_argument(index),
+        }
+    }
+
+    fn remove_argument(&mut self, index: usize, value: i64) {
+        use Instruction::*;
+        match self {
+            Input(_) => {}
+            Add(_, y) | Mul(_, y) | Div(_, y) | Mod(_, y) | Eql(_, y) => {
+                y.remove_argument(index, value)
+            }
+        }
+    }
+}
+
+// The instruction as it's written in the ALU program.
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Instruction::*;
+        let (name, x, y) = match *self {
+            Input(x) => ("inp", x, None),
+            Add(x, y) => ("add", x, Some(y)),
+            Mul(x, y) => ("mul", x, Some(y)),
+            Div(x, y) => ("div", x, Some(y)),
+            Mod(x, y) => ("mod", x, Some(y)),
+            Eql(x, y) => ("eql", x, Some(y)),
+        };
+        write!(f, "{} {}", name, x.name())?;
+        if let Some(y) = y {
+            write!(f, " {}", y)?;
+        }
+        Ok(())
+    }
+}
+
+fn read_unary_instruction<'a, F, I, V>(cons: F, iter: &mut I) -> Result<Instruction, String>
+where
+    F: Fn(V) -> Instruction,
+    I: Iterator<Item = &'a str>,
+    V: FromStr<Err = String>,
+{
+    Ok(cons(read_param(iter)?))
+}
+
+fn read_binary_instruction<'a, F, I, V1, V2>(cons: F, iter: &mut I) -> Result<Instruction, String>
+where
+    F: Fn(V1, V2) -> Instruction,
+    I: Iterator<Item = &'a str>,
+    V1: FromStr<Err = String>,
+    V2: FromStr<Err = String>,
+{
+    Ok(cons(read_param(iter)?, read_param(iter)?))
+}
+
+fn read_param<'a, I, V>(iter: &mut I) -> Result<V, String>
+where
+    I: Iterator<Item = &'a str>,
+    V: FromStr<Err = String>,
+{
+    iter.next()
+        .ok_or_else(|| "Missing parameter".to_string())?
+        .parse()
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use Instruction::*;
+        let mut parts = s.split(' ');
+        match parts
+            .next()
+            .ok_or_else(|| "Empty instruction".to_string())?
+        {
+            "inp" => read_unary_instruction(Input, &mut parts),
+            "add" => read_binary_instruction(Add, &mut parts),
+            "mul" => read_binary_instruction(Mul, &mut parts),
+            "div" => read_binary_instruction(Div, &mut parts),
+            "mod" => read_binary_instruction(Mod, &mut parts),
+            "eql" => read_binary_instruction(Eql, &mut parts),
+            instruction => Err(format!("Unknown instruction {}", instruction)),
+        }
+    }
+}
+
+fn read_instructions(reader: impl BufRead) -> Result<Box<[Instruction]>> {
+    reader
+        .lines()
+        .map(|line| line?.parse().map_err(AocError::Parse))
+        .collect()
+}
+
+struct State {
+    variables: [i64; 4],
+}
+
+impl State {
+    fn new() -> Self {
+        State { variables: [0; 4] }
+    }
+
+    fn set(&mut self, variable: Variable, value: i64) {
+        self.variables[variable as usize] = value
+    }
+
+    fn get(&self, variable: Variable) -> i64 {
+        self.variables[variable as usize]
+    }
+}
+
+fn output_for_digit(z: i64, digit: i64, a: i64, b: i64, c: i64) -> i64 {
+    (if (z % 26) + b != digit {
+        (z / a) * 25 + (digit + c)
+    } else {
+        0
+    }) + (z / a)
+}
+
+fn run(instructions: &[Instruction], input: &[i64], arguments: &[i64], z: i64) -> i64 {
+    let mut state = State::new();
+    state.set(Variable::Z, z);
+    let mut inputs = input.iter().cloned();
+
+    for instruction in instructions.iter() {
+        instruction.execute(&mut state, &mut inputs, arguments);
+    }
+
+    state.get(Variable::Z)
+}
+fn extract_arguments(function: &mut [Instruction]) -> Vec<i64> {
+    let mut args = vec![];
+
+    for instruction in function {
+        if let Some(arg) = instruction.extract_argument(args.len()) {
+            args.push(arg);
+        }
+    }
+
+    args
+}
+
+fn print_function_output(variable: Variable, function: &[Instruction]) {
+    let mut inputs = 0..;
+    let assignments = function
+        .iter()
+        .map(|instruction| instruction.assignment(&mut inputs))
+        .collect::<Vec<_>>();
+    let mut exp = Expression::from(variable);
+    exp.expand(&assignments);
+    exp.normalize();
+    tracing::debug!("{} = {}", variable, exp);
+}
+
+fn resolve_common_args(instructions: &mut [Instruction], arguments: &mut [Vec<i64>]) {
+    let mut index = 0;
+    while index < arguments[0].len() {
+        if arguments
+            .iter()
+            .tuple_windows()
+            .all(|(args1, args2)| args1[index] == args2[index])
+        {
+            for instruction in instructions.iter_mut() {
+                instruction.remove_argument(index, arguments[0][index]);
+            }
+
+            for args in arguments.iter_mut() {
+                args.remove(index);
+            }
+        } else {
+            index += 1;
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn extract_function(
+    instructions: &[Instruction],
+    length: usize,
+) -> (Box<[Instruction]>, Box<[Box<[i64]>]>) {
+    let mut function: Option<Box<[Instruction]>> = None;
+    let mut arguments = vec![];
+
+    for index in (0..instructions.len()).step_by(length) {
+        let mut func = instructions[index..index + length]
+            .to_vec()
+            .into_boxed_slice();
+        let args = extract_arguments(&mut func);
+        arguments.push(args);
+
+        if let Some(function) = &function {
+            assert_eq!(*function, func);
+        } else {
+            function = Some(func);
+        }
+    }
+
+    let mut function = function.unwrap();
+
+    resolve_common_args(&mut function, &mut arguments);
+
+    (
+        function,
+        arguments
+            .into_iter()
+            .map(Vec::into_boxed_slice)
+            .collect::<Vec<_>>()
+            .into_boxed_slice(),
+    )
+}
+
+fn find_model_numbers(instructions: &[Instruction]) -> Box<[i64]> {
+    let (function, arguments) = extract_function(instructions, 18);
+
+    for a in [1, 26] {
+        for b in -16..=13 {
+            for c in 2..=15 {
+                for digit in 1..10 {
+                    for z in 0..26 {
+                        assert_eq!(
+                            output_for_digit(z, digit, a, b, c),
+                            run(&function, &[digit], &[a, b, c], z)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    for instruction in function.iter() {
+        tracing::debug!("{}", instruction);
+    }
+    for variable in Variable::all() {
+        print_function_output(variable, &function);
+    }
+    for args in arguments.iter() {
+        tracing::debug!(?args, "arguments");
+    }
+
+    let span = tracing::info_span!("possible_zs").entered();
+    let mut zs = vec![[0_i64].into_iter().collect::<HashSet<_>>()];
+
+    let progress = tracker::progress("z sets", Some(arguments.len() as u64 - 1));
+    for (index, args) in arguments[..arguments.len() - 1].iter().enumerate() {
+        let last_zs = zs.last().unwrap();
+        let new_zs: HashSet<i64> = last_zs
+            .iter()
+            .flat_map(|z| (1..10).map(|digit| run(&function, &[digit], args, *z)))
+            .collect();
+        tracing::info!(index, zs = new_zs.len(), "possible zs");
+        progress.inc(1);
+        zs.push(new_zs);
+    }
+    drop(progress);
+    drop(span);
+
+    let _span = tracing::info_span!("valid_numbers").entered();
+    let mut candidates: HashMap<i64, Vec<Vec<i64>>> = [(0, vec![vec![]])].into_iter().collect();
+    for (index, args) in arguments.iter().enumerate().rev() {
+        let mut new_candidates: HashMap<i64, Vec<Vec<i64>>> = HashMap::new();
+
+        for z_in in zs[index].iter() {
+            for digit in 1..10 {
+                let z_out = run(&function, &[digit], args, *z_in);
+                if let Some(seqs) = candidates.get(&z_out) {
+                    for seq in seqs {
+                        let mut seq = seq.clone();
+                        seq.push(digit);
+                        new_candidates.entry(*z_in).or_default().push(seq);
+                    }
+                }
+            }
+        }
+
+        candidates = new_candidates;
+        tracing::info!(index, candidates = candidates.len(), "candidates");
+    }
+
+    let mut nums = candidates
+        .get(&0)
+        .into_iter()
+        .flatten()
+        .map(|num| num.iter().rev().fold(0, |total, digit| total * 10 + digit))
+        .collect::<Vec<_>>();
+    tracing::info!(count = nums.len(), "valid model numbers");
+    nums.sort_unstable();
+    nums.into_boxed_slice()
+}
+
+pub struct Monad {
+    instructions: Box<[Instruction]>,
+    model_numbers: OnceCell<Box<[i64]>>,
+}
+
+impl Monad {
+    fn new(instructions: Box<[Instruction]>) -> Self {
+        Monad {
+            instructions,
+            model_numbers: OnceCell::new(),
+        }
+    }
+
+    fn model_numbers(&self) -> &[i64] {
+        self.model_numbers
+            .get_or_init(|| find_model_numbers(&self.instructions))
+    }
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 24;
+    type Input = Monad;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        Ok(Monad::new(read_instructions(reader)?))
+    }
+
+    fn part1(monad: &Self::Input) -> Result<Answer> {
+        let largest = monad
+            .model_numbers()
+            .last()
+            .ok_or_else(|| AocError::no_solution(1, "no valid model numbers"))?;
+        Ok((*largest).into())
+    }
+
+    fn part2(monad: &Self::Input) -> Result<Answer> {
+        let smallest = monad
+            .model_numbers()
+            .first()
+            .ok_or_else(|| AocError::no_solution(2, "no valid model numbers"))?;
+        Ok((*smallest).into())
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day24>(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn variable() -> impl Strategy<Value = Variable> {
+        prop::sample::select(Variable::all().collect::<Vec<_>>())
+    }
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        use Instruction::*;
+        let value = prop_oneof![
+            variable().prop_map(Value::Variable),
+            any::<i64>().prop_map(Value::Literal),
+        ];
+        prop_oneof![
+            variable().prop_map(Input),
+            (0..5_u8, variable(), value).prop_map(|(op, x, y)| match op {
+                0 => Add(x, y),
+                1 => Mul(x, y),
+                2 => Div(x, y),
+                3 => Mod(x, y),
+                _ => Eql(x, y),
+            }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_instruction_round_trip(instruction in instruction()) {
+            prop_assert_eq!(instruction.to_string().parse::<Instruction>(), Ok(instruction));
+        }
+    }
+
+    #[test]
+    fn test_display_instruction() {
+        for line in ["inp w", "add z -12", "mul y x", "eql x w"] {
+            assert_eq!(line.parse::<Instruction>().unwrap().to_string(), line);
+        }
+    }
+}
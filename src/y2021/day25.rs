@@ -0,0 +1,133 @@
+use crate::core::animate;
+use crate::core::answer::Answer;
+use crate::core::error::{AocError, Result};
+use crate::core::position::{Backing, Direction, Position, TorusMap};
+use crate::core::runner::{self, Solution};
+use crate::core::simulate::simulate_until;
+use std::io::BufRead;
+
+type CucumberMap = TorusMap<Direction>;
+
+fn read_map(reader: impl BufRead) -> Result<CucumberMap> {
+    let grid = reader
+        .lines()
+        .map(|line| Ok(line?.chars().collect::<Vec<_>>()))
+        .collect::<Result<Vec<_>>>()?;
+
+    if grid.is_empty() {
+        return Err(AocError::parse("empty map"));
+    }
+
+    let map = grid
+        .iter()
+        .enumerate()
+        .flat_map(|(y, chars)| {
+            chars
+                .iter()
+                .enumerate()
+                .filter_map(|(x, &c)| {
+                    Direction::try_from(c)
+                        .ok()
+                        .map(|d| (Position::new(x as i64, y as i64), d))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    Ok(CucumberMap::with_backing(
+        map,
+        grid[0].len() as i64,
+        grid.len() as i64,
+        Backing::Dense,
+    ))
+}
+
+fn move_cucumbers(map: &mut CucumberMap, move_in: Direction) -> bool {
+    let moves = map
+        .iter()
+        .filter_map(|(position, direction)| {
+            if *direction == move_in {
+                let next = position.step(*direction);
+
+                if !map.contains_key(&next) {
+                    Some((position, next))
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let moved = !moves.is_empty();
+
+    map.make_moves(moves);
+
+    moved
+}
+
+// Far more than any real input needs.
+const MAX_STEPS: usize = 100_000;
+
+// The number of steps until nothing moves, and where they all end up.
+fn move_until_gridlock(map: &CucumberMap) -> Result<(usize, CucumberMap)> {
+    let step = |map: &mut CucumberMap| {
+        let moved_east = move_cucumbers(map, Direction::East);
+        let moved_south = move_cucumbers(map, Direction::South);
+        animate::frame(|| map.render());
+        moved_east || moved_south
+    };
+    simulate_until(map.clone(), step, |_, &moved| !moved, MAX_STEPS)
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 25;
+    type Input = CucumberMap;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        read_map(reader)
+    }
+
+    fn part1(map: &Self::Input) -> Result<Answer> {
+        let (steps, map) = move_until_gridlock(map)?;
+        tracing::debug!("final map:\n{}", map);
+        Ok(steps.into())
+    }
+
+    fn part2(_map: &Self::Input) -> Result<Answer> {
+        Ok(Answer::None)
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day25>(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>
+";
+
+    #[test]
+    fn test_gridlock() {
+        let map = read_map(EXAMPLE.as_bytes()).unwrap();
+        let (steps, map) = move_until_gridlock(&map).unwrap();
+        assert_eq!(steps, 58);
+        insta::assert_snapshot!("final_map", map.to_string());
+    }
+}
@@ -0,0 +1,111 @@
+use crate::core::answer::Answer;
+use crate::core::error::{AocError, Result};
+use crate::core::runner::{self, Solution};
+use either::Either;
+use std::io::BufRead;
+
+type CucumberMap = Vec<Vec<char>>;
+
+fn read_map(reader: impl BufRead) -> Result<CucumberMap> {
+    let map = reader
+        .lines()
+        .map(|line| Ok(line?.chars().collect::<Vec<_>>()))
+        .collect::<Result<Vec<_>>>()?;
+
+    if map.is_empty() {
+        return Err(AocError::parse("empty map"));
+    }
+
+    Ok(map)
+}
+
+fn set(map: &mut CucumberMap, (x, y): (usize, usize), c: char) {
+    map[y][x] = c;
+}
+
+fn get(map: &CucumberMap, (x, y): (usize, usize)) -> char {
+    map[y][x]
+}
+
+fn move_cucumbers(map: &mut CucumberMap, direction: char) -> bool {
+    let width = map[0].len();
+    let height = map.len();
+
+    let potential_moves = if direction == '>' {
+        Either::Left((0..height).flat_map(|y| {
+            (0..width)
+                .zip((0..width).cycle().skip(1))
+                .map(move |(x, next_x)| ((x, y), (next_x, y)))
+        }))
+    } else {
+        Either::Right((0..width).flat_map(|x| {
+            (0..height)
+                .zip((0..height).cycle().skip(1))
+                .map(move |(y, next_y)| ((x, y), (x, next_y)))
+        }))
+    };
+
+    let moves = potential_moves
+        .filter(|&(from, to)| get(map, from) == direction && get(map, to) == '.')
+        .collect::<Vec<_>>();
+
+    let moved = !moves.is_empty();
+
+    for (from, to) in moves {
+        set(map, from, '.');
+        set(map, to, direction);
+    }
+
+    moved
+}
+
+fn print_map(map: &CucumberMap) {
+    for row in map {
+        for c in row {
+            eprint!("{}", c)
+        }
+        eprintln!()
+    }
+    eprintln!()
+}
+
+fn move_until_gridlock(map: &CucumberMap) -> usize {
+    let mut map = map.clone();
+
+    for step in 1.. {
+        let mut updated = false;
+        updated |= move_cucumbers(&mut map, '>');
+        updated |= move_cucumbers(&mut map, 'v');
+
+        if !updated {
+            print_map(&map);
+            return step;
+        }
+    }
+
+    unreachable!()
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const YEAR: u32 = 2021;
+    const DAY: u32 = 25;
+    type Input = CucumberMap;
+
+    fn parse(reader: impl BufRead) -> Result<Self::Input> {
+        read_map(reader)
+    }
+
+    fn part1(map: &Self::Input) -> Result<Answer> {
+        Ok(move_until_gridlock(map).into())
+    }
+
+    fn part2(_map: &Self::Input) -> Result<Answer> {
+        Ok(Answer::None)
+    }
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    runner::solve_str::<Day25>(input)
+}
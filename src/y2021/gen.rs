@@ -0,0 +1,575 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{RngExt, SeedableRng};
+use std::fmt::Write;
+
+// Random puzzle inputs that the solutions accept, for seeing how they scale
+// past the official inputs.  The same seed always gives the same input.
+pub struct Generator {
+    rng: StdRng,
+}
+
+// The size of the official inputs, near enough.
+pub fn default_size(day: u32) -> usize {
+    match day {
+        1 | 2 | 3 | 8 | 22 => 1000,
+        4 => 100,
+        5 | 6 | 7 | 13 | 25 => 300,
+        9 | 15 | 20 => 100,
+        10 | 14 | 18 => 100,
+        11 => 10,
+        12 => 10,
+        17 => 50,
+        _ => 1,
+    }
+}
+
+// Whether all the octopuses in `grid` flash on the same step within
+// `max_steps`.
+fn synchronises(grid: &str, max_steps: usize) -> bool {
+    let width = grid.lines().next().map_or(0, str::len) as i64;
+    let mut energy = grid
+        .lines()
+        .flat_map(|line| line.bytes().map(|b| b - b'0'))
+        .collect::<Vec<_>>();
+    let height = energy.len() as i64 / width.max(1);
+
+    for _ in 0..max_steps {
+        let mut flashing = vec![];
+        for (index, level) in energy.iter_mut().enumerate() {
+            *level += 1;
+            if *level == 10 {
+                flashing.push(index as i64);
+            }
+        }
+        while let Some(index) = flashing.pop() {
+            let (x, y) = (index % width, index / width);
+            for (nx, ny) in (x - 1..=x + 1).flat_map(|nx| (y - 1..=y + 1).map(move |ny| (nx, ny))) {
+                if (0..width).contains(&nx) && (0..height).contains(&ny) {
+                    let level = &mut energy[(ny * width + nx) as usize];
+                    *level += 1;
+                    if *level == 10 {
+                        flashing.push(ny * width + nx);
+                    }
+                }
+            }
+        }
+        let mut all = true;
+        for level in energy.iter_mut() {
+            if *level > 9 {
+                *level = 0;
+            } else {
+                all = false;
+            }
+        }
+        if all {
+            return true;
+        }
+    }
+    false
+}
+
+fn join<T: ToString>(values: impl IntoIterator<Item = T>, separator: &str) -> String {
+    values
+        .into_iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Self {
+        Generator {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // An input for `day` with about `size` of whatever it's made of (lines,
+    // grid width, ...), or `None` for the days there's no generator for.
+    pub fn input(&mut self, day: u32, size: usize) -> Option<String> {
+        let size = size.max(1);
+        Some(match day {
+            1 => self.depths(size),
+            2 => self.commands(size),
+            3 => self.diagnostics(size),
+            4 => self.bingo(size),
+            5 => self.vents(size, 1000),
+            6 => self.lanternfish(size),
+            7 => self.crabs(size),
+            8 => self.displays(size),
+            9 => self.heightmap(size),
+            10 => self.navigation(size),
+            11 => self.octopuses(size),
+            12 => self.caves(size),
+            13 => self.origami(size),
+            14 => self.polymer(size),
+            15 => self.digit_grid(size, 1..=9),
+            17 => self.target_area(size as i64),
+            18 => self.snailfish(size),
+            20 => self.trench_map(size),
+            21 => self.dice(),
+            22 => self.reboot_steps(size),
+            23 => self.amphipods(),
+            25 => self.cucumbers(size),
+            _ => return None,
+        })
+    }
+
+    // A sonar sweep drifting down from the surface.
+    pub fn depths(&mut self, count: usize) -> String {
+        let mut depth = 100_i64;
+        let mut text = String::new();
+        for _ in 0..count {
+            depth = (depth + self.rng.random_range(-20..=40)).max(0);
+            writeln!(text, "{}", depth).unwrap();
+        }
+        text
+    }
+
+    pub fn commands(&mut self, count: usize) -> String {
+        let mut text = String::new();
+        for _ in 0..count {
+            let command = ["forward", "down", "up"][self.rng.random_range(0..3)];
+            writeln!(text, "{} {}", command, self.rng.random_range(1..10)).unwrap();
+        }
+        text
+    }
+
+    // Distinct binary numbers, so the rating filters always end on one.
+    pub fn diagnostics(&mut self, count: usize) -> String {
+        let width = (usize::BITS - (count * 2).leading_zeros()).max(5) as usize;
+        let mut values = (0..1_u64 << width).collect::<Vec<_>>();
+        values.shuffle(&mut self.rng);
+        let mut text = String::new();
+        for value in &values[..count] {
+            writeln!(text, "{:0width$b}", value, width = width).unwrap();
+        }
+        text
+    }
+
+    // `cards` 5x5 cards, with every number drawn so that they all win.
+    pub fn bingo(&mut self, cards: usize) -> String {
+        let mut numbers = (0..100).collect::<Vec<_>>();
+        numbers.shuffle(&mut self.rng);
+        let mut text = format!("{}\n", join(&numbers, ","));
+        for _ in 0..cards {
+            numbers.shuffle(&mut self.rng);
+            text.push('\n');
+            for row in numbers[..25].chunks(5) {
+                writeln!(
+                    text,
+                    "{}",
+                    join(row.iter().map(|n| format!("{:2}", n)), " ")
+                )
+                .unwrap();
+            }
+        }
+        text
+    }
+
+    // Horizontal, vertical and diagonal lines in a `extent` square.
+    pub fn vents(&mut self, count: usize, extent: i64) -> String {
+        let mut text = String::new();
+        while text.lines().count() < count {
+            let (x1, y1) = (
+                self.rng.random_range(0..extent),
+                self.rng.random_range(0..extent),
+            );
+            let (dx, dy) = [
+                (1, 0),
+                (-1, 0),
+                (0, 1),
+                (0, -1),
+                (1, 1),
+                (1, -1),
+                (-1, 1),
+                (-1, -1),
+            ][self.rng.random_range(0..8)];
+            // The furthest the line can go without leaving the square.
+            let room = |start: i64, delta: i64| match delta {
+                1 => extent - 1 - start,
+                -1 => start,
+                _ => extent,
+            };
+            let longest = room(x1, dx).min(room(y1, dy)).min(extent / 2);
+            if longest < 1 {
+                continue;
+            }
+            let length = self.rng.random_range(1..=longest);
+            writeln!(
+                text,
+                "{},{} -> {},{}",
+                x1,
+                y1,
+                x1 + dx * length,
+                y1 + dy * length
+            )
+            .unwrap();
+        }
+        text
+    }
+
+    pub fn lanternfish(&mut self, count: usize) -> String {
+        let timers = (0..count).map(|_| self.rng.random_range(1..=5));
+        format!("{}\n", join(timers.collect::<Vec<_>>(), ","))
+    }
+
+    pub fn crabs(&mut self, count: usize) -> String {
+        let positions = (0..count).map(|_| self.rng.random_range(0..2000));
+        format!("{}\n", join(positions.collect::<Vec<_>>(), ","))
+    }
+
+    // Seven-segment displays, each wired up differently.
+    pub fn displays(&mut self, count: usize) -> String {
+        const DIGITS: [&str; 10] = [
+            "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+        ];
+        let mut text = String::new();
+        for _ in 0..count {
+            let mut wiring = "abcdefg".chars().collect::<Vec<_>>();
+            wiring.shuffle(&mut self.rng);
+            let output_digits = (0..4)
+                .map(|_| self.rng.random_range(0..10))
+                .collect::<Vec<_>>();
+            let mut scramble = |digit: usize| {
+                let mut segments = DIGITS[digit]
+                    .chars()
+                    .map(|c| wiring[(c as u8 - b'a') as usize])
+                    .collect::<Vec<_>>();
+                segments.shuffle(&mut self.rng);
+                segments.into_iter().collect::<String>()
+            };
+            let mut patterns = (0..10).map(&mut scramble).collect::<Vec<_>>();
+            let output = output_digits
+                .into_iter()
+                .map(&mut scramble)
+                .collect::<Vec<_>>();
+            patterns.shuffle(&mut self.rng);
+            writeln!(text, "{} | {}", patterns.join(" "), output.join(" ")).unwrap();
+        }
+        text
+    }
+
+    // Most random grids of octopuses never all flash together, so keep trying
+    // until one does within `MAX_STEPS`, or give up and return the last try.
+    pub fn octopuses(&mut self, size: usize) -> String {
+        const TRIES: usize = 1000;
+        const MAX_STEPS: usize = 1000;
+        let mut grid = self.digit_grid(size, 0..=9);
+        for _ in 1..TRIES {
+            if synchronises(&grid, MAX_STEPS) {
+                break;
+            }
+            grid = self.digit_grid(size, 0..=9);
+        }
+        grid
+    }
+
+    // A `size` square of digits from `digits`.
+    pub fn digit_grid(&mut self, size: usize, digits: std::ops::RangeInclusive<u32>) -> String {
+        let mut text = String::new();
+        for _ in 0..size {
+            for _ in 0..size {
+                let digit = self.rng.random_range(digits.clone());
+                text.push(char::from_digit(digit, 10).unwrap());
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    // Walls of 9s with a lot of basins in between.
+    pub fn heightmap(&mut self, size: usize) -> String {
+        let size = size.max(5);
+        let mut text = String::new();
+        for _ in 0..size {
+            for _ in 0..size {
+                let height = if self.rng.random_bool(0.3) {
+                    9
+                } else {
+                    self.rng.random_range(0..9)
+                };
+                text.push(char::from_digit(height, 10).unwrap());
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    // Chunks that are either cut short or have a wrong closing character.
+    pub fn navigation(&mut self, count: usize) -> String {
+        const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+        let mut text = String::new();
+        for _ in 0..count {
+            let mut line = String::new();
+            let mut open = vec![];
+            for _ in 0..self.rng.random_range(20..110) {
+                if !open.is_empty() && self.rng.random_bool(0.4) {
+                    line.push(open.pop().unwrap());
+                } else {
+                    let (opening, closing) = PAIRS[self.rng.random_range(0..4)];
+                    line.push(opening);
+                    open.push(closing);
+                }
+            }
+            // Whether it ends up corrupted or incomplete, it has to end with
+            // a chunk still open.
+            if open.is_empty() {
+                line.push('(');
+                open.push(')');
+            }
+            if self.rng.random_bool(0.5) {
+                let expected = open.pop().unwrap();
+                let wrong = PAIRS.iter().map(|&(_, c)| c).find(|&c| c != expected);
+                line.push(wrong.unwrap());
+            }
+            writeln!(text, "{}", line).unwrap();
+        }
+        text
+    }
+
+    // `count` caves with no two big caves next to each other, so there are
+    // only finitely many paths.
+    pub fn caves(&mut self, count: usize) -> String {
+        let mut names = vec!["start".to_string(), "end".to_string()];
+        names.extend((0..count.max(1)).map(|index| {
+            if index % 2 == 0 {
+                format!("c{}", index)
+            } else {
+                format!("C{}", index)
+            }
+        }));
+        let is_big = |index: usize| index >= 2 && index % 2 == 1;
+
+        // Always a way through.
+        let mut tunnels = vec![(0, 2), (2, 1)];
+        for a in 0..names.len() {
+            for b in a + 1..names.len() {
+                if (a < 2 && b < 2)
+                    || (is_big(a) && is_big(b))
+                    || tunnels.contains(&(a, b))
+                    || tunnels.contains(&(b, a))
+                {
+                    continue;
+                }
+                if self.rng.random_bool(0.3) {
+                    tunnels.push((a, b));
+                }
+            }
+        }
+
+        let mut text = String::new();
+        for (a, b) in tunnels {
+            writeln!(text, "{}-{}", names[a], names[b]).unwrap();
+        }
+        text
+    }
+
+    // `count` dots on paper that folds in half, alternately left and up,
+    // down to the size of the code.
+    pub fn origami(&mut self, count: usize) -> String {
+        let (mut width, mut height) = (39_i64, 6_i64);
+        let mut folds = vec![];
+        while folds.is_empty() || width * height < count as i64 * 4 {
+            if folds.len() % 2 == 0 {
+                folds.push(format!("fold along x={}", width));
+                width = width * 2 + 1;
+            } else {
+                folds.push(format!("fold along y={}", height));
+                height = height * 2 + 1;
+            }
+        }
+        folds.reverse();
+
+        // Keep dots off the fold lines, which are in the middle of each
+        // half, quarter, ... down to the size of the code.
+        let on_fold = |position: i64, mut size: i64, code_size: i64| {
+            while size > code_size {
+                size /= 2;
+                if position % (size + 1) == size {
+                    return true;
+                }
+            }
+            false
+        };
+        let mut seen = std::collections::HashSet::new();
+        let mut dots = vec![];
+        while dots.len() < count {
+            let (x, y) = (
+                self.rng.random_range(0..width),
+                self.rng.random_range(0..height),
+            );
+            if !on_fold(x, width, 39) && !on_fold(y, height, 6) && seen.insert((x, y)) {
+                dots.push((x, y));
+            }
+        }
+
+        let mut text = String::new();
+        for (x, y) in dots {
+            writeln!(text, "{},{}", x, y).unwrap();
+        }
+        writeln!(text).unwrap();
+        for fold in folds {
+            writeln!(text, "{}", fold).unwrap();
+        }
+        text
+    }
+
+    // A template of `length` elements with a rule for every pair.
+    pub fn polymer(&mut self, length: usize) -> String {
+        let elements = b"BCFHKNOPSV";
+        let mut pick = || elements[self.rng.random_range(0..elements.len())] as char;
+        let mut text = (0..length.max(2)).map(|_| pick()).collect::<String>();
+        text.push_str("\n\n");
+        for &a in elements {
+            for &b in elements {
+                writeln!(text, "{}{} -> {}", a as char, b as char, pick()).unwrap();
+            }
+        }
+        text
+    }
+
+    // A target below and to the right, about `size` across.
+    pub fn target_area(&mut self, size: i64) -> String {
+        let size = size.max(5);
+        let x_min = self.rng.random_range(size..size * 3);
+        let y_max = -self.rng.random_range(size..size * 2);
+        format!(
+            "target area: x={}..{}, y={}..{}\n",
+            x_min,
+            x_min + size,
+            y_max - size / 2,
+            y_max
+        )
+    }
+
+    fn snailfish_value(&mut self, depth: usize) -> String {
+        if depth == 4 || (depth > 0 && self.rng.random_bool(0.3)) {
+            self.rng.random_range(0..10).to_string()
+        } else {
+            format!(
+                "[{},{}]",
+                self.snailfish_value(depth + 1),
+                self.snailfish_value(depth + 1)
+            )
+        }
+    }
+
+    // Reduced snailfish numbers.
+    pub fn snailfish(&mut self, count: usize) -> String {
+        let mut text = String::new();
+        for _ in 0..count.max(2) {
+            writeln!(text, "{}", self.snailfish_value(0)).unwrap();
+        }
+        text
+    }
+
+    // An enhancement algorithm and a `size` square image.  If the algorithm
+    // lights up the dark infinite background, it turns it off again.
+    pub fn trench_map(&mut self, size: usize) -> String {
+        let mut pixel = |p: f64| if self.rng.random_bool(p) { '#' } else { '.' };
+        let mut algorithm = (0..512).map(|_| pixel(0.5)).collect::<Vec<_>>();
+        if algorithm[0] == '#' {
+            algorithm[511] = '.';
+        }
+        let mut text = algorithm.into_iter().collect::<String>();
+        text.push_str("\n\n");
+        for _ in 0..size {
+            text.extend((0..size).map(|_| pixel(0.5)));
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn dice(&mut self) -> String {
+        format!(
+            "Player 1 starting position: {}\nPlayer 2 starting position: {}\n",
+            self.rng.random_range(1..=10),
+            self.rng.random_range(1..=10)
+        )
+    }
+
+    fn cuboid(&mut self, extent: i64, max_size: i64) -> String {
+        let mut range = |axis| {
+            let min = self.rng.random_range(-extent..extent);
+            let max = min + self.rng.random_range(0..max_size);
+            format!("{}={}..{}", axis, min, max)
+        };
+        format!("{},{},{}", range('x'), range('y'), range('z'))
+    }
+
+    // Starting with steps inside the initialization area, like the real
+    // input, then steps all over.
+    pub fn reboot_steps(&mut self, count: usize) -> String {
+        let mut text = String::new();
+        for index in 0..count {
+            let on = index == 0 || self.rng.random_bool(0.6);
+            let cuboid = if index < count / 20 + 1 {
+                self.cuboid(50, 50)
+            } else {
+                self.cuboid(100_000, 30_000)
+            };
+            writeln!(text, "{} {}", if on { "on" } else { "off" }, cuboid).unwrap();
+        }
+        text
+    }
+
+    // Two of each amphipod, shuffled between the rooms.
+    pub fn amphipods(&mut self) -> String {
+        let mut amphipods = "AABBCCDD".chars().collect::<Vec<_>>();
+        amphipods.shuffle(&mut self.rng);
+        let row = |row: &[char]| join(row, "#");
+        format!(
+            "#############\n#...........#\n###{}###\n  #{}#\n  #########\n",
+            row(&amphipods[..4]),
+            row(&amphipods[4..])
+        )
+    }
+
+    // A `size` square of sea cucumbers, about half of it full.
+    pub fn cucumbers(&mut self, size: usize) -> String {
+        let mut text = String::new();
+        for _ in 0..size {
+            for _ in 0..size {
+                text.push(match self.rng.random_range(0..4) {
+                    0 => '>',
+                    1 => 'v',
+                    _ => '.',
+                });
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_repeatable() {
+        for day in 1..=25 {
+            let size = default_size(day).min(20);
+            assert_eq!(
+                Generator::new(7).input(day, size),
+                Generator::new(7).input(day, size)
+            );
+        }
+        assert_ne!(Generator::new(1).depths(10), Generator::new(2).depths(10));
+        assert_eq!(Generator::new(1).input(16, 10), None);
+    }
+
+    #[test]
+    fn test_sizes() {
+        let mut generator = Generator::new(0);
+        assert_eq!(generator.depths(50).lines().count(), 50);
+        assert_eq!(generator.bingo(3).split("\n\n").count(), 4);
+        assert_eq!(generator.digit_grid(7, 1..=9).lines().count(), 7);
+        assert!(synchronises(&generator.octopuses(10), 1000));
+        let origami = generator.origami(1000);
+        let (dots, folds) = origami.split_once("\n\n").unwrap();
+        assert_eq!(dots.lines().count(), 1000);
+        assert!(folds.lines().all(|fold| fold.starts_with("fold along ")));
+    }
+}
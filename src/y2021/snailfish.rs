@@ -0,0 +1,366 @@
+use crate::core::arena::{Arena, NodeId};
+use crate::core::error::{AocError, Result};
+use std::fmt::{Debug, Display};
+use std::iter::Sum;
+use std::ops::Add;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Node {
+    Literal(u64),
+    Pair(NodeId, NodeId),
+}
+
+// A literal, along with the pair it's in and how many pairs deep it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Leaf {
+    id: NodeId,
+    parent: NodeId,
+    depth: usize,
+}
+
+// A snailfish number: a pair whose halves are each either a literal or
+// another pair.
+#[derive(Clone)]
+pub struct Number {
+    nodes: Arena<Node>,
+    root: NodeId,
+}
+
+impl Number {
+    fn literal(&self, id: NodeId) -> u64 {
+        match self.nodes[id] {
+            Node::Literal(val) => val,
+            Node::Pair(_, _) => panic!("node {} isn't a literal", id),
+        }
+    }
+
+    // The literals from left to right.
+    fn leaves(&self) -> Vec<Leaf> {
+        let mut leaves = vec![];
+        self.collect_leaves(self.root, self.root, 0, &mut leaves);
+        leaves
+    }
+
+    fn collect_leaves(&self, id: NodeId, parent: NodeId, depth: usize, leaves: &mut Vec<Leaf>) {
+        match self.nodes[id] {
+            Node::Literal(_) => leaves.push(Leaf { id, parent, depth }),
+            Node::Pair(left, right) => {
+                self.collect_leaves(left, id, depth + 1, leaves);
+                self.collect_leaves(right, id, depth + 1, leaves);
+            }
+        }
+    }
+
+    fn add_to_literal(&mut self, id: NodeId, amount: u64) {
+        if let Node::Literal(val) = &mut self.nodes[id] {
+            *val += amount;
+        }
+    }
+
+    // Explodes the leftmost pair of literals nested inside four pairs, if
+    // there is one.
+    fn explode(&mut self, leaves: &[Leaf]) -> bool {
+        let index = leaves
+            .windows(2)
+            .position(|pair| pair[0].depth > 4 && pair[0].parent == pair[1].parent);
+
+        if let Some(index) = index {
+            let (left, right) = (leaves[index], leaves[index + 1]);
+
+            if let Some(prev) = index.checked_sub(1) {
+                self.add_to_literal(leaves[prev].id, self.literal(left.id));
+            }
+            if let Some(next) = leaves.get(index + 2) {
+                self.add_to_literal(next.id, self.literal(right.id));
+            }
+
+            self.nodes.free(left.id);
+            self.nodes.free(right.id);
+            self.nodes[left.parent] = Node::Literal(0);
+            true
+        } else {
+            false
+        }
+    }
+
+    // Splits the leftmost literal of 10 or more, if there is one.
+    fn split(&mut self, leaves: &[Leaf]) -> bool {
+        if let Some(leaf) = leaves.iter().find(|leaf| self.literal(leaf.id) >= 10) {
+            let val = self.literal(leaf.id);
+            let left = self.nodes.alloc(Node::Literal(val / 2));
+            let right = self.nodes.alloc(Node::Literal(val - val / 2));
+            self.nodes[leaf.id] = Node::Pair(left, right);
+            true
+        } else {
+            false
+        }
+    }
+
+    // Explodes and splits until there's nothing left to explode or split.
+    // Adding numbers reduces the result already.
+    pub fn reduce(mut self) -> Number {
+        loop {
+            let leaves = self.leaves();
+            if !self.explode(&leaves) && !self.split(&leaves) {
+                break;
+            }
+        }
+
+        self
+    }
+
+    fn magnitude_of(&self, id: NodeId) -> u64 {
+        match self.nodes[id] {
+            Node::Literal(val) => val,
+            Node::Pair(left, right) => 3 * self.magnitude_of(left) + 2 * self.magnitude_of(right),
+        }
+    }
+
+    pub fn magnitude(&self) -> u64 {
+        self.magnitude_of(self.root)
+    }
+
+    // Copies the tree under `id` in `other` into this number's nodes.
+    fn graft(&mut self, other: &Number, id: NodeId) -> NodeId {
+        let node = match other.nodes[id] {
+            Node::Literal(val) => Node::Literal(val),
+            Node::Pair(left, right) => {
+                Node::Pair(self.graft(other, left), self.graft(other, right))
+            }
+        };
+        self.nodes.alloc(node)
+    }
+
+    fn same_as(&self, id: NodeId, other: &Number, other_id: NodeId) -> bool {
+        match (self.nodes[id], other.nodes[other_id]) {
+            (Node::Literal(a), Node::Literal(b)) => a == b,
+            (Node::Pair(left, right), Node::Pair(other_left, other_right)) => {
+                self.same_as(left, other, other_left) && self.same_as(right, other, other_right)
+            }
+            _ => false,
+        }
+    }
+
+    fn fmt_node(&self, id: NodeId, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.nodes[id] {
+            Node::Literal(val) => Display::fmt(&val, f),
+            Node::Pair(left, right) => {
+                write!(f, "[")?;
+                self.fmt_node(left, f)?;
+                write!(f, ",")?;
+                self.fmt_node(right, f)?;
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.same_as(self.root, other, other.root)
+    }
+}
+
+impl Eq for Number {}
+
+impl Add for Number {
+    type Output = Number;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        let right = self.graft(&rhs, rhs.root);
+        self.root = self.nodes.alloc(Node::Pair(self.root, right));
+        self.reduce()
+    }
+}
+
+// Panics if there aren't any numbers to add up.
+impl Sum<Number> for Number {
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Number>,
+    {
+        iter.reduce(Number::add).unwrap()
+    }
+}
+
+impl FromStr for Number {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parsing::number(s)
+    }
+}
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_node(self.root, f)
+    }
+}
+
+impl Debug for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+mod parsing {
+    use super::{Node, Number};
+
+    use crate::core::arena::{Arena, NodeId};
+    use crate::core::error::Result;
+    use crate::core::parsing::{integer, parse_all};
+    use nom::bytes::complete::tag;
+    use nom::IResult;
+
+    fn value<'a>(nodes: &mut Arena<Node>, input: &'a str) -> IResult<&'a str, NodeId> {
+        if let Ok((rest, val)) = integer::<u64>(input) {
+            Ok((rest, nodes.alloc(Node::Literal(val))))
+        } else {
+            pair(nodes, input)
+        }
+    }
+
+    fn pair<'a>(nodes: &mut Arena<Node>, input: &'a str) -> IResult<&'a str, NodeId> {
+        let (input, _) = tag("[")(input)?;
+        let (input, left) = value(nodes, input)?;
+        let (input, _) = tag(",")(input)?;
+        let (input, right) = value(nodes, input)?;
+        let (input, _) = tag("]")(input)?;
+        Ok((input, nodes.alloc(Node::Pair(left, right))))
+    }
+
+    pub(super) fn number(input: &str) -> Result<Number> {
+        let mut nodes = Arena::new();
+        let root = parse_all(|input| pair(&mut nodes, input), input)?;
+        Ok(Number { nodes, root })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Numbers nested up to `depth` pairs deep, with literals below `max`.
+    fn number(depth: u32, max: u64) -> impl Strategy<Value = Number> {
+        let literal = (0..max).prop_map(|val| val.to_string());
+        let value = literal.prop_recursive(depth - 1, 64, 2, |inner| {
+            (inner.clone(), inner).prop_map(|(left, right)| format!("[{},{}]", left, right))
+        });
+        (value.clone(), value)
+            .prop_map(|(left, right)| format!("[{},{}]", left, right).parse().unwrap())
+    }
+
+    fn depth(number: &Number) -> usize {
+        number.leaves().iter().map(|leaf| leaf.depth).max().unwrap()
+    }
+
+    fn literals(number: &Number) -> Vec<u64> {
+        number
+            .leaves()
+            .iter()
+            .map(|leaf| number.literal(leaf.id))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(number in number(6, 100)) {
+            prop_assert_eq!(number.to_string().parse::<Number>().unwrap(), number);
+        }
+
+        #[test]
+        fn test_reduce(left in number(4, 10), right in number(4, 10)) {
+            let sum = left + right;
+            prop_assert!(depth(&sum) <= 4, "{} is too deep", sum);
+            prop_assert!(literals(&sum).iter().all(|&literal| literal < 10), "{} needs splitting", sum);
+        }
+
+        #[test]
+        fn test_reduce_is_idempotent(number in number(4, 10)) {
+            prop_assert_eq!(number.clone().reduce(), number);
+        }
+    }
+
+    #[test]
+    fn test_explode() {
+        let mut number: Number = "[[[[[9,8],1],2],3],4]".parse().unwrap();
+        assert!(number.explode(&number.leaves()));
+        assert_eq!(&number.to_string(), "[[[[0,9],2],3],4]");
+        assert!(!number.explode(&number.leaves()));
+    }
+
+    #[test]
+    fn test_leaves() {
+        let number: Number = "[[[[0,7],4],[7,[[8,4],9]]],[1,1]]".parse().unwrap();
+
+        assert_eq!(literals(&number), [0, 7, 4, 7, 8, 4, 9, 1, 1]);
+        assert_eq!(
+            number
+                .leaves()
+                .iter()
+                .map(|leaf| leaf.depth)
+                .collect::<Vec<_>>(),
+            [4, 4, 3, 3, 5, 5, 4, 2, 2]
+        );
+    }
+
+    #[test]
+    fn test_add_reduce() {
+        let left: Number = "[[[[4,3],4],4],[7,[[8,4],9]]]".parse().unwrap();
+        let right: Number = "[1,1]".parse().unwrap();
+
+        let mut number = left.clone();
+        let right_root = number.graft(&right, right.root);
+        number.root = number.nodes.alloc(Node::Pair(number.root, right_root));
+        number.explode(&number.leaves());
+        assert_eq!(&number.to_string(), "[[[[0,7],4],[7,[[8,4],9]]],[1,1]]");
+
+        number.explode(&number.leaves());
+        assert_eq!(&number.to_string(), "[[[[0,7],4],[15,[0,13]]],[1,1]]");
+
+        assert_eq!(
+            &(left + right).to_string(),
+            "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"
+        );
+    }
+
+    #[test]
+    fn test_reduce_frees_nodes() {
+        let left: Number = "[[[[4,3],4],4],[7,[[8,4],9]]]".parse().unwrap();
+        let sum = left + "[1,1]".parse().unwrap();
+        assert_eq!(sum.nodes.len(), 2 * sum.leaves().len() - 1);
+    }
+
+    #[test]
+    fn test_sum() {
+        let sum = ["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]", "[6,6]"]
+            .iter()
+            .map(|number| number.parse::<Number>().unwrap())
+            .sum::<Number>();
+        assert_eq!(sum.to_string(), "[[[[5,0],[7,4]],[5,5]],[6,6]]");
+    }
+
+    #[test]
+    fn test_magnitude() {
+        for (number, magnitude) in [
+            ("[9,1]", 29),
+            ("[[1,2],[[3,4],5]]", 143),
+            (
+                "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]",
+                3488,
+            ),
+        ] {
+            assert_eq!(number.parse::<Number>().unwrap().magnitude(), magnitude);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for number in ["", "5", "[1]", "[1,2", "[1,2]x", "[[1,2],-3]"] {
+            assert!(number.parse::<Number>().is_err(), "parsed {:?}", number);
+        }
+        assert!("[1,2]\n".parse::<Number>().is_ok());
+    }
+}
@@ -0,0 +1,296 @@
+use std::fmt::{Display, Write};
+
+// An integer expression over variables of type `V`, with the operations of
+// the day 24 ALU: truncating division, remainder, and equality giving 1 or 0.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Expression<V> {
+    Constant(i64),
+    Variable(V),
+    Add(Box<Expression<V>>, Box<Expression<V>>),
+    Mul(Box<Expression<V>>, Box<Expression<V>>),
+    Div(Box<Expression<V>>, Box<Expression<V>>),
+    Mod(Box<Expression<V>>, Box<Expression<V>>),
+    Eql(Box<Expression<V>>, Box<Expression<V>>),
+}
+
+impl<V> Expression<V> {
+    pub fn constant(value: i64) -> Self {
+        Expression::Constant(value)
+    }
+
+    pub fn variable(variable: V) -> Self {
+        Expression::Variable(variable)
+    }
+
+    pub fn sum(x: Self, y: Self) -> Self {
+        Expression::Add(Box::new(x), Box::new(y))
+    }
+
+    pub fn product(x: Self, y: Self) -> Self {
+        Expression::Mul(Box::new(x), Box::new(y))
+    }
+
+    pub fn quotient(x: Self, y: Self) -> Self {
+        Expression::Div(Box::new(x), Box::new(y))
+    }
+
+    pub fn remainder(x: Self, y: Self) -> Self {
+        Expression::Mod(Box::new(x), Box::new(y))
+    }
+
+    pub fn equals(x: Self, y: Self) -> Self {
+        Expression::Eql(Box::new(x), Box::new(y))
+    }
+
+    fn operands(&self) -> Option<(&Self, &Self)> {
+        use Expression::*;
+        match self {
+            Add(x, y) | Mul(x, y) | Div(x, y) | Mod(x, y) | Eql(x, y) => Some((x, y)),
+            Constant(_) | Variable(_) => None,
+        }
+    }
+
+    fn operands_mut(&mut self) -> Option<(&mut Self, &mut Self)> {
+        use Expression::*;
+        match self {
+            Add(x, y) | Mul(x, y) | Div(x, y) | Mod(x, y) | Eql(x, y) => Some((x, y)),
+            Constant(_) | Variable(_) => None,
+        }
+    }
+
+    pub fn is_constant(&self, value: i64) -> bool {
+        matches!(self, Expression::Constant(c) if *c == value)
+    }
+
+    pub fn is_compound(&self) -> bool {
+        self.operands().is_some()
+    }
+
+    // The number of nodes in the expression tree.
+    pub fn size(&self) -> usize {
+        match self.operands() {
+            Some((x, y)) => 1 + x.size() + y.size(),
+            None => 1,
+        }
+    }
+
+    // The value of the expression, or `None` if a variable has no value or
+    // the arithmetic overflows or divides by zero.
+    pub fn evaluate<F>(&self, value_of: &F) -> Option<i64>
+    where
+        F: Fn(&V) -> Option<i64>,
+    {
+        use Expression::*;
+        match self {
+            Constant(c) => Some(*c),
+            Variable(v) => value_of(v),
+            Add(x, y) => x.evaluate(value_of)?.checked_add(y.evaluate(value_of)?),
+            Mul(x, y) => x.evaluate(value_of)?.checked_mul(y.evaluate(value_of)?),
+            Div(x, y) => x.evaluate(value_of)?.checked_div(y.evaluate(value_of)?),
+            Mod(x, y) => x.evaluate(value_of)?.checked_rem(y.evaluate(value_of)?),
+            Eql(x, y) => Some((x.evaluate(value_of)? == y.evaluate(value_of)?) as i64),
+        }
+    }
+
+    // Simplifies the expression bottom up: folds operations on constants and
+    // drops additions of 0 and multiplications or divisions by 1.
+    pub fn normalize(&mut self) {
+        use Expression::*;
+        if let Some((x, y)) = self.operands_mut() {
+            x.normalize();
+            y.normalize();
+        }
+
+        if let Some((&Constant(_), &Constant(_))) = self.operands() {
+            if let Some(value) = self.evaluate(&|_| None) {
+                *self = Constant(value);
+                return;
+            }
+        }
+
+        let replacement = match self {
+            Add(x, y) if x.is_constant(0) => Some(std::mem::replace(&mut **y, Constant(0))),
+            Add(x, y) if y.is_constant(0) => Some(std::mem::replace(&mut **x, Constant(0))),
+            Mul(x, y) if x.is_constant(0) || y.is_constant(0) => Some(Constant(0)),
+            Mul(x, y) if x.is_constant(1) => Some(std::mem::replace(&mut **y, Constant(0))),
+            Mul(x, y) | Div(x, y) if y.is_constant(1) => {
+                Some(std::mem::replace(&mut **x, Constant(0)))
+            }
+            _ => None,
+        };
+
+        if let Some(replacement) = replacement {
+            *self = replacement;
+        }
+    }
+}
+
+impl<V: PartialEq + Clone> Expression<V> {
+    // Replaces every occurrence of `variable` with `expression`.
+    pub fn substitute(&mut self, variable: &V, expression: &Expression<V>) {
+        match self {
+            Expression::Variable(v) if v == variable => *self = expression.clone(),
+            _ => {
+                if let Some((x, y)) = self.operands_mut() {
+                    x.substitute(variable, expression);
+                    y.substitute(variable, expression);
+                }
+            }
+        }
+    }
+
+    // Given the expression in terms of the variables after the `assignments`
+    // have run in order, rewrites it in terms of the variables before.
+    pub fn expand(&mut self, assignments: &[(V, Expression<V>)]) {
+        for (variable, expression) in assignments.iter().rev() {
+            self.substitute(variable, expression);
+        }
+    }
+}
+
+impl<V> From<i64> for Expression<V> {
+    fn from(value: i64) -> Self {
+        Expression::Constant(value)
+    }
+}
+
+fn write_operand<W: Write, V: Display>(mut w: W, x: &Expression<V>) -> std::fmt::Result {
+    if x.is_compound() {
+        write!(w, "({})", x)
+    } else {
+        write!(w, "{}", x)
+    }
+}
+
+fn write_binary_op<W: Write, V: Display>(
+    mut w: W,
+    op: &'static str,
+    x: &Expression<V>,
+    y: &Expression<V>,
+) -> std::fmt::Result {
+    write_operand(&mut w, x)?;
+    write!(w, " {} ", op)?;
+    write_operand(&mut w, y)
+}
+
+impl<V: Display> Display for Expression<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Expression::*;
+        match self {
+            Constant(c) => write!(f, "{}", c),
+            Variable(v) => write!(f, "{}", v),
+            Add(x, y) => write_binary_op(f, "+", x, y),
+            Mul(x, y) => write_binary_op(f, "*", x, y),
+            Div(x, y) => write_binary_op(f, "/", x, y),
+            Mod(x, y) => write_binary_op(f, "%", x, y),
+            Eql(x, y) => write_binary_op(f, "==", x, y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    type Exp = Expression<&'static str>;
+
+    fn var(name: &'static str) -> Exp {
+        Expression::variable(name)
+    }
+
+    fn expression() -> impl Strategy<Value = Exp> {
+        let leaf = prop_oneof![
+            (-3..30_i64).prop_map(Expression::constant),
+            prop::sample::select(vec!["x", "y", "z"]).prop_map(Expression::variable),
+        ];
+        leaf.prop_recursive(4, 32, 2, |inner| {
+            (0..5_u8, inner.clone(), inner).prop_map(|(op, x, y)| match op {
+                0 => Expression::sum(x, y),
+                1 => Expression::product(x, y),
+                2 => Expression::quotient(x, y),
+                3 => Expression::remainder(x, y),
+                _ => Expression::equals(x, y),
+            })
+        })
+    }
+
+    fn value_of(name: &&'static str) -> Option<i64> {
+        match *name {
+            "x" => Some(7),
+            "y" => Some(-2),
+            "z" => Some(26),
+            _ => None,
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_normalize_keeps_value(exp in expression()) {
+            let mut normalized = exp.clone();
+            normalized.normalize();
+            prop_assert!(normalized.size() <= exp.size());
+            if let Some(value) = exp.evaluate(&value_of) {
+                prop_assert_eq!(normalized.evaluate(&value_of), Some(value));
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut exp = Expression::product(var("x"), 0.into());
+        exp.normalize();
+        assert_eq!(exp, Expression::constant(0));
+
+        let mut exp = Expression::sum(Expression::product(2.into(), 3.into()), var("x"));
+        exp.normalize();
+        assert_eq!(exp, Expression::sum(6.into(), var("x")));
+    }
+
+    #[test]
+    fn test_normalize_large() {
+        let mut exp = Expression::equals(
+            Expression::equals(
+                Expression::sum(
+                    Expression::remainder(
+                        Expression::sum(Expression::product(var("x"), 0.into()), var("z")),
+                        26.into(),
+                    ),
+                    var("a"),
+                ),
+                var("w"),
+            ),
+            0.into(),
+        );
+        assert_eq!(exp.size(), 13);
+        exp.normalize();
+        assert_eq!(
+            exp,
+            Expression::equals(
+                Expression::equals(
+                    Expression::sum(Expression::remainder(var("z"), 26.into()), var("a")),
+                    var("w")
+                ),
+                0.into()
+            )
+        );
+        assert_eq!(exp.size(), 9);
+        assert_eq!(exp.to_string(), "(((z % 26) + a) == w) == 0");
+    }
+
+    #[test]
+    fn test_expand() {
+        // x = w; x = x * 2; y = x + 1
+        let assignments = [
+            ("x", var("w")),
+            ("x", Expression::product(var("x"), 2.into())),
+            ("y", Expression::sum(var("x"), 1.into())),
+        ];
+        let mut exp = Expression::sum(var("y"), var("x"));
+        exp.expand(&assignments);
+        assert_eq!(exp.to_string(), "((w * 2) + 1) + (w * 2)");
+
+        let value_of = |name: &&str| (*name == "w").then_some(5);
+        assert_eq!(exp.evaluate(&value_of), Some(21));
+    }
+}
@@ -0,0 +1,92 @@
+use aoc2021::core::config::Config;
+use aoc2021::core::registry;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use toml::{Table, Value};
+
+fn load_answers() -> Table {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml");
+    fs::read_to_string(path).unwrap().parse().unwrap()
+}
+
+fn answer_to_string(value: &Value) -> String {
+    match value {
+        Value::String(answer) => answer.clone(),
+        Value::Integer(answer) => answer.to_string(),
+        _ => panic!("Unsupported answer {:?}", value),
+    }
+}
+
+fn printed_answer_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(answer) => answer.clone(),
+        answer => answer.to_string(),
+    }
+}
+
+fn run_day(day: &str, binary: &str, input: &Path) -> serde_json::Value {
+    let output = Command::new(binary)
+        .arg("--json")
+        .arg(input)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{} failed: {}",
+        day,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn check_day(day: &str, binary: &str) {
+    let answers = load_answers();
+    let expected = match answers.get(day).and_then(Value::as_table) {
+        Some(expected) => expected,
+        None => return,
+    };
+
+    let (year, number) = registry::YEARS
+        .iter()
+        .flat_map(|year| year.days)
+        .find(|found| found.bin == day)
+        .map(|found| (found.year, found.number))
+        .unwrap();
+    let input =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(Config::default().input_path(year, number));
+    if !input.exists() {
+        eprintln!("Skipping {}: no input at {}", day, input.display());
+        return;
+    }
+
+    let printed = run_day(day, binary, &input);
+    for part in ["part1", "part2"] {
+        if let Some(answer) = expected.get(part) {
+            assert_eq!(
+                printed_answer_to_string(&printed[part]),
+                answer_to_string(answer),
+                "{} {}",
+                day,
+                part
+            );
+        }
+    }
+}
+
+macro_rules! days {
+    ($($day:ident),* $(,)?) => {
+        $(
+            #[test]
+            fn $day() {
+                check_day(stringify!($day), env!(concat!("CARGO_BIN_EXE_", stringify!($day))));
+            }
+        )*
+    };
+}
+
+days!(
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+);
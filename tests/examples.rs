@@ -0,0 +1,71 @@
+// Checks each day against the examples from the puzzle text, kept under
+// tests/data/ so they can run without anyone's personal inputs.  Day 19's
+// example is too long to copy out reliably and day 24 doesn't have one.
+use aoc2021::core::answer::Answer;
+use aoc2021::core::error::Result;
+use std::fs;
+use std::path::Path;
+
+fn check_example(
+    file: &str,
+    solve: fn(&str) -> Result<(Answer, Answer)>,
+    part1: Option<&str>,
+    part2: Option<&str>,
+) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("data")
+        .join(file);
+    let input = fs::read_to_string(&path).unwrap();
+    let (answer1, answer2) = solve(&input).unwrap();
+
+    if let Some(part1) = part1 {
+        assert_eq!(answer1.to_string(), part1, "{} part1", file);
+    }
+    if let Some(part2) = part2 {
+        assert_eq!(answer2.to_string(), part2, "{} part2", file);
+    }
+}
+
+macro_rules! examples {
+    ($($test:ident: $day:ident($file:literal) => ($part1:expr, $part2:expr)),* $(,)?) => {
+        $(
+            #[test]
+            fn $test() {
+                check_example($file, aoc2021::y2021::$day::solve, $part1, $part2);
+            }
+        )*
+    };
+}
+
+examples!(
+    day01: day01("day01.txt") => (Some("7"), Some("5")),
+    day02: day02("day02.txt") => (Some("150"), Some("900")),
+    day03: day03("day03.txt") => (Some("198"), Some("230")),
+    day04: day04("day04.txt") => (Some("4512"), Some("1924")),
+    day05: day05("day05.txt") => (Some("5"), Some("12")),
+    day06: day06("day06.txt") => (Some("5934"), Some("26984457539")),
+    day07: day07("day07.txt") => (Some("37"), Some("168")),
+    day08: day08("day08.txt") => (Some("26"), Some("61229")),
+    day09: day09("day09.txt") => (Some("15"), Some("1134")),
+    day10: day10("day10.txt") => (Some("26397"), Some("288957")),
+    day11: day11("day11.txt") => (Some("1656"), Some("195")),
+    day12: day12("day12.txt") => (Some("10"), Some("36")),
+    // Part 2 draws a square rather than any letters; see the snapshot tests.
+    day13: day13("day13.txt") => (Some("17"), None),
+    day14: day14("day14.txt") => (Some("1588"), Some("2188189693529")),
+    day15: day15("day15.txt") => (Some("40"), Some("315")),
+    day16_version_sum_16: day16("day16-1.txt") => (Some("16"), None),
+    day16_version_sum_31: day16("day16-2.txt") => (Some("31"), None),
+    day16_sum: day16("day16-3.txt") => (None, Some("3")),
+    day16_equal: day16("day16-4.txt") => (None, Some("1")),
+    day17: day17("day17.txt") => (Some("45"), Some("112")),
+    day18: day18("day18.txt") => (Some("4140"), Some("3993")),
+    day18_flat: day18_flat("day18.txt") => (Some("4140"), Some("3993")),
+    day20: day20("day20.txt") => (Some("35"), Some("3351")),
+    day21: day21("day21.txt") => (Some("739785"), Some("444356092776315")),
+    day22: day22("day22.txt") => (Some("39"), Some("39")),
+    day23: day23("day23.txt") => (Some("12521"), Some("44169")),
+    day25: day25("day25.txt") => (Some("58"), None),
+    day25_2: day25_2("day25.txt") => (Some("58"), None),
+);